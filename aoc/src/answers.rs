@@ -0,0 +1,247 @@
+use helpers::{
+    parse::{InputFile, Span},
+    ParseError, Part,
+};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+const HEADER: &str = "\
+# Known answers, checked by `aoc verify` and updated by `aoc record`.
+# One answer per line: day, part, input file and the expected answer, separated by tabs.
+# Input files are relative to the workspace root. In answers, `\\n` is a newline,
+# `\\t` a tab and `\\\\` a backslash.";
+
+/// The `answers.txt` at the workspace root.
+pub fn default_path() -> PathBuf {
+    workspace_root().join("answers.txt")
+}
+
+pub fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+/// How an input file is named in the registry: relative to the workspace root when
+/// it's inside it, so the file works from any checkout.
+pub fn input_name(path: &str) -> String {
+    let root = workspace_root();
+    let relative = match (Path::new(path).canonicalize(), root.canonicalize()) {
+        (Ok(path), Ok(root)) => path.strip_prefix(root).ok().map(Path::to_path_buf),
+        _ => None,
+    };
+    match relative {
+        Some(relative) => relative.to_string_lossy().into_owned(),
+        None => path.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub day: u8,
+    pub part: Part,
+    /// Input file, relative to the workspace root unless it's absolute.
+    pub input: String,
+    pub expected: String,
+}
+
+impl Entry {
+    pub fn input_path(&self) -> PathBuf {
+        workspace_root().join(&self.input)
+    }
+
+    fn parse(line: Span) -> Result<Self, ParseError> {
+        let mut fields = line.split('\t');
+        let day = line.next_from(&mut fields, "a day")?;
+        let day = match day.parse("a day")? {
+            d @ 1..=25 => d,
+            _ => return Err(day.error("a day between 1 and 25")),
+        };
+        let part = line.next_from(&mut fields, "a part")?;
+        let part = match part.as_str() {
+            "1" => Part::One,
+            "2" => Part::Two,
+            _ => return Err(part.error("part 1 or 2")),
+        };
+        let input = line.next_from(&mut fields, "an input file")?;
+        if input.is_empty() {
+            return Err(input.error("an input file"));
+        }
+        let expected = line.next_from(&mut fields, "an expected answer")?;
+        line.expect_end(&mut fields)?;
+
+        Ok(Self {
+            day,
+            part,
+            input: input.as_str().to_string(),
+            expected: unescape(expected)?,
+        })
+    }
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}",
+            self.day,
+            self.part,
+            self.input,
+            escape(&self.expected)
+        )
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Registry {
+    entries: Vec<Entry>,
+}
+
+impl Registry {
+    pub fn load(path: &Path) -> Result<Self, ParseError> {
+        let file = InputFile::open(&path.to_string_lossy())?;
+        Self::parse(&file)
+    }
+
+    /// Like `load`, but a missing file is just an empty registry.
+    pub fn load_or_default(path: &Path) -> Result<Self, ParseError> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn parse(file: &InputFile) -> Result<Self, ParseError> {
+        let entries = file
+            .lines()
+            .filter(|l| !l.trim().is_empty() && !l.as_str().starts_with('#'))
+            .map(Entry::parse)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { entries })
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Adds an answer, replacing any existing one for the same day, part and input.
+    /// Returns the answer that was replaced.
+    pub fn record(&mut self, entry: Entry) -> Option<String> {
+        let existing = self
+            .entries
+            .iter_mut()
+            .find(|e| (e.day, e.part, &e.input) == (entry.day, entry.part, &entry.input));
+        match existing {
+            Some(existing) => Some(std::mem::replace(existing, entry).expected),
+            None => {
+                self.entries.push(entry);
+                self.entries.sort_by_key(|e| (e.day, e.part as u8));
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+}
+
+impl Display for Registry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        for entry in &self.entries {
+            writeln!(f, "{}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+fn escape(answer: &str) -> String {
+    answer
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+}
+
+fn unescape(span: Span) -> Result<String, ParseError> {
+    let mut answer = String::new();
+    let mut chars = span.chars();
+    while let Some((c, at)) = chars.next() {
+        if c != '\\' {
+            answer.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some(('n', _)) => answer.push('\n'),
+            Some(('t', _)) => answer.push('\t'),
+            Some(('\\', _)) => answer.push('\\'),
+            Some((_, bad)) => return Err(bad.error("`n`, `t` or `\\` after a backslash")),
+            None => return Err(at.missing("an escaped character")),
+        }
+    }
+    Ok(answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: u8, part: Part, input: &str, expected: &str) -> Entry {
+        Entry {
+            day,
+            part,
+            input: input.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut registry = Registry::default();
+        registry.record(entry(13, Part::Two, "day13/input.txt", "\n# #\t\\\n"));
+        registry.record(entry(1, Part::One, "inputs/alice/day01.txt", "1521"));
+
+        let text = registry.to_string();
+        assert!(text.contains("1\t1\tinputs/alice/day01.txt\t1521\n"));
+        assert!(text.contains("13\t2\tday13/input.txt\t\\n# #\\t\\\\\\n\n"));
+
+        let parsed = Registry::parse(&InputFile::from_text("answers.txt", &text)).unwrap();
+        assert_eq!(registry, parsed);
+        assert_eq!(1, parsed.entries()[0].day);
+    }
+
+    #[test]
+    fn test_record_replaces() {
+        let mut registry = Registry::default();
+        assert_eq!(None, registry.record(entry(2, Part::One, "a.txt", "1")));
+        assert_eq!(None, registry.record(entry(2, Part::One, "b.txt", "2")));
+        assert_eq!(
+            Some(String::from("1")),
+            registry.record(entry(2, Part::One, "a.txt", "3"))
+        );
+        assert_eq!(2, registry.entries().len());
+        assert_eq!("3", registry.entries()[0].expected);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parse = |text| Registry::parse(&InputFile::from_text("answers.txt", text));
+        assert_eq!(
+            "part 1 or 2",
+            parse("# comment\n\n4\t3\tday04/input.txt\t10")
+                .unwrap_err()
+                .expected
+        );
+        assert_eq!(
+            "an expected answer",
+            parse("4\t1\tday04/input.txt").unwrap_err().expected
+        );
+        assert_eq!(
+            (1, 22),
+            parse("4\t1\tday04/input.txt\t\\x")
+                .map_err(|e| (e.line, e.column))
+                .unwrap_err()
+        );
+    }
+}
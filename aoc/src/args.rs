@@ -0,0 +1,566 @@
+use helpers::{frames, Part};
+
+pub const USAGE: &str = "Usage:
+    aoc run <DAYS> [--part <1|2>] [--input <PATH>] [--threads <N>] [--stream]
+    aoc bench <DAYS> [--part <1|2>] [--input <PATH>] [--threads <N>] [--stream]
+              [--warmup <N>] [--iterations <N>] [--format <table|json|csv>]
+              [--output <PATH>]
+    aoc verify [DAYS] [--answers <PATH>]
+    aoc record <DAYS> [--part <1|2>] [--input <PATH>] [--stream] [--answers <PATH>]
+    aoc animate <DAY> [--part <1|2>] [--input <PATH>] [--format <ansi|pbm|pgm|gif>]
+                [--output <PATH>] [--scale <N>] [--delay <MS>]
+    aoc gen <DAY> [--size <N>] [--seed <N>] [--output <PATH>]
+    aoc help
+
+DAYS is a single day (15), an inclusive range (3..7 or 3-7), a comma separated
+list (1,4,9) or `all`. --input may only be given when running a single day, and
+`--input -` reads the input from stdin.
+
+Without --input, each day reads dayNN/input.txt from the input cache: the current
+directory, or AOC_INPUT_DIR if it's set. Inputs missing from the cache are downloaded
+from AOC_BASE_URL (https://adventofcode.com/2021 by default) using the session
+token in AOC_SESSION, and are never downloaded again once cached.
+
+--threads (or AOC_THREADS) splits the slowest loops of days 7, 17, 19 and 20
+across N threads, 0 meaning one per core, and lets run solve several days at once,
+printing them when they've all finished. Days run one at a time on a single thread
+by default, which keeps their timings comparable.
+
+--stream has days 1, 2, 3, 5, 10 and 22 read their input a line at a time and
+answer both parts as they go, rather than reading it all in first, so they can
+take inputs bigger than memory. Their time then counts as parsing. Other days
+run as usual.
+
+Solvers that trace their progress write it to stderr when AOC_TRACE is `text` or
+`json` (one JSON object per line), or to the file named by AOC_TRACE_FILE.
+
+bench runs each part --warmup times (default 3) untimed, then --iterations times
+(default 10), and reports min/median/mean/stddev of parse and solve time separately.
+Results are printed as a table unless --format asks for JSON or CSV, and go to
+stdout unless --output names a file.
+
+verify checks every answer in the answers file (answers.txt at the workspace root
+unless --answers says otherwise), optionally only for DAYS. record solves DAYS and
+saves the answers there, replacing any previous answer for the same input.
+
+animate records a frame for each step of a day that draws them (11, 13 and 20) and
+plays them in the terminal (ansi, the default), writes one image per frame into
+the --output directory (pbm or pgm), or writes an animated --output file (gif).
+Each cell is --scale pixels across, picked to suit the image size by default, and
+frames are --delay milliseconds apart (default 100).
+
+gen writes a made-up input for DAY, shaped like the real one but as large as you
+like, to stdout or the --output file, e.g. `aoc gen 15 --size 1000 | aoc bench 15
+--input -`. --size counts whatever the day's input is made of (readings, boards,
+cells across a grid, ...) and by default gives an input worth timing, while the
+same --seed (default 2021) always gives the same input. Days 7, 9, 10 and 17 turn
+down a --size whose answers could outgrow their types, day 2 more commands than a
+script can run, day 3 more readings than fit in 63 bits, and day 12 more small
+caves than there are two-letter names.";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(RunArgs),
+    Bench(BenchArgs),
+    Verify(VerifyArgs),
+    Record(RecordArgs),
+    Animate(AnimateArgs),
+    Gen(GenArgs),
+    Help,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RunArgs {
+    pub days: Days,
+    pub parts: Vec<Part>,
+    pub input: Option<String>,
+    pub threads: Option<usize>,
+    /// Whether days that can should solve their input a line at a time.
+    pub stream: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BenchArgs {
+    pub run: RunArgs,
+    pub warmup: usize,
+    pub iterations: usize,
+    pub format: Format,
+    pub output: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct VerifyArgs {
+    pub days: Days,
+    pub answers: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RecordArgs {
+    pub run: RunArgs,
+    pub answers: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AnimateArgs {
+    pub run: RunArgs,
+    pub format: frames::Format,
+    pub output: Option<String>,
+    pub scale: Option<usize>,
+    pub delay: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct GenArgs {
+    pub day: u8,
+    pub size: Option<usize>,
+    pub seed: u64,
+    pub output: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Days {
+    All,
+    Only(Vec<u8>),
+}
+
+impl Command {
+    /// The thread count asked for, if this command runs any days.
+    pub fn threads(&self) -> Option<usize> {
+        match self {
+            Command::Run(run) => run.threads,
+            Command::Bench(bench) => bench.run.threads,
+            Command::Record(record) => record.run.threads,
+            Command::Animate(animate) => animate.run.threads,
+            Command::Verify(_) | Command::Gen(_) | Command::Help => None,
+        }
+    }
+}
+
+impl Days {
+    pub fn is_single(&self) -> bool {
+        matches!(self, Days::Only(days) if days.len() == 1)
+    }
+}
+
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        Some("run") => parse_run(args).map(Command::Run),
+        Some("bench") => parse_bench(args).map(Command::Bench),
+        Some("verify") => parse_verify(args).map(Command::Verify),
+        Some("record") => parse_record(args).map(Command::Record),
+        Some("animate") => parse_animate(args).map(Command::Animate),
+        Some("gen") => parse_gen(args).map(Command::Gen),
+        Some("help") | Some("--help") | Some("-h") | None => Ok(Command::Help),
+        Some(other) => Err(format!("Unknown command `{}`", other)),
+    }
+}
+
+fn parse_run<I: Iterator<Item = String>>(mut args: I) -> Result<RunArgs, String> {
+    let mut days = None;
+    let mut parts = vec![Part::One, Part::Two];
+    let mut input = None;
+    let mut threads = None;
+    let mut stream = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => parts = vec![parse_part(&expect_value(&arg, args.next())?)?],
+            "--input" | "-i" => input = Some(expect_value(&arg, args.next())?),
+            "--threads" | "-j" => threads = Some(parse_count(&arg, args.next())?),
+            "--stream" => stream = true,
+            _ if days.is_none() && !arg.starts_with('-') => days = Some(parse_days(&arg)?),
+            _ => return Err(format!("Unexpected argument `{}`", arg)),
+        }
+    }
+
+    let days = days.ok_or("Missing which days to run")?;
+    if input.is_some() && !days.is_single() {
+        return Err(String::from(
+            "--input can only be used when running a single day",
+        ));
+    }
+
+    Ok(RunArgs {
+        days,
+        parts,
+        input,
+        threads,
+        stream,
+    })
+}
+
+fn parse_bench<I: Iterator<Item = String>>(mut args: I) -> Result<BenchArgs, String> {
+    let mut warmup = 3;
+    let mut iterations = 10;
+    let mut format = Format::Table;
+    let mut output = None;
+    let mut run_args = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--warmup" | "-w" => warmup = parse_count(&arg, args.next())?,
+            "--iterations" | "-n" => iterations = parse_count(&arg, args.next())?,
+            "--format" | "-f" => format = parse_format(&expect_value(&arg, args.next())?)?,
+            "--output" | "-o" => output = Some(expect_value(&arg, args.next())?),
+            _ => run_args.push(arg),
+        }
+    }
+
+    if iterations == 0 {
+        return Err(String::from("--iterations must be at least 1"));
+    }
+
+    Ok(BenchArgs {
+        run: parse_run(run_args.into_iter())?,
+        warmup,
+        iterations,
+        format,
+        output,
+    })
+}
+
+fn parse_verify<I: Iterator<Item = String>>(mut args: I) -> Result<VerifyArgs, String> {
+    let mut days = None;
+    let mut answers = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" | "-a" => answers = Some(expect_value(&arg, args.next())?),
+            _ if days.is_none() && !arg.starts_with('-') => days = Some(parse_days(&arg)?),
+            _ => return Err(format!("Unexpected argument `{}`", arg)),
+        }
+    }
+
+    Ok(VerifyArgs {
+        days: days.unwrap_or(Days::All),
+        answers,
+    })
+}
+
+fn parse_record<I: Iterator<Item = String>>(mut args: I) -> Result<RecordArgs, String> {
+    let mut answers = None;
+    let mut run_args = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" | "-a" => answers = Some(expect_value(&arg, args.next())?),
+            _ => run_args.push(arg),
+        }
+    }
+
+    let run = parse_run(run_args.into_iter())?;
+    if run.input.as_deref() == Some("-") {
+        return Err(String::from(
+            "answers can't be recorded for input read from stdin",
+        ));
+    }
+    Ok(RecordArgs { run, answers })
+}
+
+fn parse_animate<I: Iterator<Item = String>>(mut args: I) -> Result<AnimateArgs, String> {
+    let mut format = frames::Format::Ansi;
+    let mut output = None;
+    let mut scale = None;
+    let mut delay = 100;
+    let mut run_args = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" | "-f" => format = expect_value(&arg, args.next())?.parse()?,
+            "--output" | "-o" => output = Some(expect_value(&arg, args.next())?),
+            "--scale" | "-s" => scale = Some(parse_count(&arg, args.next())?),
+            "--delay" | "-d" => delay = parse_count(&arg, args.next())? as u64,
+            _ => run_args.push(arg),
+        }
+    }
+
+    let run = parse_run(run_args.into_iter())?;
+    if !run.days.is_single() {
+        return Err(String::from("animate works on a single day"));
+    }
+    if run.stream {
+        return Err(String::from("animate can't --stream, it needs every step"));
+    }
+    if scale == Some(0) {
+        return Err(String::from("--scale must be at least 1"));
+    }
+    if format != frames::Format::Ansi && output.is_none() {
+        return Err(String::from("--output is needed for pbm, pgm and gif"));
+    }
+    Ok(AnimateArgs {
+        run,
+        format,
+        output,
+        scale,
+        delay,
+    })
+}
+
+fn parse_gen<I: Iterator<Item = String>>(mut args: I) -> Result<GenArgs, String> {
+    let mut day = None;
+    let mut size = None;
+    let mut seed = 2021;
+    let mut output = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" | "-s" => size = Some(parse_count(&arg, args.next())?),
+            "--seed" => seed = parse_count(&arg, args.next())? as u64,
+            "--output" | "-o" => output = Some(expect_value(&arg, args.next())?),
+            _ if day.is_none() && !arg.starts_with('-') => day = Some(parse_day(&arg)?),
+            _ => return Err(format!("Unexpected argument `{}`", arg)),
+        }
+    }
+
+    if size == Some(0) {
+        return Err(String::from("--size must be at least 1"));
+    }
+    Ok(GenArgs {
+        day: day.ok_or("Missing which day to generate an input for")?,
+        size,
+        seed,
+        output,
+    })
+}
+
+fn parse_count(flag: &str, value: Option<String>) -> Result<usize, String> {
+    let value = expect_value(flag, value)?;
+    value
+        .parse()
+        .map_err(|_| format!("{} must be a whole number, not `{}`", flag, value))
+}
+
+fn parse_format(s: &str) -> Result<Format, String> {
+    match s {
+        "table" => Ok(Format::Table),
+        "json" => Ok(Format::Json),
+        "csv" => Ok(Format::Csv),
+        _ => Err(format!("Format must be table, json or csv, not `{}`", s)),
+    }
+}
+
+fn expect_value(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("Missing value for {}", flag))
+}
+
+fn parse_part(s: &str) -> Result<Part, String> {
+    match s {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("Part must be 1 or 2, not `{}`", s)),
+    }
+}
+
+pub fn parse_days(s: &str) -> Result<Days, String> {
+    if s == "all" {
+        return Ok(Days::All);
+    }
+
+    let mut days = Vec::new();
+    for item in s.split(',') {
+        let range = item.split_once("..").or_else(|| item.split_once('-'));
+        match range {
+            Some((start, end)) => {
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                if start > end {
+                    return Err(format!("Day range `{}` is backwards", item));
+                }
+                days.extend(start..=end);
+            }
+            None => days.push(parse_day(item)?),
+        }
+    }
+
+    days.sort_unstable();
+    days.dedup();
+    Ok(Days::Only(days))
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    match s.trim().parse() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("`{}` is not a day between 1 and 25", s)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(Ok(Days::Only(vec![15])), parse_days("15"));
+        assert_eq!(Ok(Days::Only(vec![3, 4, 5, 6, 7])), parse_days("3..7"));
+        assert_eq!(Ok(Days::Only(vec![3, 4, 5, 6, 7])), parse_days("3-7"));
+        assert_eq!(Ok(Days::Only(vec![1, 4, 5, 9])), parse_days("9,1,4-5,4"));
+        assert_eq!(Ok(Days::All), parse_days("all"));
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("7..3").is_err());
+        assert!(parse_days("x").is_err());
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            Ok(Command::Run(RunArgs {
+                days: Days::Only(vec![15]),
+                parts: vec![Part::Two],
+                input: Some(String::from("day15/test_input.txt")),
+                threads: None,
+                stream: false,
+            })),
+            parse(args("run 15 --part 2 --input day15/test_input.txt"))
+        );
+        assert_eq!(
+            Ok(Command::Run(RunArgs {
+                days: Days::Only(vec![1, 2, 3]),
+                parts: vec![Part::One, Part::Two],
+                input: None,
+                threads: Some(4),
+                stream: true,
+            })),
+            parse(args("run 1..3 --stream --threads 4"))
+        );
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            Ok(Command::Bench(BenchArgs {
+                run: RunArgs {
+                    days: Days::Only(vec![19]),
+                    parts: vec![Part::One],
+                    input: None,
+                    threads: None,
+                    stream: false,
+                },
+                warmup: 1,
+                iterations: 25,
+                format: Format::Csv,
+                output: Some(String::from("bench.csv")),
+            })),
+            parse(args(
+                "bench 19 -n 25 --part 1 --warmup 1 --format csv -o bench.csv"
+            ))
+        );
+        assert!(parse(args("bench all --iterations 0")).is_err());
+        assert!(parse(args("bench all --format xml")).is_err());
+        assert!(matches!(
+            parse(args("bench 5 --stream")),
+            Ok(Command::Bench(BenchArgs {
+                run: RunArgs { stream: true, .. },
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_parse_animate() {
+        assert_eq!(
+            Ok(Command::Animate(AnimateArgs {
+                run: RunArgs {
+                    days: Days::Only(vec![20]),
+                    parts: vec![Part::Two],
+                    input: None,
+                    threads: None,
+                    stream: false,
+                },
+                format: frames::Format::Gif,
+                output: Some(String::from("day20.gif")),
+                scale: Some(3),
+                delay: 40,
+            })),
+            parse(args(
+                "animate 20 -p 2 -f gif -o day20.gif --scale 3 --delay 40"
+            ))
+        );
+        assert!(parse(args("animate 11 --stream")).is_err());
+    }
+
+    #[test]
+    fn test_parse_verify_and_record() {
+        assert_eq!(
+            Ok(Command::Verify(VerifyArgs {
+                days: Days::All,
+                answers: None,
+            })),
+            parse(args("verify"))
+        );
+        assert_eq!(
+            Ok(Command::Verify(VerifyArgs {
+                days: Days::Only(vec![4, 5]),
+                answers: Some(String::from("theirs.txt")),
+            })),
+            parse(args("verify 4-5 --answers theirs.txt"))
+        );
+        assert_eq!(
+            Ok(Command::Record(RecordArgs {
+                run: RunArgs {
+                    days: Days::Only(vec![7]),
+                    parts: vec![Part::One, Part::Two],
+                    input: Some(String::from("alice.txt")),
+                    threads: None,
+                    stream: false,
+                },
+                answers: None,
+            })),
+            parse(args("record 7 --input alice.txt"))
+        );
+        assert!(parse(args("record")).is_err());
+        assert!(parse(args("verify 1 2")).is_err());
+    }
+
+    #[test]
+    fn test_parse_gen() {
+        assert_eq!(
+            Ok(Command::Gen(GenArgs {
+                day: 15,
+                size: Some(1000),
+                seed: 7,
+                output: Some(String::from("big.txt")),
+            })),
+            parse(args("gen 15 --size 1000 --seed 7 -o big.txt"))
+        );
+        assert_eq!(
+            Ok(Command::Gen(GenArgs {
+                day: 4,
+                size: None,
+                seed: 2021,
+                output: None,
+            })),
+            parse(args("gen 4"))
+        );
+        assert!(parse(args("gen")).is_err());
+        assert!(parse(args("gen 1-3")).is_err());
+        assert!(parse(args("gen 1 --size 0")).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Ok(Command::Help), parse(args("")));
+        assert!(parse(args("fly 1")).is_err());
+        assert!(parse(args("run")).is_err());
+        assert!(parse(args("run 1 --part 3")).is_err());
+        assert!(parse(args("run 1 --input")).is_err());
+        assert!(parse(args("run 1 --threads x")).is_err());
+        assert!(parse(args("run 1..3 --input input.txt")).is_err());
+        assert!(parse(args("run 1 2")).is_err());
+        assert!(parse(args("record 1 --input -")).is_err());
+        assert!(parse(args("animate 11,13")).is_err());
+        assert!(parse(args("animate 11 --format gif")).is_err());
+        assert!(parse(args("animate 11 --format png -o out.png")).is_err());
+    }
+}
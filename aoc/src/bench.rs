@@ -0,0 +1,238 @@
+use crate::args::Format;
+use helpers::{table::Table, Answer, ParseError, Part};
+use std::{fmt::Write, time::Duration};
+
+/// Summary of a set of timing samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// Sample standard deviation, zero when there's only one sample.
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "can't summarise zero samples");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        let nanos = samples.iter().map(|d| d.as_nanos() as f64);
+        let mean = nanos.clone().sum::<f64>() / samples.len() as f64;
+        let variance = if samples.len() > 1 {
+            nanos.map(|n| (n - mean).powi(2)).sum::<f64>() / (samples.len() - 1) as f64
+        } else {
+            0.0
+        };
+
+        Self {
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    pub day: u8,
+    pub part: Part,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+/// Runs a part of a day with `run`, `warmup` times untimed, then `iterations` times,
+/// timing parse and solve separately.
+pub fn measure<F>(
+    day: u8,
+    part: Part,
+    warmup: usize,
+    iterations: usize,
+    mut run: F,
+) -> Result<Measurement, ParseError>
+where
+    F: FnMut() -> Result<Answer, ParseError>,
+{
+    for _ in 0..warmup {
+        run()?;
+    }
+
+    let mut parse_times = Vec::with_capacity(iterations);
+    let mut solve_times = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let answer = run()?;
+        parse_times.push(answer.parse_time);
+        solve_times.push(answer.solve_time);
+    }
+
+    Ok(Measurement {
+        day,
+        part,
+        iterations,
+        parse: Stats::from_samples(&parse_times),
+        solve: Stats::from_samples(&solve_times),
+    })
+}
+
+pub fn render(measurements: &[Measurement], format: Format) -> String {
+    match format {
+        Format::Table => to_table(measurements).to_string(),
+        Format::Json => to_json(measurements),
+        Format::Csv => to_csv(measurements),
+    }
+}
+
+fn to_table(measurements: &[Measurement]) -> Table {
+    let mut table = Table::new(&[
+        "Day",
+        "Part",
+        "Runs",
+        "Parse median",
+        "Solve min",
+        "Solve median",
+        "Solve mean",
+        "Solve stddev",
+    ]);
+    for m in measurements {
+        table.push(vec![
+            format!("{:02}", m.day),
+            m.part.to_string(),
+            m.iterations.to_string(),
+            format!("{:.2?}", m.parse.median),
+            format!("{:.2?}", m.solve.min),
+            format!("{:.2?}", m.solve.median),
+            format!("{:.2?}", m.solve.mean),
+            format!("{:.2?}", m.solve.stddev),
+        ]);
+    }
+    table
+}
+
+/// A JSON array with one object per day and part. Times are in nanoseconds.
+fn to_json(measurements: &[Measurement]) -> String {
+    let mut json = String::from("[");
+    for (i, m) in measurements.iter().enumerate() {
+        let separator = if i == 0 { "" } else { "," };
+        write!(
+            json,
+            "{}\n  {{\"day\": {}, \"part\": {}, \"iterations\": {}, \"parse_ns\": {}, \"solve_ns\": {}}}",
+            separator,
+            m.day,
+            m.part,
+            m.iterations,
+            stats_json(&m.parse),
+            stats_json(&m.solve)
+        )
+        .unwrap();
+    }
+    json.push_str("\n]\n");
+    json
+}
+
+fn stats_json(stats: &Stats) -> String {
+    format!(
+        "{{\"min\": {}, \"median\": {}, \"mean\": {}, \"stddev\": {}}}",
+        stats.min.as_nanos(),
+        stats.median.as_nanos(),
+        stats.mean.as_nanos(),
+        stats.stddev.as_nanos()
+    )
+}
+
+/// One row per day and part. Times are in nanoseconds.
+fn to_csv(measurements: &[Measurement]) -> String {
+    let mut csv = String::from(
+        "day,part,iterations,\
+         parse_min_ns,parse_median_ns,parse_mean_ns,parse_stddev_ns,\
+         solve_min_ns,solve_median_ns,solve_mean_ns,solve_stddev_ns\n",
+    );
+    for m in measurements {
+        writeln!(
+            csv,
+            "{},{},{},{},{}",
+            m.day,
+            m.part,
+            m.iterations,
+            stats_csv(&m.parse),
+            stats_csv(&m.solve)
+        )
+        .unwrap();
+    }
+    csv
+}
+
+fn stats_csv(stats: &Stats) -> String {
+    format!(
+        "{},{},{},{}",
+        stats.min.as_nanos(),
+        stats.median.as_nanos(),
+        stats.mean.as_nanos(),
+        stats.stddev.as_nanos()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    fn measurement() -> Measurement {
+        Measurement {
+            day: 19,
+            part: Part::Two,
+            iterations: 4,
+            parse: Stats::from_samples(&[ms(1)]),
+            solve: Stats::from_samples(&[ms(4), ms(2), ms(8), ms(6)]),
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&[ms(4), ms(2), ms(8), ms(6)]);
+        assert_eq!(ms(2), stats.min);
+        assert_eq!(ms(5), stats.median);
+        assert_eq!(ms(5), stats.mean);
+        // sqrt(20 / 3) ms
+        assert_eq!(Duration::from_nanos(2_581_989), stats.stddev);
+
+        let single = Stats::from_samples(&[ms(3)]);
+        assert_eq!((ms(3), Duration::ZERO), (single.median, single.stddev));
+    }
+
+    #[test]
+    fn test_json() {
+        let expected = "[
+  {\"day\": 19, \"part\": 2, \"iterations\": 4, \
+\"parse_ns\": {\"min\": 1000000, \"median\": 1000000, \"mean\": 1000000, \"stddev\": 0}, \
+\"solve_ns\": {\"min\": 2000000, \"median\": 5000000, \"mean\": 5000000, \"stddev\": 2581989}}
+]
+";
+        assert_eq!(expected, render(&[measurement()], Format::Json));
+        assert_eq!("[\n]\n", render(&[], Format::Json));
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = render(&[measurement()], Format::Csv);
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(2, lines.len());
+        assert_eq!(11, lines[0].split(',').count());
+        assert_eq!(
+            "19,2,4,1000000,1000000,1000000,0,2000000,5000000,5000000,2581989",
+            lines[1]
+        );
+    }
+}
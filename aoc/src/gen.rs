@@ -0,0 +1,1000 @@
+use helpers::{grid::Grid, rng::Rng};
+use std::collections::{HashSet, VecDeque};
+use std::io::{self, Write};
+
+/// Writes synthetic inputs for one day, shaped like the real ones but as large as
+/// asked for, so the solvers can be timed well beyond the puzzle's own input.
+pub struct Generator {
+    pub day: u8,
+    /// What `--size` counts, or `None` if the day's input doesn't grow.
+    pub unit: Option<&'static str>,
+    /// Large enough to be worth timing.
+    pub default_size: usize,
+    /// The largest size the day's solver is sure to take, with answers that fit in
+    /// its types, however the input comes out.
+    pub max_size: usize,
+    write: fn(&mut Rng, usize, &mut dyn Write) -> io::Result<()>,
+}
+
+impl Generator {
+    pub fn write(&self, seed: u64, size: usize, out: &mut dyn Write) -> io::Result<()> {
+        (self.write)(&mut Rng::new(seed), size, out)
+    }
+}
+
+pub static GENERATORS: &[Generator] = &[
+    Generator {
+        day: 1,
+        unit: Some("depth readings"),
+        default_size: 1_000_000,
+        max_size: usize::MAX,
+        write: day01,
+    },
+    Generator {
+        day: 2,
+        unit: Some("commands"),
+        default_size: 5_000,
+        // the aim stays between 0 and 9, so only the script's own limit gets in the way
+        max_size: day02::MAX_COMMANDS as usize,
+        write: day02,
+    },
+    Generator {
+        day: 3,
+        unit: Some("readings"),
+        default_size: 30_001,
+        // readings are generated as u64s, so stop at 63 bits wide, which only has
+        // room for this many
+        max_size: (1 << 62) - 1,
+        write: day03,
+    },
+    Generator {
+        day: 4,
+        unit: Some("boards"),
+        default_size: 10_000,
+        max_size: usize::MAX,
+        write: day04,
+    },
+    Generator {
+        day: 5,
+        unit: Some("vents"),
+        default_size: 10_000,
+        max_size: usize::MAX,
+        write: day05,
+    },
+    Generator {
+        day: 6,
+        unit: Some("fish"),
+        default_size: 1_000_000,
+        max_size: usize::MAX,
+        write: day06,
+    },
+    Generator {
+        day: 7,
+        unit: Some("crabs"),
+        default_size: 1_000,
+        // each crab costs at most 2001000 fuel to move 2000
+        max_size: (i64::MAX / 2_001_000) as usize,
+        write: day07,
+    },
+    Generator {
+        day: 8,
+        unit: Some("displays"),
+        default_size: 100_000,
+        max_size: usize::MAX,
+        write: day08,
+    },
+    Generator {
+        day: 9,
+        unit: Some("cells across"),
+        default_size: 1_000,
+        // a low point worth 1 for every 8x8 block, added up in a u16
+        max_size: 255 * 8,
+        write: day09,
+    },
+    Generator {
+        day: 10,
+        unit: Some("lines"),
+        default_size: 100_000,
+        // a corrupted line scores at most 25137, added up in a u32
+        max_size: (u32::MAX / 25137) as usize,
+        write: day10,
+    },
+    Generator {
+        day: 11,
+        unit: Some("octopuses across"),
+        default_size: 500,
+        max_size: usize::MAX,
+        write: day11,
+    },
+    Generator {
+        day: 12,
+        unit: Some("small caves"),
+        default_size: 12,
+        // caves are named with two letters, which runs out here
+        max_size: 26 * 26,
+        write: day12,
+    },
+    Generator {
+        day: 13,
+        unit: Some("folds"),
+        default_size: 16,
+        max_size: usize::MAX,
+        write: day13,
+    },
+    Generator {
+        day: 14,
+        unit: Some("elements in the template"),
+        default_size: 100_000,
+        max_size: usize::MAX,
+        write: day14,
+    },
+    Generator {
+        day: 15,
+        unit: Some("cells across"),
+        default_size: 500,
+        max_size: usize::MAX,
+        write: day15,
+    },
+    Generator {
+        day: 16,
+        unit: Some("packets"),
+        default_size: 100_000,
+        max_size: usize::MAX,
+        write: day16,
+    },
+    Generator {
+        day: 17,
+        unit: Some("as the far edge of the target"),
+        default_size: 200,
+        // the target is up to 4 x size deep, and the highest shot climbs about half
+        // its depth squared, in an i32
+        max_size: 16_384,
+        write: day17,
+    },
+    Generator {
+        day: 18,
+        unit: Some("snailfish numbers"),
+        default_size: 500,
+        max_size: usize::MAX,
+        write: day18,
+    },
+    Generator {
+        day: 19,
+        unit: Some("scanners"),
+        default_size: 40,
+        max_size: usize::MAX,
+        write: day19,
+    },
+    Generator {
+        day: 20,
+        unit: Some("pixels across"),
+        default_size: 400,
+        max_size: usize::MAX,
+        write: day20,
+    },
+    Generator {
+        day: 21,
+        unit: None,
+        default_size: 1,
+        max_size: usize::MAX,
+        write: day21,
+    },
+    Generator {
+        day: 22,
+        unit: Some("cuboids"),
+        default_size: 600,
+        max_size: usize::MAX,
+        write: day22,
+    },
+    Generator {
+        day: 23,
+        unit: None,
+        default_size: 1,
+        max_size: usize::MAX,
+        write: day23,
+    },
+];
+
+pub fn find(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.day == day)
+}
+
+/// A random walk that drifts downwards, like the sea floor.
+fn day01(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut depth = rng.range(100..=200);
+    for _ in 0..size {
+        writeln!(out, "{}", depth)?;
+        depth = (depth + rng.range(-10..=12)).max(0);
+    }
+    Ok(())
+}
+
+/// Keeps the aim between 0 and 9, so the depth grows with the distance travelled
+/// rather than with its square.
+fn day02(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut aim = 0;
+    for _ in 0..size {
+        let x = rng.range(1..=9);
+        if rng.chance(0.5) {
+            writeln!(out, "forward {}", x)?;
+        } else if rng.chance(0.5) && aim + x <= 9 || aim - x < 0 {
+            aim += x;
+            writeln!(out, "down {}", x)?;
+        } else {
+            aim -= x;
+            writeln!(out, "up {}", x)?;
+        }
+    }
+    Ok(())
+}
+
+/// An odd number of readings, so no column is ever a tie, each just wide enough to
+/// tell them apart. The ratings whittle the readings down by their leading bits, and
+/// would run out if all that's left agreed on the next bit, so the readings are split
+/// between the two at every bit until each is on its own.
+fn day03(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    fn split(rng: &mut Rng, prefix: u64, bits: u32, count: u64, readings: &mut Vec<u64>) {
+        if count == 1 {
+            readings.push(prefix << bits | rng.below(1 << bits));
+            return;
+        }
+        let half = 1 << (bits - 1);
+        let zeros = ((count as f64 * (0.3 + rng.f64() * 0.4)) as u64)
+            .clamp(count.saturating_sub(half).max(1), half.min(count - 1));
+        split(rng, prefix << 1, bits - 1, zeros, readings);
+        split(rng, prefix << 1 | 1, bits - 1, count - zeros, readings);
+    }
+
+    let size = size | 1;
+    let bits = (usize::BITS - (size * 2).leading_zeros()).clamp(12, 63);
+    let mut readings = Vec::with_capacity(size);
+    split(rng, 0, bits, size as u64, &mut readings);
+    rng.shuffle(&mut readings);
+    for reading in readings {
+        writeln!(out, "{:0width$b}", reading, width = bits as usize)?;
+    }
+    Ok(())
+}
+
+/// Every number from 0 to 99 is called, so every board wins eventually.
+fn day04(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut numbers = (0..100).collect::<Vec<_>>();
+    rng.shuffle(&mut numbers);
+    let called = numbers.iter().map(u8::to_string).collect::<Vec<_>>();
+    writeln!(out, "{}", called.join(","))?;
+
+    for _ in 0..size {
+        rng.shuffle(&mut numbers);
+        writeln!(out)?;
+        for row in numbers[..25].chunks(5) {
+            let row = row.iter().map(|n| format!("{:>2}", n)).collect::<Vec<_>>();
+            writeln!(out, "{}", row.join(" "))?;
+        }
+    }
+    Ok(())
+}
+
+/// Horizontal, vertical and diagonal vents in equal measure, on a 1000x1000 floor.
+fn day05(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size {
+        let (x1, y1) = (rng.range(0..=989), rng.range(0..=989));
+        let (x2, y2) = match rng.below(3) {
+            0 => (rng.range(0..=989), y1),
+            1 => (x1, rng.range(0..=989)),
+            _ => {
+                let (dx, dy) = (*rng.choose(&[-1, 1]), *rng.choose(&[-1, 1]));
+                let room_x = if dx > 0 { 989 - x1 } else { x1 };
+                let room_y = if dy > 0 { 989 - y1 } else { y1 };
+                let length = rng.range(0..=room_x.min(room_y));
+                (x1 + dx * length, y1 + dy * length)
+            }
+        };
+        writeln!(out, "{},{} -> {},{}", x1, y1, x2, y2)?;
+    }
+    Ok(())
+}
+
+fn day06(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let fish = (0..size)
+        .map(|_| rng.range(1..=5).to_string())
+        .collect::<Vec<_>>();
+    writeln!(out, "{}", fish.join(","))
+}
+
+/// Bunched up towards 0 like the real crabs, and no further out than 2000 so the
+/// fuel costs stay in range.
+fn day07(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let crabs = (0..size)
+        .map(|_| ((rng.f64() * rng.f64() * 2000.0) as u32).to_string())
+        .collect::<Vec<_>>();
+    writeln!(out, "{}", crabs.join(","))
+}
+
+const DIGITS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// Each display has its wires crossed its own way, and lists its patterns in any
+/// order with their segments in any order.
+fn day08(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size {
+        let mut wires = *b"abcdefg";
+        rng.shuffle(&mut wires);
+        let mut digits = (0..10).collect::<Vec<_>>();
+        rng.shuffle(&mut digits);
+        let inputs = digits
+            .iter()
+            .map(|&d| pattern(&wires, d, rng))
+            .collect::<Vec<_>>();
+        let readings = (0..4)
+            .map(|_| {
+                let digit = rng.index(10);
+                pattern(&wires, digit, rng)
+            })
+            .collect::<Vec<_>>();
+        writeln!(out, "{} | {}", inputs.join(" "), readings.join(" "))?;
+    }
+    Ok(())
+}
+
+/// How `digit` is shown when the segments are wired up to `wires`.
+fn pattern(wires: &[u8], digit: usize, rng: &mut Rng) -> String {
+    let mut segments = DIGITS[digit]
+        .bytes()
+        .map(|s| wires[(s - b'a') as usize] as char)
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut segments);
+    segments.into_iter().collect()
+}
+
+/// Basins grow out from seeds scattered across the grid until they meet, where
+/// they're walled off with 9s. Heights rise with the distance from the seed, so each
+/// basin has the one low point, and none of them get so big that filling it goes
+/// too deep.
+fn day09(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    const SPACING: usize = 8;
+    let mut basin = Grid::filled(size, size, None);
+    let mut queue = VecDeque::new();
+    for y in (0..size).step_by(SPACING) {
+        for x in (0..size).step_by(SPACING) {
+            // seeds stay at least 3 apart, so no two basins start out touching
+            let seed = (
+                (x + rng.index(SPACING - 2)).min(size - 1),
+                (y + rng.index(SPACING - 2)).min(size - 1),
+            );
+            basin[seed] = Some(queue.len());
+            queue.push_back(seed);
+        }
+    }
+    let seeds = queue.iter().copied().collect::<Vec<_>>();
+    while let Some(point) = queue.pop_front() {
+        for next in basin.neighbors4(point).collect::<Vec<_>>() {
+            if basin[next].is_none() {
+                basin[next] = basin[point];
+                queue.push_back(next);
+            }
+        }
+    }
+
+    let is_wall = |point| basin.neighbors4(point).any(|n| basin[n] < basin[point]);
+    let mut heights = Grid::filled(size, size, 9u8);
+    for &seed in seeds.iter() {
+        heights[seed] = 0;
+        queue.push_back(seed);
+    }
+    while let Some(point) = queue.pop_front() {
+        for next in heights.neighbors4(point).collect::<Vec<_>>() {
+            if heights[next] == 9 && basin[next] == basin[point] && !is_wall(next) {
+                heights[next] = (heights[point] + 1).min(8);
+                queue.push_back(next);
+            }
+        }
+    }
+    write!(out, "{}", heights)
+}
+
+/// Half the lines are corrupted and the rest are incomplete. Never more than 20
+/// chunks are left open, so completion scores fit in 64 bits.
+fn day10(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    const PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+    for _ in 0..size {
+        let length = rng.range(80..=110) as usize;
+        let corrupt_at = rng.chance(0.5).then(|| rng.index(length));
+        let mut open = Vec::new();
+        let mut line = String::new();
+        for i in 0..length {
+            if corrupt_at == Some(i) && !open.is_empty() {
+                let expected = open.pop().unwrap();
+                let wrong = loop {
+                    let (_, close) = *rng.choose(&PAIRS);
+                    if close != expected {
+                        break close;
+                    }
+                };
+                line.push(wrong);
+            } else if open.is_empty() || open.len() < 20 && rng.chance(0.55) {
+                let (opening, closing) = *rng.choose(&PAIRS);
+                line.push(opening);
+                open.push(closing);
+            } else {
+                line.push(open.pop().unwrap());
+            }
+        }
+        if open.is_empty() && corrupt_at.is_none() {
+            line.push(rng.choose(&PAIRS).0);
+        }
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// Random energy levels almost never synchronise, so most octopuses start at 9 and
+/// the rest anywhere from 0 to 8. The 9s set off cascades that pull the others into
+/// step within a few dozen steps, and on the rare occasion they don't within part 2's
+/// limit, the grid is drawn again with more 9s.
+fn day11(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut nines = 0.85;
+    loop {
+        let energy = (0..size * size)
+            .map(|_| {
+                if rng.chance(nines) {
+                    9
+                } else {
+                    rng.range(0..=8) as u8
+                }
+            })
+            .collect::<Vec<_>>();
+        let octopuses = Grid::new(size, size, energy);
+        if day11::first_synchronised_step(&octopuses).is_some() {
+            return write!(out, "{}", octopuses);
+        }
+        nines = (nines + 0.05f64).min(1.0);
+    }
+}
+
+/// The number of paths grows exponentially with the number of caves, so sizes much
+/// past the default take a very long time to solve. Big caves are never joined to
+/// each other, which would allow endless paths.
+fn day12(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let name = |i: usize| {
+        format!(
+            "{}{}",
+            (b'a' + (i / 26) as u8) as char,
+            (b'a' + (i % 26) as u8) as char
+        )
+    };
+    let small = (0..size).map(name).collect::<Vec<_>>();
+    let big = (0..size / 4 + 1)
+        .map(|i| name(i).to_uppercase())
+        .collect::<Vec<_>>();
+
+    let mut edges = HashSet::new();
+    let mut join = |a: &str, b: &str| {
+        // either way round is the same passage
+        edges.insert((a.min(b).to_string(), a.max(b).to_string()));
+    };
+    let mut caves = small.iter().chain(big.iter()).collect::<Vec<_>>();
+    for cave in ["start", "end"] {
+        rng.shuffle(&mut caves);
+        for &other in caves.iter().take(2) {
+            join(cave, other);
+        }
+    }
+    for (i, cave) in small.iter().enumerate() {
+        // each small cave is joined to one of the caves before it, so they're all
+        // connected, and then to a couple of others at random
+        if i > 0 {
+            join(&small[rng.index(i)], cave);
+        }
+        for _ in 0..2 {
+            let other = *rng.choose(&caves);
+            if other != cave {
+                join(cave, other);
+            }
+        }
+    }
+    for cave in big.iter() {
+        let other = rng.choose(&small);
+        join(other, cave);
+    }
+
+    let mut edges = edges.into_iter().collect::<Vec<_>>();
+    edges.sort_unstable();
+    rng.shuffle(&mut edges);
+    for (from, to) in edges {
+        writeln!(out, "{}-{}", from, to)?;
+    }
+    Ok(())
+}
+
+/// Starts from the folded-up 40x6 code and unfolds it, alternating between the two
+/// axes, so folding the paper back up gives the code again. Each dot ends up on one
+/// side of the fold or the other, or both, and never on the fold itself.
+fn day13(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let (mut width, mut height) = (40, 6);
+    let mut dots = (0..width)
+        .flat_map(|x| (0..height).map(move |y| (x, y)))
+        .filter(|_| rng.chance(0.4))
+        .collect::<Vec<_>>();
+
+    let mut folds = Vec::new();
+    for i in 0..size {
+        let along_x = i % 2 == 0;
+        let line = if along_x { width } else { height };
+        let mut unfolded = Vec::with_capacity(dots.len() * 2);
+        for &(x, y) in dots.iter() {
+            let mirrored = if along_x {
+                (2 * line - x, y)
+            } else {
+                (x, 2 * line - y)
+            };
+            match rng.below(3) {
+                0 => unfolded.push((x, y)),
+                1 => unfolded.push(mirrored),
+                _ => unfolded.extend([(x, y), mirrored]),
+            }
+        }
+        dots = unfolded;
+        if along_x {
+            width = 2 * width + 1;
+        } else {
+            height = 2 * height + 1;
+        }
+        folds.push((if along_x { 'x' } else { 'y' }, line));
+    }
+
+    rng.shuffle(&mut dots);
+    for (x, y) in dots {
+        writeln!(out, "{},{}", x, y)?;
+    }
+    writeln!(out)?;
+    for (axis, line) in folds.into_iter().rev() {
+        writeln!(out, "fold along {}={}", axis, line)?;
+    }
+    Ok(())
+}
+
+/// A long template over ten elements, with a rule for every pair of them.
+fn day14(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    const ELEMENTS: &[char] = &['B', 'C', 'F', 'H', 'K', 'N', 'O', 'P', 'S', 'V'];
+    let template = (0..size).map(|_| *rng.choose(ELEMENTS)).collect::<String>();
+    writeln!(out, "{}", template)?;
+    writeln!(out)?;
+    for &a in ELEMENTS {
+        for &b in ELEMENTS {
+            writeln!(out, "{}{} -> {}", a, b, rng.choose(ELEMENTS))?;
+        }
+    }
+    Ok(())
+}
+
+fn day15(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let risks = Grid::from_fn(size, size, |_| rng.range(1..=9));
+    write!(out, "{}", risks)
+}
+
+/// The largest value an operator packet is allowed to reach, which keeps the sum of
+/// a couple of thousand of them well inside 64 bits.
+const MAX_PACKET_VALUE: u64 = 1 << 40;
+
+/// One outer sum packet holding as many packets as it takes to use up `size`,
+/// nested up to 30 deep.
+fn day16(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut budget = size.saturating_sub(1);
+    let mut children = Vec::new();
+    while budget > 0 && children.len() < 2047 {
+        children.push(packet(rng, &mut budget, 1).0);
+    }
+    if children.is_empty() {
+        children.push(literal(rng.below(16)));
+    }
+    let mut bits = operator(rng.below(8), 0, &children, true);
+
+    while !bits.len().is_multiple_of(8) {
+        bits.push('0');
+    }
+    let hex = bits
+        .as_bytes()
+        .chunks(4)
+        .map(|nibble| {
+            let nibble = std::str::from_utf8(nibble).unwrap();
+            format!("{:X}", u8::from_str_radix(nibble, 2).unwrap())
+        })
+        .collect::<String>();
+    writeln!(out, "{}", hex)
+}
+
+/// A random packet as bits, along with its value.
+fn packet(rng: &mut Rng, budget: &mut usize, depth: usize) -> (String, u64) {
+    *budget = budget.saturating_sub(1);
+    let version = rng.below(8);
+    if *budget == 0 || depth >= 30 || rng.chance(0.4) {
+        let value = rng.below(1 << 12);
+        let mut bits = format!("{:03b}100", version);
+        bits.push_str(&literal(value)[6..]);
+        return (bits, value);
+    }
+
+    if rng.chance(0.2) {
+        let (left, a) = packet(rng, budget, depth + 1);
+        let (right, b) = packet(rng, budget, depth + 1);
+        let (type_id, value) = *rng.choose(&[(5, a > b), (6, a < b), (7, a == b)]);
+        let children = [left, right];
+        let bits = operator(version, type_id, &children, rng.chance(0.5));
+        return (bits, value as u64);
+    }
+
+    let count = rng.range(1..=4) as usize;
+    let (children, values): (Vec<_>, Vec<_>) =
+        (0..count).map(|_| packet(rng, budget, depth + 1)).unzip();
+    let sum = values.iter().try_fold(0u64, |sum, &v| sum.checked_add(v));
+    let product = values
+        .iter()
+        .try_fold(1u64, |product, &v| product.checked_mul(v));
+    let mut choices = vec![
+        (2, *values.iter().min().unwrap()),
+        (3, *values.iter().max().unwrap()),
+    ];
+    for (type_id, value) in [(0, sum), (1, product)] {
+        if let Some(value) = value.filter(|&v| v <= MAX_PACKET_VALUE) {
+            choices.push((type_id, value));
+        }
+    }
+    let (type_id, value) = *rng.choose(&choices);
+    let bits = operator(version, type_id, &children, rng.chance(0.5));
+    (bits, value)
+}
+
+/// A literal packet with version 0.
+fn literal(value: u64) -> String {
+    let mut bits = String::from("000100");
+    let groups = (0..)
+        .take_while(|&i| i == 0 || value >> (4 * i) != 0)
+        .count();
+    for i in (0..groups).rev() {
+        let more = if i > 0 { '1' } else { '0' };
+        bits.push(more);
+        bits.push_str(&format!("{:04b}", (value >> (4 * i)) & 0xf));
+    }
+    bits
+}
+
+/// An operator packet around `children`, giving their count or (if it fits) their
+/// length in bits.
+fn operator(version: u64, type_id: u64, children: &[String], by_count: bool) -> String {
+    let length = children.iter().map(String::len).sum::<usize>();
+    let mut bits = format!("{:03b}{:03b}", version, type_id);
+    if by_count || length >= 1 << 15 {
+        bits.push_str(&format!("1{:011b}", children.len()));
+    } else {
+        bits.push_str(&format!("0{:015b}", length));
+    }
+    children.iter().for_each(|child| bits.push_str(child));
+    bits
+}
+
+/// A target about as deep as it is far, reaching out to `size`.
+fn day17(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let size = size.max(4) as i64;
+    let max_x = size;
+    let min_x = rng.range(size / 3..=size * 2 / 3);
+    let min_y = -rng.range(size * 3..=size * 4);
+    let max_y = min_y + rng.range(size / 2..=size);
+    writeln!(
+        out,
+        "target area: x={}..{}, y={}..{}",
+        min_x, max_x, min_y, max_y
+    )
+}
+
+/// Numbers nested as deep as a reduced number can be, mostly fully, with every
+/// regular number below 10.
+fn day18(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    fn number(rng: &mut Rng, depth: usize, text: &mut String) {
+        if depth == 0 || depth < 4 && rng.chance(0.85) {
+            text.push('[');
+            number(rng, depth + 1, text);
+            text.push(',');
+            number(rng, depth + 1, text);
+            text.push(']');
+        } else {
+            text.push_str(&rng.below(10).to_string());
+        }
+    }
+
+    for _ in 0..size {
+        let mut text = String::new();
+        number(rng, 0, &mut text);
+        writeln!(out, "{}", text)?;
+    }
+    Ok(())
+}
+
+type Point = (i64, i64, i64);
+
+/// The scanners form a chain, each within range of the one before it and sharing at
+/// least 12 beacons with it. Every scanner but the first is turned to one of the 24
+/// orientations, and they're listed in a random order, so they have to be matched up
+/// in whatever order they fit.
+fn day19(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let size = size.max(1);
+    let mut scanners = vec![(0, 0, 0)];
+    let mut beacons = HashSet::new();
+    for _ in 1..size {
+        let last = *scanners.last().unwrap();
+        let mut step = || *rng.choose(&[-1, 1]) * rng.range(400..=1000);
+        let next = (last.0 + step(), last.1 + step(), last.2 + step());
+        let shared = |a: i64, b: i64| (a.max(b) - 1000, a.min(b) + 1000);
+        let (x, y, z) = (
+            shared(last.0, next.0),
+            shared(last.1, next.1),
+            shared(last.2, next.2),
+        );
+        while beacons.len() < 12 * scanners.len() {
+            beacons.insert((
+                rng.range(x.0..=x.1),
+                rng.range(y.0..=y.1),
+                rng.range(z.0..=z.1),
+            ));
+        }
+        scanners.push(next);
+    }
+    for &(x, y, z) in scanners.iter() {
+        for _ in 0..10 {
+            beacons.insert((
+                x + rng.range(-1000..=1000),
+                y + rng.range(-1000..=1000),
+                z + rng.range(-1000..=1000),
+            ));
+        }
+    }
+
+    let rotations = rotations();
+    let mut beacons = beacons.into_iter().collect::<Vec<_>>();
+    beacons.sort_unstable();
+    let mut order = (1..scanners.len()).collect::<Vec<_>>();
+    rng.shuffle(&mut order);
+    order.insert(0, 0);
+    for (id, &i) in order.iter().enumerate() {
+        let scanner = scanners[i];
+        let rotation = if i == 0 {
+            rotations[0]
+        } else {
+            *rng.choose(&rotations)
+        };
+        let mut seen = beacons
+            .iter()
+            .map(|b| (b.0 - scanner.0, b.1 - scanner.1, b.2 - scanner.2))
+            .filter(|b| b.0.abs() <= 1000 && b.1.abs() <= 1000 && b.2.abs() <= 1000)
+            .map(|b| rotate(b, rotation))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut seen);
+
+        if id > 0 {
+            writeln!(out)?;
+        }
+        writeln!(out, "--- scanner {} ---", id)?;
+        for (x, y, z) in seen {
+            writeln!(out, "{},{},{}", x, y, z)?;
+        }
+    }
+    Ok(())
+}
+
+/// An orientation as which axis each axis comes from, and whether it's flipped.
+type Rotation = ([usize; 3], [i64; 3]);
+
+/// The 24 ways to turn a scanner: every reordering and flipping of the axes that
+/// keeps them right-handed.
+fn rotations() -> Vec<Rotation> {
+    const ORDERS: [([usize; 3], i64); 6] = [
+        ([0, 1, 2], 1),
+        ([1, 2, 0], 1),
+        ([2, 0, 1], 1),
+        ([0, 2, 1], -1),
+        ([2, 1, 0], -1),
+        ([1, 0, 2], -1),
+    ];
+    let mut rotations = Vec::new();
+    for (order, parity) in ORDERS {
+        for flips in 0..8 {
+            let signs = [0, 1, 2].map(|i| if flips & (1 << i) == 0 { 1 } else { -1 });
+            if signs.iter().product::<i64>() == parity {
+                rotations.push((order, signs));
+            }
+        }
+    }
+    rotations
+}
+
+fn rotate(point: Point, (order, signs): Rotation) -> Point {
+    let axes = [point.0, point.1, point.2];
+    (
+        axes[order[0]] * signs[0],
+        axes[order[1]] * signs[1],
+        axes[order[2]] * signs[2],
+    )
+}
+
+/// If the algorithm lights up dark pixels, it has to switch off lit ones too,
+/// otherwise the infinite image ends up lit.
+fn day20(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let pixel = |rng: &mut Rng| if rng.chance(0.5) { '#' } else { '.' };
+    let mut algorithm = (0..512).map(|_| pixel(rng)).collect::<Vec<_>>();
+    if algorithm[0] == '#' {
+        algorithm[511] = '.';
+    }
+    writeln!(out, "{}", algorithm.into_iter().collect::<String>())?;
+    writeln!(out)?;
+    for _ in 0..size {
+        let row = (0..size).map(|_| pixel(rng)).collect::<String>();
+        writeln!(out, "{}", row)?;
+    }
+    Ok(())
+}
+
+fn day21(rng: &mut Rng, _size: usize, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "Player 1 starting position: {}", rng.range(1..=10))?;
+    writeln!(out, "Player 2 starting position: {}", rng.range(1..=10))
+}
+
+/// Like the real reboot, the first 20 steps stay inside the initialization area and
+/// the rest are big cuboids that overlap a lot.
+fn day22(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for i in 0..size {
+        let on = i == 0 || rng.chance(0.6);
+        let (reach, extent) = if i < 20 {
+            (50, 5..=50)
+        } else {
+            (100_000, 10_000..=60_000)
+        };
+        let mut range = || {
+            let extent = rng.range(extent.clone());
+            let start = rng.range(-reach..=reach - extent);
+            (start, start + extent)
+        };
+        let (x, y, z) = (range(), range(), range());
+        writeln!(
+            out,
+            "{} x={}..{},y={}..{},z={}..{}",
+            if on { "on" } else { "off" },
+            x.0,
+            x.1,
+            y.0,
+            y.1,
+            z.0,
+            z.1
+        )?;
+    }
+    Ok(())
+}
+
+fn day23(rng: &mut Rng, _size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut pods = *b"AABBCCDD";
+    rng.shuffle(&mut pods);
+    let pods = pods.map(char::from);
+    writeln!(out, "#############")?;
+    writeln!(out, "#...........#")?;
+    writeln!(out, "###{}#{}#{}#{}###", pods[0], pods[1], pods[2], pods[3])?;
+    writeln!(out, "  #{}#{}#{}#{}#", pods[4], pods[5], pods[6], pods[7])?;
+    writeln!(out, "  #########")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles;
+    use helpers::Part;
+
+    fn generate(day: u8, seed: u64, size: usize) -> String {
+        let mut text = Vec::new();
+        find(day).unwrap().write(seed, size, &mut text).unwrap();
+        String::from_utf8(text).unwrap()
+    }
+
+    /// Solves both parts of a day from the generated text.
+    fn solve(day: u8, text: &str) -> Vec<String> {
+        let path = std::env::temp_dir().join(format!("aoc-gen-{}-{}.txt", day, std::process::id()));
+        std::fs::write(&path, text).unwrap();
+        let puzzle = puzzles::find(day).unwrap();
+        let answers = [Part::One, Part::Two]
+            .map(|part| match puzzle.run(part, &path.to_string_lossy()) {
+                Ok(answer) => answer.value,
+                Err(e) => panic!("day {} couldn't parse its input\n{}", day, e),
+            })
+            .to_vec();
+        std::fs::remove_file(path).unwrap();
+        answers
+    }
+
+    #[test]
+    fn test_every_puzzle_has_a_generator() {
+        let days = GENERATORS.iter().map(|g| g.day).collect::<Vec<_>>();
+        let solved = puzzles::PUZZLES.iter().map(|p| p.day()).collect::<Vec<_>>();
+        assert_eq!(solved, days);
+    }
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        let small = [
+            (1, 1000),
+            (2, 1000),
+            (3, 101),
+            (4, 50),
+            (5, 200),
+            (6, 100),
+            (7, 100),
+            (8, 100),
+            (9, 50),
+            (10, 100),
+            (11, 20),
+            (12, 6),
+            (13, 4),
+            (14, 50),
+            (15, 30),
+            (16, 200),
+            (17, 30),
+            (18, 20),
+            (19, 4),
+            (20, 20),
+            (21, 1),
+            (22, 40),
+            (23, 1),
+        ];
+        for (day, size) in small {
+            for seed in 0..3 {
+                solve(day, &generate(day, seed, size));
+            }
+        }
+    }
+
+    #[test]
+    fn test_repeatable() {
+        for generator in GENERATORS.iter() {
+            let day = generator.day;
+            assert_eq!(generate(day, 5, 10), generate(day, 5, 10));
+            if generator.unit.is_some() {
+                assert_ne!(generate(day, 5, 10), generate(day, 6, 10));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sizes() {
+        assert_eq!(1000, generate(1, 1, 1000).lines().count());
+        assert_eq!(1 + 6 * 25, generate(4, 1, 25).lines().count());
+        let grid = generate(15, 1, 40);
+        assert_eq!(40, grid.lines().count());
+        assert!(grid.lines().all(|line| line.len() == 40));
+        for generator in GENERATORS.iter() {
+            assert!(generator.default_size <= generator.max_size);
+        }
+        // answers that overflow panic in tests, so this checks they fit at the
+        // default sizes
+        for day in [2, 3, 7] {
+            solve(day, &generate(day, 1, find(day).unwrap().default_size));
+        }
+        // day 11 has to synchronise eventually, and day 19 is built so the answer is
+        // known
+        let octopuses = generate(11, 1, 30);
+        assert!(('0'..='9').all(|energy| octopuses.contains(energy)));
+        let step = solve(11, &octopuses)[1].parse::<usize>().unwrap();
+        assert!(step > 1 && step <= day11::MAX_STEPS, "{}", step);
+        assert_eq!("0", solve(19, &generate(19, 1, 1))[1]);
+        // the last small cave gets a name like any other
+        let caves = generate(12, 1, 26 * 26);
+        assert!(caves
+            .lines()
+            .any(|line| line.split('-').any(|cave| cave == "zz")));
+    }
+
+    #[test]
+    fn test_rotations() {
+        let rotations = rotations();
+        let turned = rotations
+            .iter()
+            .map(|&r| rotate((1, 2, 3), r))
+            .collect::<HashSet<_>>();
+        assert_eq!(24, turned.len());
+    }
+}
@@ -0,0 +1,517 @@
+mod answers;
+mod args;
+mod bench;
+mod gen;
+mod puzzles;
+
+use answers::{Entry, Registry};
+use args::{AnimateArgs, BenchArgs, Command, Days, GenArgs, RecordArgs, RunArgs, VerifyArgs};
+use helpers::{
+    frames,
+    input::{InputError, InputSource},
+    parallel,
+    table::Table,
+    trace, Answer, ParseError, Part, Puzzle, Unsolved,
+};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+fn main() {
+    let command = match args::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, args::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if let Err(e) = trace::init_from_env().and_then(|_| parallel::init_from_env()) {
+        eprintln!("{}", e);
+        std::process::exit(2);
+    }
+    if let Some(threads) = command.threads() {
+        parallel::set_threads(threads);
+    }
+
+    match command {
+        Command::Run(run_args) => run(&run_args),
+        Command::Bench(bench_args) => bench(&bench_args),
+        Command::Verify(verify_args) => verify(&verify_args),
+        Command::Record(record_args) => record(&record_args),
+        Command::Animate(animate_args) => animate(&animate_args),
+        Command::Gen(gen_args) => generate(&gen_args),
+        Command::Help => println!("{}", args::USAGE),
+    }
+}
+
+fn run(run_args: &RunArgs) {
+    let puzzles = select_puzzles(&run_args.days);
+    let runs = if parallel::threads() > 1 && puzzles.len() > 1 {
+        // the days are solved side by side, so their output waits until they're done
+        let runs = parallel::map(&puzzles, |&puzzle| run_day(run_args, puzzle));
+        runs.iter().for_each(report_day);
+        runs
+    } else {
+        puzzles
+            .into_iter()
+            .map(|puzzle| {
+                let day_run = run_day(run_args, puzzle);
+                report_day(&day_run);
+                day_run
+            })
+            .collect()
+    };
+
+    if runs.len() > 1 {
+        println!();
+        print!("{}", summary_table(&runs));
+    }
+    if runs.iter().any(|r| r.error.is_some()) {
+        std::process::exit(1);
+    }
+}
+
+/// How running a day's parts went: the answers it got, and what stopped it if it
+/// didn't get them all.
+struct DayRun {
+    day: u8,
+    answers: Vec<(Part, Answer)>,
+    error: Option<String>,
+}
+
+fn run_day(run_args: &RunArgs, puzzle: &dyn Puzzle) -> DayRun {
+    let day = puzzle.day();
+    let mut day_run = DayRun {
+        day,
+        answers: Vec::new(),
+        error: None,
+    };
+    let input = match input_for(run_args, day) {
+        Ok(input) => input,
+        Err(e) => {
+            day_run.error = Some(format!("Day {:02} has no input: {}\n", day, e));
+            return day_run;
+        }
+    };
+
+    for &part in run_args.parts.iter() {
+        match run_part(run_args, puzzle, part, &input) {
+            Ok(answer) => day_run.answers.push((part, answer)),
+            Err(e) => {
+                day_run.error = Some(format!("Day {:02} could not read its input\n{}\n", day, e));
+                break;
+            }
+        }
+    }
+    day_run
+}
+
+/// Solves a part, reading the input a line at a time if `--stream` asked for it and
+/// the day can.
+fn run_part(
+    run_args: &RunArgs,
+    puzzle: &dyn Puzzle,
+    part: Part,
+    input: &str,
+) -> Result<Answer, ParseError> {
+    if run_args.stream {
+        if let Some(answers) = puzzle.run_stream(input) {
+            let [one, two] = answers?;
+            return Ok(match part {
+                Part::One => one,
+                Part::Two => two,
+            });
+        }
+    }
+    puzzle.run(part, input)
+}
+
+fn report_day(day_run: &DayRun) {
+    for (part, answer) in day_run.answers.iter() {
+        println!(
+            "Day {:02} part {}: {} ({:?})",
+            day_run.day,
+            part,
+            answer.value,
+            answer.elapsed()
+        );
+    }
+    if let Some(error) = &day_run.error {
+        eprintln!("{}", error);
+    }
+}
+
+fn bench(bench_args: &BenchArgs) {
+    let run_args = &bench_args.run;
+    let mut measurements = Vec::new();
+    let mut failed = false;
+
+    for puzzle in select_puzzles(&run_args.days) {
+        let day = puzzle.day();
+        let input = match input_for(run_args, day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {:02} has no input: {}\n", day, e);
+                failed = true;
+                continue;
+            }
+        };
+
+        for &part in run_args.parts.iter() {
+            // progress goes to stderr so stdout stays clean for JSON/CSV
+            eprintln!("Benchmarking day {:02} part {}", day, part);
+            let result =
+                bench::measure(day, part, bench_args.warmup, bench_args.iterations, || {
+                    run_part(run_args, puzzle, part, &input)
+                });
+            match result {
+                Ok(measurement) => measurements.push(measurement),
+                Err(e) => {
+                    eprintln!("Day {:02} could not read its input\n{}\n", day, e);
+                    failed = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    let report = bench::render(&measurements, bench_args.format);
+    match &bench_args.output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, report) {
+                eprintln!("Couldn't write results to {}: {}", path, e);
+                std::process::exit(1);
+            }
+        }
+        None => print!("{}", report),
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+fn verify(verify_args: &VerifyArgs) {
+    let registry = load_answers(&verify_args.answers, Registry::load);
+    let entries = registry
+        .entries()
+        .iter()
+        .filter(|e| match &verify_args.days {
+            Days::All => true,
+            Days::Only(days) => days.contains(&e.day),
+        });
+
+    let mut table = Table::new(&["Day", "Part", "Input", "Result", "Time"]);
+    let mut failures = Vec::new();
+    let mut passed = 0;
+
+    for entry in entries {
+        let (result, time) = match check_answer(entry) {
+            Ok(answer) if answer.value == entry.expected => {
+                passed += 1;
+                ("ok", format!("{:.2?}", answer.elapsed()))
+            }
+            Ok(answer) => {
+                failures.push(format!(
+                    "Day {:02} part {} on {}: expected {}, got {}",
+                    entry.day, entry.part, entry.input, entry.expected, answer.value
+                ));
+                ("FAIL", format!("{:.2?}", answer.elapsed()))
+            }
+            Err(message) => {
+                failures.push(format!(
+                    "Day {:02} part {} on {}: {}",
+                    entry.day, entry.part, entry.input, message
+                ));
+                ("ERROR", String::new())
+            }
+        };
+        table.push(vec![
+            format!("{:02}", entry.day),
+            entry.part.to_string(),
+            entry.input.clone(),
+            result.to_string(),
+            time,
+        ]);
+    }
+
+    print!("{}", table);
+    for failure in failures.iter() {
+        println!("\n{}", failure);
+    }
+    println!("\n{} passed, {} failed", passed, failures.len());
+    if !failures.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+fn check_answer(entry: &Entry) -> Result<Answer, String> {
+    let puzzle = puzzles::find(entry.day).ok_or("this day hasn't been solved")?;
+    let input = entry.input_path();
+    puzzle
+        .run(entry.part, &input.to_string_lossy())
+        .map_err(|e| format!("could not read the input\n{}", e))
+}
+
+fn record(record_args: &RecordArgs) {
+    let run_args = &record_args.run;
+    let path = answers_path(&record_args.answers);
+    let mut registry = load_answers(&record_args.answers, Registry::load_or_default);
+    let mut failed = false;
+
+    for puzzle in select_puzzles(&run_args.days) {
+        let day = puzzle.day();
+        let input = match input_for(run_args, day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {:02} has no input: {}\n", day, e);
+                failed = true;
+                continue;
+            }
+        };
+
+        for &part in run_args.parts.iter() {
+            let answer = match run_part(run_args, puzzle, part, &input) {
+                Ok(answer) => answer,
+                Err(e) => {
+                    eprintln!("Day {:02} could not read its input\n{}\n", day, e);
+                    failed = true;
+                    break;
+                }
+            };
+            if answer.value == Unsolved.to_string() {
+                println!("Day {:02} part {} is unsolved, not recording it", day, part);
+                continue;
+            }
+
+            let entry = Entry {
+                day,
+                part,
+                input: answers::input_name(&input),
+                expected: answer.value.clone(),
+            };
+            match registry.record(entry) {
+                Some(previous) if previous == answer.value => {}
+                Some(previous) => println!(
+                    "Day {:02} part {}: {} (was {})",
+                    day, part, answer.value, previous
+                ),
+                None => println!("Day {:02} part {}: {}", day, part, answer.value),
+            }
+        }
+    }
+
+    if let Err(e) = registry.save(&path) {
+        eprintln!("Couldn't write answers to {}: {}", path.display(), e);
+        std::process::exit(1);
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+fn animate(animate_args: &AnimateArgs) {
+    let run_args = &animate_args.run;
+    let puzzle = select_puzzles(&run_args.days)[0];
+    let day = puzzle.day();
+    let input = match input_for(run_args, day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Day {:02} has no input: {}", day, e);
+            std::process::exit(1);
+        }
+    };
+
+    // frames come in as trace events, so this replaces any AOC_TRACE sink
+    let recorder = frames::Recorder::default();
+    trace::set_sink(Some(Box::new(recorder.clone())));
+    for &part in run_args.parts.iter() {
+        if let Err(e) = puzzle.run(part, &input) {
+            eprintln!("Day {:02} could not read its input\n{}", day, e);
+            std::process::exit(1);
+        }
+    }
+    trace::set_sink(None);
+
+    let recorded = recorder.take();
+    if recorded.is_empty() {
+        eprintln!("Day {:02} doesn't draw any frames", day);
+        std::process::exit(1);
+    }
+    let delay = Duration::from_millis(animate_args.delay);
+    let scale = animate_args
+        .scale
+        .unwrap_or_else(|| default_scale(&recorded));
+
+    let result = match (animate_args.format, &animate_args.output) {
+        (frames::Format::Gif, Some(output)) => {
+            std::fs::write(output, frames::encode_gif(&recorded, scale, delay))
+        }
+        (frames::Format::Pbm | frames::Format::Pgm, Some(output)) => {
+            frames::write_pnm(&recorded, Path::new(output), animate_args.format, scale).map(|_| ())
+        }
+        (_, Some(output)) => std::fs::File::create(output)
+            .and_then(|mut file| frames::write_ansi(&recorded, &mut file, Duration::ZERO)),
+        (_, None) => frames::write_ansi(&recorded, &mut std::io::stdout(), delay),
+    };
+    match result {
+        Ok(()) => eprintln!("Day {:02}: {} frames", day, recorded.len()),
+        Err(e) => {
+            eprintln!("Couldn't write the frames: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn generate(gen_args: &GenArgs) {
+    let day = gen_args.day;
+    let generator = match gen::find(day) {
+        Some(generator) => generator,
+        None => {
+            eprintln!(
+                "Day {:02} hasn't been solved, so there's nothing to generate",
+                day
+            );
+            std::process::exit(1);
+        }
+    };
+    let size = gen_args.size.unwrap_or(generator.default_size);
+    if size > generator.max_size {
+        eprintln!(
+            "Day {:02} can't handle more than {} {}",
+            day,
+            generator.max_size,
+            generator.unit.unwrap_or("of anything")
+        );
+        std::process::exit(2);
+    }
+
+    let result = match &gen_args.output {
+        Some(output) => std::fs::File::create(output).and_then(|file| {
+            let mut out = std::io::BufWriter::new(file);
+            generator.write(gen_args.seed, size, &mut out)?;
+            out.flush()
+        }),
+        None => {
+            let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+            generator
+                .write(gen_args.seed, size, &mut out)
+                .and_then(|_| out.flush())
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("Couldn't write the input: {}", e);
+        std::process::exit(1);
+    }
+
+    match generator.unit {
+        Some(unit) => eprintln!("Day {:02}: {} {}, seed {}", day, size, unit, gen_args.seed),
+        None => eprintln!("Day {:02}: seed {}", day, gen_args.seed),
+    }
+}
+
+/// Scales the largest frame up to at least 200 pixels across, as long as that's no
+/// more than 16 pixels a cell.
+fn default_scale(recorded: &[helpers::grid::Grid<u8>]) -> usize {
+    let largest = recorded
+        .iter()
+        .map(|frame| frame.width().max(frame.height()))
+        .max()
+        .unwrap_or(1);
+    (200 / largest.max(1)).clamp(1, 16)
+}
+
+fn answers_path(answers: &Option<String>) -> PathBuf {
+    answers
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(answers::default_path)
+}
+
+fn load_answers<F>(answers: &Option<String>, load: F) -> Registry
+where
+    F: FnOnce(&std::path::Path) -> Result<Registry, helpers::ParseError>,
+{
+    match load(&answers_path(answers)) {
+        Ok(registry) => registry,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// The input file to use for a day, either given on the command line or from the
+/// input cache, downloading it if need be.
+fn input_for(run_args: &RunArgs, day: u8) -> Result<String, InputError> {
+    match &run_args.input {
+        Some(input) => Ok(input.clone()),
+        None => InputSource::from_env()
+            .resolve(day)
+            .map(|path| path.to_string_lossy().into_owned()),
+    }
+}
+
+fn select_puzzles(days: &Days) -> Vec<&'static dyn Puzzle> {
+    match days {
+        Days::All => puzzles::PUZZLES.to_vec(),
+        Days::Only(days) => days
+            .iter()
+            .filter_map(|&day| {
+                let puzzle = puzzles::find(day);
+                if puzzle.is_none() {
+                    eprintln!("Day {} hasn't been solved, skipping it", day);
+                }
+                puzzle
+            })
+            .collect(),
+    }
+}
+
+fn summary_table(runs: &[DayRun]) -> Table {
+    let mut table = Table::new(&["Day", "Part 1", "Time", "Part 2", "Time"]);
+    let mut total = Duration::default();
+
+    for DayRun { day, answers, .. } in runs {
+        let mut row = vec![format!("{:02}", day)];
+        if answers.is_empty() {
+            row.extend([
+                String::from("(bad input)"),
+                String::new(),
+                String::new(),
+                String::new(),
+            ]);
+            table.push(row);
+            continue;
+        }
+        for part in [Part::One, Part::Two] {
+            match answers.iter().find(|(p, _)| *p == part) {
+                Some((_, answer)) => {
+                    total += answer.elapsed();
+                    row.push(summary_value(&answer.value));
+                    row.push(format!("{:.2?}", answer.elapsed()));
+                }
+                None => row.extend([String::new(), String::new()]),
+            }
+        }
+        table.push(row);
+    }
+
+    table.push(vec![
+        String::from("Total"),
+        String::new(),
+        String::new(),
+        String::new(),
+        format!("{:.2?}", total),
+    ]);
+    table
+}
+
+/// Multi-line answers (like day 13's folded code) don't fit in a table cell.
+fn summary_value(value: &str) -> String {
+    if value.trim().contains('\n') {
+        String::from("(printed above)")
+    } else {
+        value.trim().to_string()
+    }
+}
@@ -0,0 +1,43 @@
+use helpers::Puzzle;
+
+pub static PUZZLES: &[&dyn Puzzle] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+];
+
+pub fn find(day: u8) -> Option<&'static dyn Puzzle> {
+    PUZZLES.iter().copied().find(|p| p.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzles_in_order() {
+        for (i, puzzle) in PUZZLES.iter().enumerate() {
+            assert_eq!(i + 1, puzzle.day() as usize);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+mod report;
+mod window;
+
+use helpers::{
+    parse::{InputFile, Span},
+    stream::LineReader,
+    ParseError, Solution,
+};
+use std::io::BufRead;
+
+pub use report::{Gap, Options, Outlier, Report, RunningStats};
+pub use window::{Trend, TrendTracker, Window};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<i32>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(file_name: &str) -> Result<Vec<i32>, ParseError> {
+        parse_ints_from_file(file_name)
+    }
+
+    fn part1(readings: &Vec<i32>) -> usize {
+        Trend::of(readings.iter().copied(), 1).increases
+    }
+
+    fn part2(readings: &Vec<i32>) -> usize {
+        Trend::of(readings.iter().copied(), 3).increases
+    }
+
+    fn stream(file_name: &str) -> Option<Result<(usize, usize), ParseError>> {
+        Some(LineReader::open(file_name).and_then(solve_stream))
+    }
+}
+
+/// Both parts in one pass over the readings, holding no more than a window of them.
+pub fn solve_stream<R: BufRead>(lines: LineReader<R>) -> Result<(usize, usize), ParseError> {
+    let mut singles = Trend::counter(1);
+    let mut triples = Trend::counter(3);
+    for depth in lines.parse_lines(parse_depth) {
+        let depth = depth?;
+        singles.push(depth);
+        triples.push(depth);
+    }
+    Ok((singles.trend().increases, triples.trend().increases))
+}
+
+/// How the sums of windows of `size` readings change across a whole file.
+pub fn trend_from_file(file_name: &str, size: usize) -> Result<Trend, ParseError> {
+    Ok(Trend::of(parse_ints_from_file(file_name)?, size))
+}
+
+/// A depth report on a whole file, see `Report`.
+pub fn report_from_file(file_name: &str, options: &Options) -> Result<Report, ParseError> {
+    Ok(Report::of(parse_ints_from_file(file_name)?, options))
+}
+
+fn parse_ints_from_file(file_name: &str) -> Result<Vec<i32>, ParseError> {
+    let input = InputFile::open(file_name)?;
+    input.lines().map(parse_depth).collect()
+}
+
+fn parse_depth(line: Span) -> Result<i32, ParseError> {
+    line.trim().parse("a depth reading")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let answer = Day01::solve_part1("test_input_1.txt");
+        assert_eq!(3, answer);
+    }
+
+    #[test]
+    fn part1_final() {
+        let answer = Day01::solve_part1("input.txt");
+        assert_eq!(1521, answer);
+    }
+
+    #[test]
+    fn test_part2() {
+        let answer = Day01::solve_part2("test_input_2.txt");
+        assert_eq!(5, answer);
+    }
+
+    #[test]
+    fn part2_final() {
+        let answer = Day01::solve_part2("input.txt");
+        assert_eq!(1543, answer);
+    }
+
+    #[test]
+    fn test_trend_from_file() {
+        let trend = trend_from_file("test_input_1.txt", 1).unwrap();
+        assert_eq!(3, trend.increases);
+        assert!(trend_from_file("input.txt", 4).unwrap().longest_run > 1);
+    }
+
+    #[test]
+    fn test_report_from_file() {
+        let report = report_from_file("input.txt", &Options::default()).unwrap();
+        assert_eq!(2000, report.stats.count);
+        assert_eq!(1521, report.trends[0].increases);
+        assert_eq!(1543, report.trends[1].increases);
+        assert_eq!(2, report.gaps.len());
+    }
+
+    #[test]
+    fn test_stream() {
+        let lines = LineReader::open("input.txt").unwrap();
+        assert_eq!(Ok((1521, 1543)), solve_stream(lines));
+    }
+
+    /// Endless `1`, `2`, `1`, `2`... lines, made up as they're read.
+    struct Zigzag {
+        lines_left: usize,
+    }
+
+    impl std::io::Read for Zigzag {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.lines_left == 0 || buf.len() < 2 {
+                return Ok(0);
+            }
+            self.lines_left -= 1;
+            buf[0] = if self.lines_left.is_multiple_of(2) {
+                b'2'
+            } else {
+                b'1'
+            };
+            buf[1] = b'\n';
+            Ok(2)
+        }
+    }
+
+    #[test]
+    fn test_stream_generated() {
+        let reader = std::io::BufReader::new(Zigzag {
+            lines_left: 1_000_000,
+        });
+        let lines = LineReader::new("zigzag", reader);
+        // every 2 is an increase, and so is every window of three that starts with 1
+        assert_eq!(Ok((500_000, 499_999)), solve_stream(lines));
+    }
+}
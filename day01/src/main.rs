@@ -1,59 +1,70 @@
-fn main() {
-    let answer = part1("input.txt");
-    println!("{}", answer);
+use day01::Options;
+use helpers::{is_file_arg, parse_number};
 
-    let answer_two = part2("input.txt");
-    println!("{}", answer_two);
-}
+const USAGE: &str = "Usage:
+    day01 [FILE]
+    day01 report [FILE] [--window <N>] [--sigmas <X>] [--gap <N>] [--format <table|json>]
 
-fn part1(file_name: &str) -> usize {
-    parse_ints_from_file(file_name)
-        .windows(2)
-        .filter(|pair| pair[0] < pair[1])
-        .count()
-}
-
-fn part2(file_name: &str) -> usize {
-    parse_ints_from_file(file_name)
-        .windows(3)
-        .map(|w| w.iter().sum())
-        .collect::<Vec<i32>>()
-        .windows(2)
-        .filter(|pair| pair[0] < pair[1])
-        .count()
-}
+report describes the sonar sweep in FILE (or the cached input): statistics of the
+depths, how often they go up, readings more than --sigmas standard deviations (3 by
+default) from the mean of the --window readings before them (10 by default), and
+gaps where the depth jumps by more than --gap (40 by default).";
 
-fn parse_ints_from_file(file_name: &str) -> Vec<i32> {
-    helpers::read_lines_panicky(file_name)
-        .map(|l| l.parse().unwrap())
-        .collect()
-}
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.first().map(String::as_str) != Some("report") {
+        helpers::run_main(&day01::Day01);
+        return;
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    let (file_name, options, json) = match parse_report_args(&args[1..]) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    };
+    let file_name = file_name.unwrap_or_else(|| helpers::resolve_or_exit(1));
 
-    #[test]
-    fn test_part1() {
-        let answer = part1("test_input_1.txt");
-        assert_eq!(3, answer);
+    match day01::report_from_file(&file_name, &options) {
+        Ok(report) if json => println!("{}", report.json()),
+        Ok(report) => print!("{}", report.table()),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
     }
+}
 
-    #[test]
-    fn part1_final() {
-        let answer = part1("input.txt");
-        assert_eq!(1521, answer);
-    }
+fn parse_report_args(args: &[String]) -> Result<(Option<String>, Options, bool), String> {
+    let mut file_name = None;
+    let mut options = Options::default();
+    let mut json = false;
+    let mut args = args.iter();
 
-    #[test]
-    fn test_part2() {
-        let answer = part2("test_input_2.txt");
-        assert_eq!(5, answer);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--window" | "-w" => options.window = parse_number(arg, value()?)?,
+            "--sigmas" | "-s" => options.sigmas = parse_number(arg, value()?)?,
+            "--gap" | "-g" => options.gap = parse_number(arg, value()?)?,
+            "--format" | "-f" => {
+                json = match value()?.as_str() {
+                    "table" => false,
+                    "json" => true,
+                    other => return Err(format!("Format must be table or json, not `{}`", other)),
+                }
+            }
+            _ if file_name.is_none() && is_file_arg(arg) => file_name = Some(arg.clone()),
+            _ => return Err(format!("Unexpected argument `{}`", arg)),
+        }
     }
 
-    #[test]
-    fn part2_final() {
-        let answer = part2("input.txt");
-        assert_eq!(1543, answer);
+    if options.window == 0 {
+        return Err(String::from("--window must be at least 1"));
     }
+    Ok((file_name, options, json))
 }
@@ -0,0 +1,401 @@
+use crate::window::{Trend, Window};
+use helpers::table::Table;
+use std::fmt::Write;
+
+/// What counts as unusual in a depth report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    /// How many readings before each one to compare it with.
+    pub window: usize,
+    /// How many standard deviations from the window's mean a reading has to be to
+    /// count as an outlier.
+    pub sigmas: f64,
+    /// How far the depth has to change from one reading to the next to count as a
+    /// gap in the sweep.
+    pub gap: i32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            window: 10,
+            sigmas: 3.0,
+            gap: 40,
+        }
+    }
+}
+
+/// Count, extremes, mean and standard deviation, updated a reading at a time
+/// (with Welford's method, so the mean and variance don't lose precision on long
+/// sweeps).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunningStats {
+    pub count: usize,
+    pub min: Option<i32>,
+    pub max: Option<i32>,
+    mean: f64,
+    /// The sum of squared differences from the mean.
+    m2: f64,
+}
+
+impl RunningStats {
+    pub fn push(&mut self, reading: i32) {
+        self.count += 1;
+        self.min = Some(self.min.map_or(reading, |min| min.min(reading)));
+        self.max = Some(self.max.map_or(reading, |max| max.max(reading)));
+        let delta = reading as f64 - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (reading as f64 - self.mean);
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    /// The population standard deviation.
+    pub fn std_dev(&self) -> Option<f64> {
+        (self.count > 0).then(|| (self.m2 / self.count as f64).sqrt())
+    }
+}
+
+/// A reading a long way from the ones just before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outlier {
+    pub index: usize,
+    pub depth: i32,
+    pub window_mean: f64,
+    /// How many of the window's standard deviations the reading is from its mean.
+    pub sigmas: f64,
+}
+
+/// A jump in depth between one reading and the next.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gap {
+    /// The reading after the jump.
+    pub index: usize,
+    pub from: i32,
+    pub to: i32,
+}
+
+impl Gap {
+    /// How far the depth moved, in 64 bits since readings can be a whole `i32` apart.
+    pub fn change(&self) -> i64 {
+        i64::from(self.to) - i64::from(self.from)
+    }
+}
+
+/// Everything worth knowing about a sonar sweep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub options: Options,
+    pub stats: RunningStats,
+    /// The puzzle's comparisons, of single readings and of windows of three.
+    pub trends: Vec<Trend>,
+    pub outliers: Vec<Outlier>,
+    pub gaps: Vec<Gap>,
+}
+
+impl Report {
+    pub fn of<I: IntoIterator<Item = i32>>(readings: I, options: &Options) -> Self {
+        let mut stats = RunningStats::default();
+        let mut trends = vec![Trend::tracker(1), Trend::tracker(3)];
+        let mut window = Window::new(options.window);
+        let mut outliers = Vec::new();
+        let mut gaps = Vec::new();
+        let mut previous: Option<i32> = None;
+
+        for (index, depth) in readings.into_iter().enumerate() {
+            stats.push(depth);
+            trends.iter_mut().for_each(|t| t.push(depth));
+
+            if let (Some(mean), Some(std_dev)) = (window.mean(), window.std_dev()) {
+                let distance = (depth as f64 - mean).abs();
+                // a window that hasn't moved at all makes any change stand out
+                let sigmas = if std_dev > 0.0 {
+                    distance / std_dev
+                } else if distance > 0.0 {
+                    f64::INFINITY
+                } else {
+                    0.0
+                };
+                if window.is_full() && sigmas > options.sigmas {
+                    outliers.push(Outlier {
+                        index,
+                        depth,
+                        window_mean: mean,
+                        sigmas,
+                    });
+                }
+            }
+            window.push(depth);
+
+            if let Some(from) = previous {
+                let gap = Gap {
+                    index,
+                    from,
+                    to: depth,
+                };
+                if gap.change().abs() > i64::from(options.gap) {
+                    gaps.push(gap);
+                }
+            }
+            previous = Some(depth);
+        }
+
+        Self {
+            options: options.clone(),
+            stats,
+            trends: trends.into_iter().map(|t| t.finish()).collect(),
+            outliers,
+            gaps,
+        }
+    }
+
+    /// The report as plain-text tables.
+    pub fn table(&self) -> String {
+        let mut text = String::new();
+        let stats = &self.stats;
+        let mut summary = Table::new(&["Readings", "Min", "Max", "Mean", "Std dev"]);
+        summary.push(vec![
+            stats.count.to_string(),
+            optional(stats.min, |min| min.to_string()),
+            optional(stats.max, |max| max.to_string()),
+            optional(stats.mean(), |mean| format!("{:.2}", mean)),
+            optional(stats.std_dev(), |std_dev| format!("{:.2}", std_dev)),
+        ]);
+        writeln!(text, "{}", summary).unwrap();
+
+        let mut trends = Table::new(&["Window", "Increases", "Decreases", "Longest run"]);
+        for trend in self.trends.iter() {
+            trends.push(vec![
+                trend.size.to_string(),
+                trend.increases.to_string(),
+                trend.decreases.len().to_string(),
+                trend.longest_run.to_string(),
+            ]);
+        }
+        writeln!(text, "{}", trends).unwrap();
+
+        writeln!(
+            text,
+            "{} outliers, more than {} standard deviations from the mean of the {} readings before",
+            self.outliers.len(),
+            self.options.sigmas,
+            self.options.window
+        )
+        .unwrap();
+        if !self.outliers.is_empty() {
+            let mut outliers = Table::new(&["Index", "Depth", "Window mean", "Std devs"]);
+            for outlier in self.outliers.iter() {
+                outliers.push(vec![
+                    outlier.index.to_string(),
+                    outlier.depth.to_string(),
+                    format!("{:.2}", outlier.window_mean),
+                    format!("{:.2}", outlier.sigmas),
+                ]);
+            }
+            write!(text, "\n{}", outliers).unwrap();
+        }
+
+        writeln!(
+            text,
+            "\n{} gaps, where the depth changed by more than {}",
+            self.gaps.len(),
+            self.options.gap
+        )
+        .unwrap();
+        if !self.gaps.is_empty() {
+            let mut gaps = Table::new(&["Index", "From", "To", "Change"]);
+            for gap in self.gaps.iter() {
+                gaps.push(vec![
+                    gap.index.to_string(),
+                    gap.from.to_string(),
+                    gap.to.to_string(),
+                    format!("{:+}", gap.change()),
+                ]);
+            }
+            write!(text, "\n{}", gaps).unwrap();
+        }
+        text
+    }
+
+    /// The report as a single JSON object.
+    pub fn json(&self) -> String {
+        let stats = &self.stats;
+        let mut json = String::from("{");
+        write!(
+            json,
+            "\"options\": {{\"window\": {}, \"sigmas\": {}, \"gap\": {}}}, ",
+            self.options.window,
+            json_number(self.options.sigmas),
+            self.options.gap
+        )
+        .unwrap();
+        write!(
+            json,
+            "\"stats\": {{\"count\": {}, \"min\": {}, \"max\": {}, \"mean\": {}, \"std_dev\": {}}}, ",
+            stats.count,
+            optional_json(stats.min.map(f64::from)),
+            optional_json(stats.max.map(f64::from)),
+            optional_json(stats.mean()),
+            optional_json(stats.std_dev())
+        )
+        .unwrap();
+
+        let trends = self.trends.iter().map(|trend| {
+            let decreases = trend
+                .decreases
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>();
+            format!(
+                "{{\"window\": {}, \"increases\": {}, \"longest_run\": {}, \"decreases\": [{}]}}",
+                trend.size,
+                trend.increases,
+                trend.longest_run,
+                decreases.join(", ")
+            )
+        });
+        write!(json, "\"trends\": [{}], ", join(trends)).unwrap();
+
+        let outliers = self.outliers.iter().map(|outlier| {
+            format!(
+                "{{\"index\": {}, \"depth\": {}, \"window_mean\": {}, \"sigmas\": {}}}",
+                outlier.index,
+                outlier.depth,
+                json_number(outlier.window_mean),
+                json_number(outlier.sigmas)
+            )
+        });
+        write!(json, "\"outliers\": [{}], ", join(outliers)).unwrap();
+
+        let gaps = self.gaps.iter().map(|gap| {
+            format!(
+                "{{\"index\": {}, \"from\": {}, \"to\": {}}}",
+                gap.index, gap.from, gap.to
+            )
+        });
+        write!(json, "\"gaps\": [{}]}}", join(gaps)).unwrap();
+        json
+    }
+}
+
+fn optional<T, F: FnOnce(T) -> String>(value: Option<T>, f: F) -> String {
+    value.map_or_else(|| String::from("-"), f)
+}
+
+fn optional_json(value: Option<f64>) -> String {
+    value.map_or_else(|| String::from("null"), json_number)
+}
+
+/// JSON has no infinity, so an outlier from a perfectly flat window gets `null`.
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        format!("{}", (value * 1000.0).round() / 1000.0)
+    } else {
+        String::from("null")
+    }
+}
+
+fn join<I: Iterator<Item = String>>(items: I) -> String {
+    items.collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> Options {
+        Options {
+            window: 3,
+            sigmas: 2.0,
+            gap: 10,
+        }
+    }
+
+    #[test]
+    fn test_running_stats() {
+        let mut stats = RunningStats::default();
+        assert_eq!((None, None), (stats.mean(), stats.std_dev()));
+        [2, 4, 4, 4, 5, 5, 7, 9].iter().for_each(|&r| stats.push(r));
+        assert_eq!(Some(5.0), stats.mean());
+        assert_eq!(Some(2.0), stats.std_dev());
+        assert_eq!((Some(2), Some(9)), (stats.min, stats.max));
+    }
+
+    #[test]
+    fn test_extreme_gaps() {
+        let report = Report::of(vec![i32::MIN, i32::MAX, i32::MAX], &options());
+        assert_eq!(1, report.gaps.len());
+        assert_eq!(u32::MAX as i64, report.gaps[0].change());
+        assert!(report.table().contains("+4294967295"));
+    }
+
+    #[test]
+    fn test_report() {
+        let report = Report::of(
+            vec![100, 101, 102, 101, 130, 131, 131, 131, 125],
+            &options(),
+        );
+        assert_eq!(9, report.stats.count);
+        assert_eq!(4, report.trends[0].increases);
+        assert_eq!(vec![3, 8], report.trends[0].decreases);
+
+        let flagged = report.outliers.iter().map(|o| o.index).collect::<Vec<_>>();
+        // 130 after 101..102, then 125 after three flat 131s
+        assert_eq!(vec![4, 8], flagged);
+        assert!(report.outliers[1].sigmas.is_infinite());
+        assert_eq!(
+            vec![Gap {
+                index: 4,
+                from: 101,
+                to: 130
+            }],
+            report.gaps
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        let report = Report::of(vec![100, 101, 102, 101, 130], &options());
+        let expected = "\
+Readings | Min | Max | Mean   | Std dev
+---------+-----+-----+--------+--------
+5        | 100 | 130 | 106.80 | 11.62
+
+Window | Increases | Decreases | Longest run
+-------+-----------+-----------+------------
+1      | 3         | 1         | 3
+3      | 2         | 0         | 3
+
+1 outliers, more than 2 standard deviations from the mean of the 3 readings before
+
+Index | Depth | Window mean | Std devs
+------+-------+-------------+---------
+4     | 130   | 101.33      | 60.81
+
+1 gaps, where the depth changed by more than 10
+
+Index | From | To  | Change
+------+------+-----+-------
+4     | 101  | 130 | +29
+";
+        assert_eq!(expected, report.table());
+    }
+
+    #[test]
+    fn test_json() {
+        let report = Report::of(vec![5, 5, 5, 5, 6], &options());
+        let expected = "{\
+\"options\": {\"window\": 3, \"sigmas\": 2, \"gap\": 10}, \
+\"stats\": {\"count\": 5, \"min\": 5, \"max\": 6, \"mean\": 5.2, \"std_dev\": 0.4}, \
+\"trends\": [{\"window\": 1, \"increases\": 1, \"longest_run\": 2, \"decreases\": []}, \
+{\"window\": 3, \"increases\": 1, \"longest_run\": 2, \"decreases\": []}], \
+\"outliers\": [{\"index\": 4, \"depth\": 6, \"window_mean\": 5, \"sigmas\": null}], \
+\"gaps\": []}";
+        assert_eq!(expected, report.json());
+        assert!(Report::of(vec![], &options())
+            .json()
+            .contains("\"mean\": null"));
+    }
+}
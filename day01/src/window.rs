@@ -0,0 +1,302 @@
+use std::collections::VecDeque;
+
+/// The last `size` readings, with their sum, minimum, maximum, mean and standard
+/// deviation kept up to date as readings are pushed, each in O(1) amortised time.
+pub struct Window {
+    size: usize,
+    readings: VecDeque<i32>,
+    sum: i64,
+    /// The sum of the squares, which is all the standard deviation needs on top.
+    squares: i128,
+    /// How many readings have been pushed, so each can be told apart by its index.
+    pushed: usize,
+    /// Readings that could still become the minimum, in increasing order, each with
+    /// its index. Anything larger than a later reading never will be.
+    mins: VecDeque<(usize, i32)>,
+    /// The same for the maximum, in decreasing order.
+    maxes: VecDeque<(usize, i32)>,
+}
+
+impl Window {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "a window has to hold at least one reading");
+        Self {
+            size,
+            readings: VecDeque::with_capacity(size + 1),
+            sum: 0,
+            squares: 0,
+            pushed: 0,
+            mins: VecDeque::new(),
+            maxes: VecDeque::new(),
+        }
+    }
+
+    /// Adds a reading, returning the one that slid out of the window to make room.
+    pub fn push(&mut self, reading: i32) -> Option<i32> {
+        let index = self.pushed;
+        self.pushed += 1;
+        self.readings.push_back(reading);
+        self.sum += reading as i64;
+        self.squares += (reading as i128).pow(2);
+
+        while self.mins.back().is_some_and(|&(_, min)| min >= reading) {
+            self.mins.pop_back();
+        }
+        self.mins.push_back((index, reading));
+        while self.maxes.back().is_some_and(|&(_, max)| max <= reading) {
+            self.maxes.pop_back();
+        }
+        self.maxes.push_back((index, reading));
+
+        if self.readings.len() <= self.size {
+            return None;
+        }
+        let leaving = self.readings.pop_front().unwrap();
+        self.sum -= leaving as i64;
+        self.squares -= (leaving as i128).pow(2);
+        let oldest = index + 1 - self.size;
+        if self.mins.front().is_some_and(|&(i, _)| i < oldest) {
+            self.mins.pop_front();
+        }
+        if self.maxes.front().is_some_and(|&(i, _)| i < oldest) {
+            self.maxes.pop_front();
+        }
+        Some(leaving)
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn len(&self) -> usize {
+        self.readings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.readings.is_empty()
+    }
+
+    /// Whether the window has seen enough readings to fill it.
+    pub fn is_full(&self) -> bool {
+        self.readings.len() == self.size
+    }
+
+    pub fn sum(&self) -> i64 {
+        self.sum
+    }
+
+    pub fn min(&self) -> Option<i32> {
+        self.mins.front().map(|&(_, min)| min)
+    }
+
+    pub fn max(&self) -> Option<i32> {
+        self.maxes.front().map(|&(_, max)| max)
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.sum as f64 / self.len() as f64)
+        }
+    }
+
+    /// The population standard deviation of the readings in the window.
+    pub fn std_dev(&self) -> Option<f64> {
+        let n = self.len() as i128;
+        if n == 0 {
+            return None;
+        }
+        // n² times the variance, worked out exactly before the one division
+        let scaled = n * self.squares - (self.sum as i128).pow(2);
+        Some((scaled as f64).sqrt() / n as f64)
+    }
+}
+
+/// How the sum of a sliding window changes from one window to the next, across all
+/// the readings. Window `i` starts at reading `i`, so with a window size of 1 the
+/// windows are just the readings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trend {
+    /// How many readings are in each window.
+    pub size: usize,
+    /// How many windows have a larger sum than the one before.
+    pub increases: usize,
+    /// The most windows in a row whose sums keep going up.
+    pub longest_run: usize,
+    /// Each window with a smaller sum than the one before.
+    pub decreases: Vec<usize>,
+    /// How many full windows there were.
+    pub windows: usize,
+}
+
+impl Trend {
+    /// Follows the trend of windows of `size` readings, one reading at a time.
+    pub fn tracker(size: usize) -> TrendTracker {
+        TrendTracker {
+            window: Window::new(size),
+            previous: None,
+            run: 0,
+            record_decreases: true,
+            trend: Trend {
+                size,
+                increases: 0,
+                longest_run: 0,
+                decreases: Vec::new(),
+                windows: 0,
+            },
+        }
+    }
+
+    /// Like `tracker`, but leaves `decreases` empty, so it never holds more than a
+    /// window of readings however many it's pushed.
+    pub fn counter(size: usize) -> TrendTracker {
+        TrendTracker {
+            record_decreases: false,
+            ..Self::tracker(size)
+        }
+    }
+
+    pub fn of<I: IntoIterator<Item = i32>>(readings: I, size: usize) -> Self {
+        let mut tracker = Self::tracker(size);
+        readings.into_iter().for_each(|r| tracker.push(r));
+        tracker.finish()
+    }
+}
+
+/// Builds up a `Trend` as readings arrive.
+pub struct TrendTracker {
+    window: Window,
+    /// The last window's sum.
+    previous: Option<i64>,
+    /// How many windows in a row have gone up, counting the one they started from.
+    run: usize,
+    /// Off for `Trend::counter`.
+    record_decreases: bool,
+    trend: Trend,
+}
+
+impl TrendTracker {
+    pub fn push(&mut self, reading: i32) {
+        self.window.push(reading);
+        if !self.window.is_full() {
+            return;
+        }
+
+        let trend = &mut self.trend;
+        let sum = self.window.sum();
+        match self.previous {
+            Some(previous) if sum > previous => {
+                trend.increases += 1;
+                self.run += 1;
+            }
+            Some(previous) => {
+                if sum < previous && self.record_decreases {
+                    trend.decreases.push(trend.windows);
+                }
+                self.run = 1;
+            }
+            None => self.run = 1,
+        }
+        trend.longest_run = trend.longest_run.max(self.run);
+        trend.windows += 1;
+        self.previous = Some(sum);
+    }
+
+    /// The trend so far.
+    pub fn trend(&self) -> &Trend {
+        &self.trend
+    }
+
+    pub fn finish(self) -> Trend {
+        self.trend
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng;
+
+    #[test]
+    fn test_window() {
+        let mut window = Window::new(3);
+        assert_eq!(
+            (None, None, None),
+            (window.min(), window.max(), window.mean())
+        );
+
+        let leaving = [5, 1, 4, 2, 8, 3].map(|r| window.push(r));
+        assert_eq!([None, None, None, Some(5), Some(1), Some(4)], leaving);
+        assert!(window.is_full());
+        assert_eq!(13, window.sum());
+        assert_eq!((Some(2), Some(8)), (window.min(), window.max()));
+        assert_eq!(Some(13.0 / 3.0), window.mean());
+        let std_dev = window.std_dev().unwrap();
+        assert!(
+            (std_dev - (62.0f64 / 9.0).sqrt()).abs() < 1e-9,
+            "{}",
+            std_dev
+        );
+    }
+
+    #[test]
+    fn test_random_windows() {
+        rng::check("window aggregates", 50, |rng| {
+            let size = rng.range(1..=6) as usize;
+            let readings = (0..rng.index(40))
+                .map(|_| rng.range(-20..=20) as i32)
+                .collect::<Vec<_>>();
+
+            let mut window = Window::new(size);
+            for (i, &reading) in readings.iter().enumerate() {
+                window.push(reading);
+                let expected = &readings[(i + 1).saturating_sub(size)..=i];
+                assert_eq!(
+                    expected.iter().map(|&r| r as i64).sum::<i64>(),
+                    window.sum()
+                );
+                assert_eq!(expected.iter().min().copied(), window.min());
+                assert_eq!(expected.iter().max().copied(), window.max());
+                let mean = expected.iter().sum::<i32>() as f64 / expected.len() as f64;
+                let variance = expected
+                    .iter()
+                    .map(|&r| (r as f64 - mean).powi(2))
+                    .sum::<f64>()
+                    / expected.len() as f64;
+                assert!((variance.sqrt() - window.std_dev().unwrap()).abs() < 1e-9);
+            }
+        });
+    }
+
+    #[test]
+    fn test_trend() {
+        let readings = [1, 2, 3, 3, 2, 4, 5, 6, 7, 1];
+        let trend = Trend::of(readings, 1);
+        assert_eq!(6, trend.increases);
+        assert_eq!(5, trend.longest_run);
+        assert_eq!(vec![4, 9], trend.decreases);
+        assert_eq!(10, trend.windows);
+
+        // sums 6, 8, 8, 9, 11, 15, 18, 14
+        let trend = Trend::of(readings, 3);
+        assert_eq!(5, trend.increases);
+        assert_eq!(5, trend.longest_run);
+        assert_eq!(vec![7], trend.decreases);
+
+        let mut counter = Trend::counter(1);
+        readings.iter().for_each(|&r| counter.push(r));
+        let counted = counter.finish();
+        assert_eq!(
+            (6, 5, 10),
+            (counted.increases, counted.longest_run, counted.windows)
+        );
+        assert!(counted.decreases.is_empty());
+
+        let too_few = Trend::of([1, 2], 3);
+        assert_eq!(
+            (0, 0, 0),
+            (too_few.increases, too_few.longest_run, too_few.windows)
+        );
+    }
+}
@@ -0,0 +1,610 @@
+use helpers::{parse::InputFile, stream::LineReader, ParseError, Solution};
+use std::io::BufRead;
+
+mod script;
+mod trajectory;
+
+pub use script::{Command, Script, ScriptParser, Statement, MAX_COMMANDS};
+pub use trajectory::{csv, svg, Point, Trajectory, View};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Script;
+    type Part1 = i128;
+    type Part2 = i128;
+
+    /// Turns down a script on the line that takes a model out of range, so the parts
+    /// can't.
+    fn parse(file_name: &str) -> Result<Script, ParseError> {
+        let mut course = Course::default();
+        Script::parse_checked(&InputFile::open(file_name)?, |s| course.follow(s))
+    }
+
+    fn part1(script: &Script) -> i128 {
+        let initial = Position::default();
+        let final_position = initial
+            .apply_all_commands(script.commands())
+            .expect("checked when the script was parsed");
+        answer(final_position.horizontal, final_position.depth)
+    }
+
+    fn part2(script: &Script) -> i128 {
+        let initial = PositionWithAim::default();
+        let final_position = initial
+            .apply_all_commands(script.commands())
+            .expect("checked when the script was parsed");
+        answer(final_position.horizontal, final_position.depth)
+    }
+
+    fn stream(file_name: &str) -> Option<Result<(i128, i128), ParseError>> {
+        Some(LineReader::open(file_name).and_then(solve_stream))
+    }
+}
+
+/// The distance times the depth, which can outgrow 64 bits even when neither of them
+/// does.
+fn answer(horizontal: i64, depth: i64) -> i128 {
+    i128::from(horizontal) * i128::from(depth)
+}
+
+/// What a model expects of the commands it follows.
+const IN_RANGE: &str = "commands that keep the position, depth and aim within an i64";
+
+/// Both of the puzzle's models following a script together, a statement at a time.
+/// The heading model's aim always matches the aim model's, and the rest of it is
+/// floating point, so it can't go out of range if they don't.
+#[derive(Debug, Default)]
+struct Course {
+    position: Position,
+    with_aim: PositionWithAim,
+}
+
+impl Course {
+    fn follow(&mut self, statement: &Statement) -> Result<(), String> {
+        let position = std::mem::take(&mut self.position);
+        self.position = position
+            .apply_all_commands(statement.commands())
+            .ok_or(IN_RANGE)?;
+        let with_aim = std::mem::take(&mut self.with_aim);
+        self.with_aim = with_aim
+            .apply_all_commands(statement.commands())
+            .ok_or(IN_RANGE)?;
+        Ok(())
+    }
+}
+
+/// Both parts in one pass, steering both submarines as each statement of the script
+/// is finished.
+pub fn solve_stream<R: BufRead>(mut lines: LineReader<R>) -> Result<(i128, i128), ParseError> {
+    let mut parser = ScriptParser::default();
+    let mut course = Course::default();
+    while let Some(line) = lines.next_line() {
+        let line = line?;
+        for statement in parser.line(line)? {
+            course
+                .follow(&statement)
+                .map_err(|expected| line.error(&expected))?;
+        }
+    }
+    if let Some(expected) = parser.missing() {
+        return Err(lines.error(&expected, "end of file"));
+    }
+    let Course { position, with_aim } = course;
+    Ok((
+        answer(position.horizontal, position.depth),
+        answer(with_aim.horizontal, with_aim.depth),
+    ))
+}
+
+/// The most commands `trajectories_from_file` will follow, so each path it records
+/// is never more than one point longer than this.
+pub const MAX_TRACED: u64 = 1_000_000;
+
+/// Follows a script with each of the named models (`position`, `aim` or
+/// `heading`), or all of them if none are named, recording everywhere they went.
+pub fn trajectories_from_file(
+    file_name: &str,
+    models: &[&str],
+) -> Result<Vec<Trajectory>, ParseError> {
+    let input = InputFile::open(file_name)?;
+    let mut course = Course::default();
+    let script = Script::parse_checked(&input, |s| course.follow(s))?;
+    let commands = script.command_count();
+    if commands > MAX_TRACED {
+        let expected = format!("no more than {} commands to trace", MAX_TRACED);
+        let found = format!("{} with the repeats written out", commands);
+        return Err(input.error(&expected, &found));
+    }
+
+    let wanted = |model| models.is_empty() || models.contains(&model);
+    let checked = "checked when the script was parsed";
+    let mut trajectories = Vec::new();
+    if wanted(Position::MODEL) {
+        let (_, trajectory) = Position::default()
+            .record_all_commands(script.commands())
+            .expect(checked);
+        trajectories.push(trajectory);
+    }
+    if wanted(PositionWithAim::MODEL) {
+        let (_, trajectory) = PositionWithAim::default()
+            .record_all_commands(script.commands())
+            .expect(checked);
+        trajectories.push(trajectory);
+    }
+    if wanted(PositionWithHeading::MODEL) {
+        let (_, trajectory) = PositionWithHeading::default()
+            .record_all_commands(script.commands())
+            .expect(checked);
+        trajectories.push(trajectory);
+    }
+    Ok(trajectories)
+}
+
+#[derive(Debug, Default)]
+pub struct Position {
+    pub horizontal: i64,
+    pub depth: i64,
+}
+
+impl CommandHandler for Position {
+    const MODEL: &'static str = "position";
+
+    fn handle(self, command: Command) -> Option<Self> {
+        let moved = match command {
+            Command::Forward(dist) => Self {
+                horizontal: self.horizontal.checked_add(dist.into())?,
+                depth: self.depth,
+            },
+            Command::Back(dist) => Self {
+                horizontal: self.horizontal.checked_sub(dist.into())?,
+                depth: self.depth,
+            },
+            Command::Down(dist) => Self {
+                horizontal: self.horizontal,
+                depth: self.depth.checked_add(dist.into())?,
+            },
+            Command::Up(dist) => Self {
+                horizontal: self.horizontal,
+                depth: self.depth.checked_sub(dist.into())?,
+            },
+            // without an aim or a heading to set, there's nothing to do
+            Command::Aim(_) | Command::Left(_) | Command::Right(_) => self,
+            Command::Reset => Self::default(),
+        };
+        Some(moved)
+    }
+
+    fn point(&self) -> Point {
+        Point {
+            horizontal: self.horizontal as f64,
+            sideways: 0.0,
+            depth: self.depth as f64,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PositionWithAim {
+    pub horizontal: i64,
+    pub depth: i64,
+    pub aim: i64,
+}
+
+impl PositionWithAim {
+    fn handle_forward(self, dist: i64) -> Option<Self> {
+        let depth_change = self.aim.checked_mul(dist)?;
+        Some(Self {
+            horizontal: self.horizontal.checked_add(dist)?,
+            depth: self.depth.checked_add(depth_change)?,
+            aim: self.aim,
+        })
+    }
+
+    fn change_aim(self, change: i64) -> Option<Self> {
+        Some(Self {
+            horizontal: self.horizontal,
+            depth: self.depth,
+            aim: self.aim.checked_add(change)?,
+        })
+    }
+}
+
+impl CommandHandler for PositionWithAim {
+    const MODEL: &'static str = "aim";
+
+    fn handle(self, command: Command) -> Option<Self> {
+        match command {
+            Command::Forward(dist) => self.handle_forward(dist.into()),
+            Command::Back(dist) => self.handle_forward(-i64::from(dist)),
+            Command::Down(change) => self.change_aim(change.into()),
+            Command::Up(change) => self.change_aim(-i64::from(change)),
+            Command::Aim(aim) => Some(Self {
+                aim: aim.into(),
+                ..self
+            }),
+            Command::Left(_) | Command::Right(_) => Some(self),
+            Command::Reset => Some(Self::default()),
+        }
+    }
+
+    fn point(&self) -> Point {
+        Point {
+            horizontal: self.horizontal as f64,
+            sideways: 0.0,
+            depth: self.depth as f64,
+        }
+    }
+}
+
+/// Steers with an aim like `PositionWithAim`, but can also turn, so going forward
+/// moves along the heading instead of always straight ahead.
+#[derive(Debug, Default)]
+pub struct PositionWithHeading {
+    pub horizontal: f64,
+    pub sideways: f64,
+    pub depth: f64,
+    pub aim: i64,
+    /// Degrees clockwise from the starting direction, from 0 up to 360.
+    pub heading: i32,
+}
+
+impl PositionWithHeading {
+    fn handle_forward(self, dist: f64) -> Self {
+        let (ahead, starboard) = self.direction();
+        Self {
+            horizontal: self.horizontal + ahead * dist,
+            sideways: self.sideways + starboard * dist,
+            depth: self.depth + self.aim as f64 * dist,
+            ..self
+        }
+    }
+
+    fn turn(self, degrees: i32) -> Self {
+        Self {
+            heading: (self.heading + degrees.rem_euclid(360)).rem_euclid(360),
+            ..self
+        }
+    }
+
+    /// How far ahead and to starboard one step along the heading goes, exactly for
+    /// right angles so the usual turns don't pick up rounding errors.
+    fn direction(&self) -> (f64, f64) {
+        match self.heading {
+            0 => (1.0, 0.0),
+            90 => (0.0, 1.0),
+            180 => (-1.0, 0.0),
+            270 => (0.0, -1.0),
+            heading => {
+                let radians = (heading as f64).to_radians();
+                (radians.cos(), radians.sin())
+            }
+        }
+    }
+}
+
+impl CommandHandler for PositionWithHeading {
+    const MODEL: &'static str = "heading";
+
+    fn handle(self, command: Command) -> Option<Self> {
+        let moved = match command {
+            Command::Forward(dist) => self.handle_forward(dist.into()),
+            Command::Back(dist) => self.handle_forward(-f64::from(dist)),
+            Command::Down(change) => Self {
+                aim: self.aim.checked_add(change.into())?,
+                ..self
+            },
+            Command::Up(change) => Self {
+                aim: self.aim.checked_sub(change.into())?,
+                ..self
+            },
+            Command::Aim(aim) => Self {
+                aim: aim.into(),
+                ..self
+            },
+            Command::Left(degrees) => self.turn(-degrees.rem_euclid(360)),
+            Command::Right(degrees) => self.turn(degrees),
+            Command::Reset => Self::default(),
+        };
+        Some(moved)
+    }
+
+    fn point(&self) -> Point {
+        Point {
+            horizontal: self.horizontal,
+            sideways: self.sideways,
+            depth: self.depth,
+        }
+    }
+}
+
+/// Something that can be steered by a script.
+pub trait CommandHandler
+where
+    Self: Default,
+{
+    /// What the model is called in a trajectory.
+    const MODEL: &'static str;
+
+    /// Where the submarine goes next, or `None` if that's further than the model can
+    /// count.
+    fn handle(self, command: Command) -> Option<Self>;
+
+    /// Where the submarine is now.
+    fn point(&self) -> Point;
+
+    fn apply_all_commands<I>(self, mut commands: I) -> Option<Self>
+    where
+        I: Iterator<Item = Command>,
+    {
+        commands.try_fold(self, |pos, command| pos.handle(command))
+    }
+
+    /// Like `apply_all_commands`, but also records the path taken: the starting
+    /// point, then the point after every command.
+    fn record_all_commands<I>(self, mut commands: I) -> Option<(Self, Trajectory)>
+    where
+        I: Iterator<Item = Command>,
+    {
+        let mut points = vec![self.point()];
+        let end = commands.try_fold(self, |pos, command| {
+            let pos = pos.handle(command)?;
+            points.push(pos.point());
+            Some(pos)
+        })?;
+        let trajectory = Trajectory {
+            model: Self::MODEL,
+            points,
+        };
+        Some((end, trajectory))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng::{check, Rng};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(150, Day02::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(1989265, Day02::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(900, Day02::solve_part2("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(2089174012, Day02::solve_part2("input.txt"));
+    }
+
+    #[test]
+    fn test_stream() {
+        let lines = LineReader::open("input.txt").unwrap();
+        assert_eq!(Ok((1989265, 2089174012)), solve_stream(lines));
+
+        let lines = LineReader::new("commands.txt", "forward 5\nsideways 3".as_bytes());
+        assert_eq!(2, solve_stream(lines).unwrap_err().line);
+
+        let script = "repeat 2 {\n  forward 3\n  down 1 }; back 1";
+        let lines = LineReader::new("commands.txt", script.as_bytes());
+        assert_eq!(Ok((10, 5)), solve_stream(lines));
+        let lines = LineReader::new("commands.txt", "repeat 2 {\nforward 1".as_bytes());
+        assert!(solve_stream(lines).is_err());
+    }
+
+    #[test]
+    fn test_far_out() {
+        let steer = |text: &str| solve_stream(LineReader::new("far.txt", text.as_bytes()));
+        // each of these used to overflow an i32 somewhere along the way
+        assert_eq!(Ok((0, 0)), steer("repeat 30 { forward 100000000 }"));
+        assert_eq!(
+            Ok((10_000_000_000, 1_000_000_000_000_000)),
+            steer("down 100000\nforward 100000")
+        );
+        let input = InputFile::from_text("far.txt", "down 2147483647\ndown 2147483647");
+        let script = Script::parse(&input).unwrap();
+        let end = PositionWithHeading::default()
+            .apply_all_commands(script.commands())
+            .unwrap();
+        assert_eq!(4294967294, end.aim);
+
+        // but a depth past an i64 is turned down on the line that got there
+        let max = i32::MAX;
+        let text = format!("aim {0}\nforward {0}\nforward {0}\nforward {0}", max);
+        let error = steer(&text).unwrap_err();
+        assert_eq!((4, 1), (error.line, error.column));
+        assert!(error.expected.contains("within an i64"), "{}", error);
+        let mut course = Course::default();
+        let input = InputFile::from_text("far.txt", &text);
+        let error = Script::parse_checked(&input, |s| course.follow(s)).unwrap_err();
+        assert_eq!((4, 1), (error.line, error.column));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = InputFile::from_text("commands.txt", "forward 5\nsideways 3");
+        let error = Script::parse(&input).unwrap_err();
+        assert_eq!((2, 1), (error.line, error.column));
+        assert_eq!("`sideways`", error.found);
+    }
+
+    #[test]
+    fn test_trajectories() {
+        let trajectories = trajectories_from_file("test_input.txt", &[]).unwrap();
+        let models = trajectories.iter().map(|t| t.model).collect::<Vec<_>>();
+        assert_eq!(vec!["position", "aim", "heading"], models);
+        assert!(trajectories.iter().all(|t| t.points.len() == 7));
+        let end = |t: &Trajectory| {
+            let point = t.points.last().unwrap();
+            (point.horizontal, point.depth)
+        };
+        assert_eq!((15.0, 10.0), end(&trajectories[0]));
+        // without any turns, the heading model goes exactly where the aim one does
+        assert_eq!((15.0, 60.0), end(&trajectories[1]));
+        assert_eq!(trajectories[1].points, trajectories[2].points);
+
+        let just_heading = trajectories_from_file("test_input.txt", &["heading"]).unwrap();
+        assert_eq!(trajectories[2..], just_heading);
+
+        let file_name = std::env::temp_dir().join(format!("day02-{}.txt", std::process::id()));
+        let script = format!("repeat {} {{ forward 1 }}", MAX_TRACED + 1);
+        std::fs::write(&file_name, script).unwrap();
+        let error = trajectories_from_file(file_name.to_str().unwrap(), &[]).unwrap_err();
+        assert!(error.expected.contains("commands to trace"), "{}", error);
+        std::fs::remove_file(file_name).unwrap();
+    }
+
+    #[test]
+    fn test_heading() {
+        let input = InputFile::from_text(
+            "commands.txt",
+            "down 1; forward 2\nright 90; forward 3\nleft 270; forward 1\nleft 45; back 2",
+        );
+        let script = Script::parse(&input).unwrap();
+        let (end, trajectory) = PositionWithHeading::default()
+            .record_all_commands(script.commands())
+            .unwrap();
+        assert_eq!(135, end.heading);
+        let points = trajectory
+            .points
+            .iter()
+            .map(|p| (p.horizontal, p.sideways, p.depth))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (0.0, 0.0, 0.0),
+                (0.0, 0.0, 0.0),
+                (2.0, 0.0, 2.0),
+                (2.0, 0.0, 2.0),
+                (2.0, 3.0, 5.0),
+                (2.0, 3.0, 5.0),
+                (1.0, 3.0, 6.0),
+            ],
+            points[..7]
+        );
+        // heading 135 going backwards moves ahead and to port
+        let last = points[8];
+        assert!((last.0 - (1.0 + 2f64.sqrt())).abs() < 1e-9, "{:?}", last);
+        assert!((last.1 - (3.0 - 2f64.sqrt())).abs() < 1e-9, "{:?}", last);
+        assert_eq!(4.0, last.2);
+
+        // turns as big as an i32 goes still come out between 0 and 360: right 127,
+        // then left 232
+        let input = InputFile::from_text(
+            "turns.txt",
+            "right 2147483647
+left -2147483648",
+        );
+        let script = Script::parse(&input).unwrap();
+        let end = PositionWithHeading::default()
+            .apply_all_commands(script.commands())
+            .unwrap();
+        assert_eq!(255, end.heading);
+    }
+
+    #[test]
+    fn test_handlers() {
+        let input = InputFile::from_text(
+            "commands.txt",
+            "down 2\nrepeat 3 { forward 4 }\nback 2  # 10 along\naim -1; forward 5",
+        );
+        let script = Script::parse(&input).unwrap();
+
+        let position = Position::default()
+            .apply_all_commands(script.commands())
+            .unwrap();
+        assert_eq!((15, 2), (position.horizontal, position.depth));
+        // the aim is 2 for 12 forward and 2 back, then -1 for 5
+        let with_aim = PositionWithAim::default()
+            .apply_all_commands(script.commands())
+            .unwrap();
+        assert_eq!(
+            (15, 15, -1),
+            (with_aim.horizontal, with_aim.depth, with_aim.aim)
+        );
+
+        let input = InputFile::from_text("commands.txt", "forward 5; down 3; reset; up 1");
+        let script = Script::parse(&input).unwrap();
+        let position = Position::default()
+            .apply_all_commands(script.commands())
+            .unwrap();
+        assert_eq!((0, -1), (position.horizontal, position.depth));
+    }
+
+    /// A random statement as it'd be written in a script, pushing the plain commands
+    /// it runs onto `flat`, one per line.
+    fn random_statement(rng: &mut Rng, depth: usize, flat: &mut Vec<String>) -> String {
+        if depth < 2 && rng.chance(0.2) {
+            let count = rng.index(4);
+            let mut once = Vec::new();
+            let body = (0..1 + rng.index(3))
+                .map(|_| random_statement(rng, depth + 1, &mut once))
+                .collect::<Vec<_>>();
+            for _ in 0..count {
+                flat.extend(once.iter().cloned());
+            }
+            let separator = if rng.chance(0.5) { "; " } else { "\n" };
+            return format!("repeat {} {{ {} }}", count, body.join(separator));
+        }
+        let command = format!(
+            "{} {}",
+            rng.choose(&["forward", "down", "up"]),
+            rng.range(0..=1000)
+        );
+        flat.push(command.clone());
+        command
+    }
+
+    /// Both parts worked out the simplest way, straight from the plain commands.
+    fn naive(flat: &[String]) -> (i128, i128) {
+        // without an aim, the depth is what the aim would be
+        let (mut horizontal, mut aim, mut aimed_depth) = (0i128, 0, 0);
+        for command in flat {
+            let (name, amount) = command.split_once(' ').unwrap();
+            let amount = amount.parse::<i128>().unwrap();
+            match name {
+                "forward" => {
+                    horizontal += amount;
+                    aimed_depth += aim * amount;
+                }
+                "down" => aim += amount,
+                _ => aim -= amount,
+            }
+        }
+        (horizontal * aim, horizontal * aimed_depth)
+    }
+
+    #[test]
+    fn test_random_scripts() {
+        check("day02 random scripts", 200, |rng| {
+            let mut flat = Vec::new();
+            let statements = (0..1 + rng.index(20))
+                .map(|_| random_statement(rng, 0, &mut flat))
+                .collect::<Vec<_>>();
+            let text = statements.join("\n");
+            let expected = naive(&flat);
+
+            let script = Script::parse(&InputFile::from_text("random", &text))
+                .unwrap_or_else(|e| panic!("\n{}", e));
+            let parts = (Day02::part1(&script), Day02::part2(&script));
+            assert_eq!(expected, parts, "\n{}", text);
+            let streamed = solve_stream(LineReader::new("random", text.as_bytes()));
+            assert_eq!(Ok(expected), streamed, "\n{}", text);
+
+            // the repeats written out run the same commands
+            let written_out = flat.join("\n");
+            let plain = Script::parse(&InputFile::from_text("flat", &written_out)).unwrap();
+            assert!(plain.commands().eq(script.commands()), "\n{}", text);
+        });
+    }
+}
@@ -1,149 +1,106 @@
-fn main() {
-    let answer_one = part1("input.txt");
-    println!("Answer one: {}", answer_one);
+use day02::View;
+use helpers::is_file_arg;
 
-    let answer_two = part2("input.txt");
-    println!("Answer two: {}", answer_two);
-}
+const USAGE: &str = "Usage:
+    day02 [FILE]
+    day02 trace [FILE] [--model <position|aim|heading>]... [--format <csv|svg>] [--view <side|top>]
 
-fn parse_commands(file_name: &str) -> impl Iterator<Item = Command> + '_ {
-    helpers::read_lines_panicky(file_name).map(Command::parse)
-}
+trace follows the commands in FILE (or the cached input) with each navigation model,
+or just the ones given with --model, and writes every point they pass through as CSV
+(the default) or as an SVG of their paths seen from the side or from above. Scripts
+that run more than a million commands are too long to trace.";
 
-fn part1(file_name: &str) -> i32 {
-    let initial = Position::default();
-    let commands = parse_commands(file_name);
-    let final_position = initial.apply_all_commands(commands);
-    final_position.horizontal * final_position.depth
-}
+const MODELS: [&str; 3] = ["position", "aim", "heading"];
 
-#[derive(Debug, Default)]
-struct Position {
-    horizontal: i32,
-    depth: i32,
+struct TraceArgs {
+    file_name: Option<String>,
+    models: Vec<String>,
+    svg: bool,
+    view: View,
 }
 
-impl CommandHandler for Position {
-    fn handle(self, command: Command) -> Self {
-        match command {
-            Command::Forward(dist) => Self {
-                horizontal: self.horizontal + dist,
-                depth: self.depth,
-            },
-            Command::Down(dist) => Self {
-                horizontal: self.horizontal,
-                depth: self.depth + dist,
-            },
-            Command::Up(dist) => Self {
-                horizontal: self.horizontal,
-                depth: self.depth - dist,
-            },
-        }
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.first().map(String::as_str) != Some("trace") {
+        helpers::run_main(&day02::Day02);
+        return;
     }
-}
-
-fn part2(file_name: &str) -> i32 {
-    let initial = PositionWithAim::default();
-    let commands = parse_commands(file_name);
-    let final_position = initial.apply_all_commands(commands);
-    final_position.horizontal * final_position.depth
-}
-
-#[derive(Default)]
-struct PositionWithAim {
-    horizontal: i32,
-    depth: i32,
-    aim: i32,
-}
 
-impl PositionWithAim {
-    fn handle_forward(self, dist: i32) -> Self {
-        let depth_change = self.aim * dist;
-        Self {
-            horizontal: self.horizontal + dist,
-            depth: self.depth + depth_change,
-            aim: self.aim,
+    let TraceArgs {
+        file_name,
+        models,
+        svg,
+        view,
+    } = match parse_trace_args(&args[1..]) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            std::process::exit(2);
         }
-    }
-
-    fn change_aim(self, change: i32) -> Self {
-        Self {
-            horizontal: self.horizontal,
-            depth: self.depth,
-            aim: self.aim + change,
+    };
+    let file_name = file_name.unwrap_or_else(|| helpers::resolve_or_exit(2));
+
+    let models = models.iter().map(String::as_str).collect::<Vec<_>>();
+    match day02::trajectories_from_file(&file_name, &models) {
+        Ok(trajectories) => {
+            if svg {
+                print!("{}", day02::svg(&trajectories, view));
+            } else {
+                print!("{}", day02::csv(&trajectories));
+            }
         }
-    }
-}
-
-impl CommandHandler for PositionWithAim {
-    fn handle(self, command: Command) -> Self {
-        match command {
-            Command::Forward(dist) => self.handle_forward(dist),
-            Command::Down(change) => self.change_aim(change),
-            Command::Up(change) => self.change_aim(-change),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
         }
     }
 }
 
-trait CommandHandler
-where
-    Self: Default,
-{
-    fn handle(self, command: Command) -> Self;
-
-    fn apply_all_commands<I>(self, commands: I) -> Self
-    where
-        I: Iterator<Item = Command>,
-    {
-        commands.fold(self, |pos, command| pos.handle(command))
-    }
-}
-
-enum Command {
-    Forward(i32),
-    Down(i32),
-    Up(i32),
-}
-
-impl Command {
-    fn parse<S: AsRef<str>>(line: S) -> Self {
-        let mut parts = line.as_ref().split(" ");
-        let direction = parts.next().expect("Direction").to_lowercase();
-        let distance: i32 = parts
-            .next()
-            .expect("Distance")
-            .parse()
-            .expect("Distance integer");
-        match direction.as_ref() {
-            "forward" => Command::Forward(distance),
-            "down" => Command::Down(distance),
-            "up" => Command::Up(distance),
-            _ => panic!("Unrecognized command direction"),
+fn parse_trace_args(args: &[String]) -> Result<TraceArgs, String> {
+    let mut parsed = TraceArgs {
+        file_name: None,
+        models: Vec::new(),
+        svg: false,
+        view: View::Side,
+    };
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--model" | "-m" => {
+                let model = value()?;
+                if !MODELS.contains(&model.as_str()) {
+                    return Err(format!(
+                        "Model must be one of {}, not `{}`",
+                        MODELS.join(", "),
+                        model
+                    ));
+                }
+                parsed.models.push(model.clone());
+            }
+            "--format" | "-f" => {
+                parsed.svg = match value()?.as_str() {
+                    "csv" => false,
+                    "svg" => true,
+                    other => return Err(format!("Format must be csv or svg, not `{}`", other)),
+                }
+            }
+            "--view" | "-v" => {
+                parsed.view = match value()?.as_str() {
+                    "side" => View::Side,
+                    "top" => View::Top,
+                    other => return Err(format!("View must be side or top, not `{}`", other)),
+                }
+            }
+            _ if parsed.file_name.is_none() && is_file_arg(arg) => {
+                parsed.file_name = Some(arg.clone())
+            }
+            _ => return Err(format!("Unexpected argument `{}`", arg)),
         }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(150, part1("test_input.txt"));
-    }
-
-    #[test]
-    fn final_part1() {
-        assert_eq!(1989265, part1("input.txt"));
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(900, part2("test_input.txt"));
-    }
-
-    #[test]
-    fn final_part2() {
-        assert_eq!(2089174012, part2("input.txt"));
-    }
+    Ok(parsed)
 }
@@ -0,0 +1,338 @@
+use helpers::{
+    parse::{InputFile, Span},
+    ParseError,
+};
+use std::iter;
+
+/// A single step for the submarine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Forward(i32),
+    Back(i32),
+    Down(i32),
+    Up(i32),
+    /// Sets the aim outright, rather than changing it.
+    Aim(i32),
+    /// Turns to port by a number of degrees, for the models that have a heading.
+    Left(i32),
+    /// Turns to starboard.
+    Right(i32),
+    /// Goes back to where the submarine started.
+    Reset,
+}
+
+/// One line of a script, or a block of them to run several times.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Statement {
+    Run(Command),
+    Repeat(u32, Vec<Statement>),
+}
+
+impl Statement {
+    /// Every command the statement runs, in order, with any repeats written out.
+    pub fn commands(&self) -> impl Iterator<Item = Command> + '_ {
+        flatten(std::slice::from_ref(self))
+    }
+
+    /// How many commands the statement runs with any repeats written out.
+    pub fn command_count(&self) -> u64 {
+        match self {
+            Statement::Run(_) => 1,
+            Statement::Repeat(count, body) => {
+                let once = body
+                    .iter()
+                    .fold(0u64, |total, s| total.saturating_add(s.command_count()));
+                once.saturating_mul(u64::from(*count))
+            }
+        }
+    }
+}
+
+/// A list of commands, one per line or separated by `;`, where
+///
+/// ```text
+/// # a comment, up to the end of the line
+/// repeat 3 {
+///     forward 5; down 2
+/// }
+/// ```
+///
+/// runs the commands in the braces three times. Blocks can be nested, and can be
+/// written on a single line too, as long as the script doesn't come to more than
+/// `MAX_COMMANDS` commands in all.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Script {
+    pub statements: Vec<Statement>,
+}
+
+impl Script {
+    pub fn parse(input: &InputFile) -> Result<Self, ParseError> {
+        Self::parse_checked(input, |_| Ok(()))
+    }
+
+    /// Like `parse`, but hands each top-level statement to `check` as soon as it's
+    /// finished, failing on the line that finished it with what `check` expected
+    /// instead.
+    pub fn parse_checked<F>(input: &InputFile, mut check: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(&Statement) -> Result<(), String>,
+    {
+        let mut parser = ScriptParser::default();
+        let mut statements = Vec::new();
+        for line in input.lines() {
+            let finished = parser.line(line)?;
+            for statement in &finished {
+                check(statement).map_err(|expected| line.error(&expected))?;
+            }
+            statements.extend(finished);
+        }
+        if let Some(expected) = parser.missing() {
+            return Err(input.error(&expected, "end of file"));
+        }
+        Ok(Self { statements })
+    }
+
+    /// Every command the script runs, in order, with the repeats written out.
+    pub fn commands(&self) -> impl Iterator<Item = Command> + '_ {
+        flatten(&self.statements)
+    }
+
+    /// How many commands the script runs with the repeats written out.
+    pub fn command_count(&self) -> u64 {
+        self.statements.iter().map(Statement::command_count).sum()
+    }
+}
+
+fn flatten(statements: &[Statement]) -> Box<dyn Iterator<Item = Command> + '_> {
+    Box::new(statements.iter().flat_map(|statement| match statement {
+        Statement::Run(command) => Box::new(iter::once(*command)),
+        Statement::Repeat(count, body) => {
+            Box::new((0..*count).flat_map(move |_| flatten(body))) as Box<dyn Iterator<Item = _>>
+        }
+    }))
+}
+
+/// The most commands a script can run with its repeats written out, so a few nested
+/// repeats can't keep the submarine going more or less forever.
+pub const MAX_COMMANDS: u64 = 100_000_000;
+
+/// A `repeat` that hasn't been closed yet.
+struct Block {
+    count: u32,
+    body: Vec<Statement>,
+    /// How many commands one run of the body comes to.
+    commands: u64,
+    line: usize,
+}
+
+/// Parses a script a line at a time, so it can be read as it streams in. Only the
+/// blocks that are still open are held on to.
+#[derive(Default)]
+pub struct ScriptParser {
+    open: Vec<Block>,
+    /// How many commands the finished statements come to.
+    commands: u64,
+}
+
+const COMMANDS: &str =
+    "a command (`forward`, `back`, `down`, `up`, `aim`, `left`, `right`, `reset` or `repeat`)";
+
+impl ScriptParser {
+    /// Parses the next line, returning the statements it finished at the top level.
+    pub fn line(&mut self, line: Span) -> Result<Vec<Statement>, ParseError> {
+        let tokens = tokens(line);
+        let mut tokens = tokens.iter().peekable();
+        let mut finished = Vec::new();
+
+        while let Some(&(token, span)) = tokens.next() {
+            let (statement, commands) = match token {
+                Token::Separator => continue,
+                Token::Close => {
+                    let block = self.open.pop().ok_or_else(|| span.error(COMMANDS))?;
+                    let commands = block.commands.saturating_mul(block.count as u64);
+                    (Statement::Repeat(block.count, block.body), commands)
+                }
+                Token::Open => return Err(span.error(COMMANDS)),
+                Token::Word => {
+                    let mut argument = |expected| match tokens.next() {
+                        Some(&(Token::Word, argument)) => Ok(argument),
+                        Some(&(_, other)) => Err(other.error(expected)),
+                        None => Err(span.missing(expected)),
+                    };
+                    let mut distance = || argument("a distance")?.parse("a distance");
+                    let command = match span.as_str().to_lowercase().as_ref() {
+                        "forward" => Command::Forward(distance()?),
+                        "back" => Command::Back(distance()?),
+                        "down" => Command::Down(distance()?),
+                        "up" => Command::Up(distance()?),
+                        "aim" => Command::Aim(argument("an aim")?.parse("an aim")?),
+                        "left" => Command::Left(argument("an angle")?.parse("an angle")?),
+                        "right" => Command::Right(argument("an angle")?.parse("an angle")?),
+                        "reset" => Command::Reset,
+                        "repeat" => {
+                            let expected = "a number of times to repeat";
+                            let count = argument(expected)?.parse(expected)?;
+                            match tokens.next() {
+                                Some(&(Token::Open, _)) => {}
+                                Some(&(_, other)) => return Err(other.error("`{`")),
+                                None => return Err(line.missing("`{`")),
+                            }
+                            self.open.push(Block {
+                                count,
+                                body: Vec::new(),
+                                commands: 0,
+                                line: span.line_number(),
+                            });
+                            continue;
+                        }
+                        _ => return Err(span.error(COMMANDS)),
+                    };
+                    (Statement::Run(command), 1)
+                }
+            };
+
+            // a statement has to be followed by something that ends it
+            if let Some(&&(Token::Word | Token::Open, extra)) = tokens.peek() {
+                return Err(extra.error("`;` or end of line"));
+            }
+            match self.open.last_mut() {
+                Some(block) => {
+                    block.body.push(statement);
+                    block.commands = block.commands.saturating_add(commands);
+                }
+                None => {
+                    self.commands = self.commands.saturating_add(commands);
+                    if self.commands > MAX_COMMANDS {
+                        let expected = format!(
+                            "no more than {} commands with the repeats written out",
+                            MAX_COMMANDS
+                        );
+                        return Err(span.error(&expected));
+                    }
+                    finished.push(statement);
+                }
+            }
+        }
+        Ok(finished)
+    }
+
+    /// What's still needed once the input has run out, if a `repeat` is left open.
+    pub fn missing(&self) -> Option<String> {
+        self.open
+            .last()
+            .map(|block| format!("`}}` to close the `repeat` on line {}", block.line))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Word,
+    Open,
+    Close,
+    Separator,
+}
+
+/// Splits a line into words and punctuation, leaving out any comment.
+fn tokens(line: Span) -> Vec<(Token, Span)> {
+    let mut tokens = Vec::new();
+    let mut word_start = None;
+    for (i, c) in line.as_str().char_indices() {
+        let token = match c {
+            '{' => Some(Token::Open),
+            '}' => Some(Token::Close),
+            ';' => Some(Token::Separator),
+            '#' => Some(Token::Separator),
+            c if c.is_whitespace() => None,
+            _ => {
+                word_start.get_or_insert(i);
+                continue;
+            }
+        };
+        if let Some(start) = word_start.take() {
+            tokens.push((Token::Word, line.slice(start, i - start)));
+        }
+        if c == '#' {
+            return tokens;
+        }
+        if let Some(token) = token {
+            tokens.push((token, line.slice(i, c.len_utf8())));
+        }
+    }
+    if let Some(start) = word_start {
+        tokens.push((Token::Word, line.slice(start, line.len() - start)));
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Result<Script, ParseError> {
+        Script::parse(&InputFile::from_text("script.txt", text))
+    }
+
+    #[test]
+    fn test_commands() {
+        let script = parse(
+            "\
+# out and back
+forward 5
+repeat 2 {
+    down 1; repeat 2 { Forward 3 }  # nested
+}
+back 2;aim -1
+left 90; right 45
+reset",
+        )
+        .unwrap();
+        let expected = [
+            Command::Forward(5),
+            Command::Down(1),
+            Command::Forward(3),
+            Command::Forward(3),
+            Command::Down(1),
+            Command::Forward(3),
+            Command::Forward(3),
+            Command::Back(2),
+            Command::Aim(-1),
+            Command::Left(90),
+            Command::Right(45),
+            Command::Reset,
+        ];
+        assert_eq!(expected.to_vec(), script.commands().collect::<Vec<_>>());
+        assert_eq!(7, script.statements.len());
+        assert_eq!(expected.len() as u64, script.command_count());
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |text| {
+            let error = parse(text).unwrap_err();
+            (error.line, error.column, error.found)
+        };
+        assert_eq!(
+            (2, 1, String::from("`sideways`")),
+            error("up 1\nsideways 3")
+        );
+        assert_eq!((1, 9, String::from("`x`")), error("forward x"));
+        assert_eq!((1, 8, String::from("end of line")), error("forward"));
+        assert_eq!((1, 11, String::from("`down`")), error("forward 5 down 1"));
+        assert_eq!(
+            (1, 10, String::from("`forward`")),
+            error("repeat 2 forward 1 }")
+        );
+        assert_eq!((2, 1, String::from("`}`")), error("up 1\n}"));
+
+        let endless = "repeat 100000 {\n  repeat 100000 { forward 1 }\n}\n";
+        let too_many = parse(endless).unwrap_err();
+        assert_eq!((3, 1), (too_many.line, too_many.column));
+        assert!(too_many.expected.contains("no more than"), "{}", too_many);
+        assert!(parse("repeat 0 {\n  repeat 100000000 { repeat 2 { up 1 } }\n}").is_ok());
+        assert!(parse(&"forward 1\n".repeat(10)).is_ok());
+
+        let unclosed = parse("repeat 2 {\n  up 1\n").unwrap_err();
+        assert_eq!(0, unclosed.line);
+        assert!(unclosed.expected.contains("on line 1"), "{}", unclosed);
+    }
+}
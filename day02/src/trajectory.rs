@@ -0,0 +1,177 @@
+use std::fmt::Write;
+
+/// Where a submarine is. The models without a heading never leave the plane where
+/// `sideways` is 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Point {
+    pub horizontal: f64,
+    /// To starboard of the starting line.
+    pub sideways: f64,
+    pub depth: f64,
+}
+
+/// Every position a model passed through: where it started, then where it was
+/// after each command.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trajectory {
+    pub model: &'static str,
+    pub points: Vec<Point>,
+}
+
+/// Which way to look at a trajectory drawn as an SVG.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum View {
+    /// Horizontal position across, depth going down.
+    Side,
+    /// From above, horizontal position across, sideways going down.
+    Top,
+}
+
+impl View {
+    fn coordinates(self, point: &Point) -> (f64, f64) {
+        match self {
+            View::Side => (point.horizontal, point.depth),
+            View::Top => (point.horizontal, point.sideways),
+        }
+    }
+}
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 400.0;
+const MARGIN: f64 = 20.0;
+const COLOURS: [&str; 4] = ["#1f77b4", "#d62728", "#2ca02c", "#9467bd"];
+
+/// The trajectories as CSV, one row per point.
+pub fn csv(trajectories: &[Trajectory]) -> String {
+    let mut csv = String::from("model,step,horizontal,sideways,depth\n");
+    for trajectory in trajectories {
+        for (step, point) in trajectory.points.iter().enumerate() {
+            writeln!(
+                csv,
+                "{},{},{},{},{}",
+                trajectory.model,
+                step,
+                number(point.horizontal),
+                number(point.sideways),
+                number(point.depth)
+            )
+            .unwrap();
+        }
+    }
+    csv
+}
+
+/// The trajectories drawn as polylines over each other. Each axis is scaled
+/// separately to fill the picture, since depths tend to be far smaller than the
+/// distance travelled.
+pub fn svg(trajectories: &[Trajectory], view: View) -> String {
+    let coordinates = trajectories
+        .iter()
+        .flat_map(|t| t.points.iter())
+        .map(|point| view.coordinates(point));
+    let (mut min_x, mut max_x, mut min_y, mut max_y) = (0.0f64, 0.0f64, 0.0f64, 0.0f64);
+    for (x, y) in coordinates {
+        min_x = min_x.min(x);
+        max_x = max_x.max(x);
+        min_y = min_y.min(y);
+        max_y = max_y.max(y);
+    }
+    // a trajectory along one axis still needs something to divide by
+    let scale_x = (WIDTH - 2.0 * MARGIN) / (max_x - min_x).max(1.0);
+    let scale_y = (HEIGHT - 2.0 * MARGIN) / (max_y - min_y).max(1.0);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\">\n",
+        WIDTH, HEIGHT
+    );
+    for (i, trajectory) in trajectories.iter().enumerate() {
+        let points = trajectory
+            .points
+            .iter()
+            .map(|point| {
+                let (x, y) = view.coordinates(point);
+                format!(
+                    "{},{}",
+                    number(MARGIN + (x - min_x) * scale_x),
+                    number(MARGIN + (y - min_y) * scale_y)
+                )
+            })
+            .collect::<Vec<_>>();
+        let colour = COLOURS[i % COLOURS.len()];
+        writeln!(
+            svg,
+            "  <polyline fill=\"none\" stroke=\"{}\" points=\"{}\"><title>{}</title></polyline>",
+            colour,
+            points.join(" "),
+            trajectory.model
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            "  <text x=\"{}\" y=\"{}\" fill=\"{}\" font-size=\"12\">{}</text>",
+            MARGIN,
+            MARGIN + 14.0 * i as f64,
+            colour,
+            trajectory.model
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Whole numbers as they are, anything else to three decimal places.
+fn number(value: f64) -> String {
+    // adding zero turns -0 into 0
+    format!("{}", (value * 1000.0).round() / 1000.0 + 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trajectory(model: &'static str, points: &[(f64, f64, f64)]) -> Trajectory {
+        let points = points
+            .iter()
+            .map(|&(horizontal, sideways, depth)| Point {
+                horizontal,
+                sideways,
+                depth,
+            })
+            .collect();
+        Trajectory { model, points }
+    }
+
+    #[test]
+    fn test_csv() {
+        let trajectories = [
+            trajectory("position", &[(0.0, 0.0, 0.0), (5.0, 0.0, 0.0)]),
+            trajectory("heading", &[(0.0, 0.0, 0.0), (0.5, -0.8660254, 2.0)]),
+        ];
+        let expected = "\
+model,step,horizontal,sideways,depth
+position,0,0,0,0
+position,1,5,0,0
+heading,0,0,0,0
+heading,1,0.5,-0.866,2
+";
+        assert_eq!(expected, csv(&trajectories));
+    }
+
+    #[test]
+    fn test_svg() {
+        let trajectories = [
+            trajectory("position", &[(0.0, 0.0, 0.0), (10.0, 0.0, 5.0)]),
+            trajectory("aim", &[(0.0, 0.0, 0.0), (10.0, 0.0, -5.0)]),
+        ];
+        let svg = svg(&trajectories, View::Side);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("points=\"20,200 780,380\""), "{}", svg);
+        assert!(svg.contains("points=\"20,200 780,20\""), "{}", svg);
+        assert_eq!(2, svg.matches("<polyline").count());
+
+        // seen from above, both stay on the line through the start
+        let top = super::svg(&trajectories, View::Top);
+        assert_eq!(2, top.matches("points=\"20,20 780,20\"").count());
+    }
+}
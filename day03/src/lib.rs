@@ -0,0 +1,307 @@
+use helpers::{parse::InputFile, stream::LineReader, ParseError, Solution};
+use std::{fmt, io::BufRead};
+
+mod product;
+mod rating;
+mod reading;
+
+pub use product::Product;
+pub use rating::{BitOrder, Criterion, Keep, Rating, Step};
+pub use reading::{ColumnStats, Reading};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = (Vec<Reading>, usize);
+    type Part1 = Power;
+    type Part2 = Product;
+
+    fn parse(file_name: &str) -> Result<(Vec<Reading>, usize), ParseError> {
+        get_readings_and_num_bits(file_name)
+    }
+
+    fn part1((readings, num_bits): &(Vec<Reading>, usize)) -> Power {
+        let mut gamma = Reading::zeros(*num_bits);
+        let mut epsilon = Reading::zeros(*num_bits);
+        let mut tied = Vec::new();
+
+        for (i, column) in ColumnStats::columns(readings, *num_bits).iter().enumerate() {
+            match DigitInfo::from(column) {
+                DigitInfo::MoreOnes => gamma.set(i, true),
+                DigitInfo::MoreZeros => epsilon.set(i, true),
+                DigitInfo::Same => tied.push(num_bits - i),
+            }
+        }
+
+        if tied.is_empty() {
+            Power::Consumption(Product::of(&gamma, &epsilon))
+        } else {
+            tied.reverse();
+            Power::Tied(tied)
+        }
+    }
+
+    fn part2((readings, num_bits): &(Vec<Reading>, usize)) -> Product {
+        let rate = |criterion: Criterion| {
+            let rating = criterion.rate(readings, *num_bits);
+            rating.expect("there's always at least one reading").reading
+        };
+
+        Product::of(&rate(Criterion::OXYGEN), &rate(Criterion::CO2))
+    }
+
+    fn stream(file_name: &str) -> Option<Result<(Power, Product), ParseError>> {
+        Some(LineReader::open(file_name).and_then(solve_stream))
+    }
+}
+
+/// Part 1's answer, which needs a most common digit in every column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Power {
+    /// The gamma rate times the epsilon rate.
+    Consumption(Product),
+    /// The columns with as many 0s as 1s, counting digits from 1 on the left.
+    Tied(Vec<usize>),
+}
+
+impl fmt::Display for Power {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Power::Consumption(power) => write!(f, "{}", power),
+            Power::Tied(columns) => {
+                let columns = columns.iter().map(usize::to_string).collect::<Vec<_>>();
+                write!(f, "none, as many 0s as 1s in digits {}", columns.join(", "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DigitInfo {
+    MoreOnes,
+    MoreZeros,
+    Same,
+}
+
+impl DigitInfo {
+    pub fn from_bit_index(readings: &[Reading], bit: usize) -> Self {
+        Self::from(&ColumnStats::of(readings, bit))
+    }
+}
+
+impl From<&ColumnStats> for DigitInfo {
+    fn from(column: &ColumnStats) -> Self {
+        match column.most_common() {
+            Some(true) => DigitInfo::MoreOnes,
+            Some(false) => DigitInfo::MoreZeros,
+            None => DigitInfo::Same,
+        }
+    }
+}
+
+fn get_readings_and_num_bits(file_name: &str) -> Result<(Vec<Reading>, usize), ParseError> {
+    let input = InputFile::open(file_name)?;
+    let num_bits = input.first_line()?.len();
+
+    let readings = input
+        .lines()
+        .map(|l| Reading::parse(l, num_bits))
+        .collect::<Result<_, _>>()?;
+
+    Ok((readings, num_bits))
+}
+
+/// Both parts, reading a line at a time. The ratings in part 2 whittle down the
+/// whole list, so the readings are kept, but as bitsets rather than lines.
+pub fn solve_stream<R: BufRead>(mut lines: LineReader<R>) -> Result<(Power, Product), ParseError> {
+    let mut num_bits = None;
+    let mut readings = Vec::new();
+    while let Some(line) = lines.next_line() {
+        let line = line?;
+        let num_bits = *num_bits.get_or_insert(line.len());
+        readings.push(Reading::parse(line, num_bits)?);
+    }
+
+    let num_bits = num_bits.ok_or_else(|| lines.error("at least one line", "an empty file"))?;
+    let input = (readings, num_bits);
+    Ok((Day03::part1(&input), Day03::part2(&input)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng::check;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Power::Consumption(Product::from(198)),
+            Day03::solve_part1("test_input.txt")
+        );
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(
+            Power::Consumption(Product::from(4006064)),
+            Day03::solve_part1("input.txt")
+        );
+    }
+
+    #[test]
+    fn test_tie() {
+        let input = InputFile::from_text("tied.txt", "100\n010\n011\n001");
+        let readings = input
+            .lines()
+            .map(|line| Reading::parse(line, 3))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let power = Day03::part1(&(readings, 3));
+        assert_eq!(Power::Tied(vec![2, 3]), power);
+        assert_eq!("none, as many 0s as 1s in digits 2, 3", power.to_string());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Product::from(230), Day03::solve_part2("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(Product::from(5941884), Day03::solve_part2("input.txt"));
+    }
+
+    #[test]
+    fn test_stream() {
+        let lines = LineReader::open("input.txt").unwrap();
+        assert_eq!(
+            Ok((
+                Power::Consumption(Product::from(4006064)),
+                Product::from(5941884)
+            )),
+            solve_stream(lines)
+        );
+
+        let lines = LineReader::new("empty.txt", "".as_bytes());
+        assert_eq!(0, solve_stream(lines).unwrap_err().line);
+        let lines = LineReader::new("ragged.txt", "0101\n101".as_bytes());
+        assert_eq!(2, solve_stream(lines).unwrap_err().line);
+    }
+
+    #[test]
+    fn test_wide_report() {
+        // the test input with 40 zeros in front, too wide for a u32
+        let text = std::fs::read_to_string("test_input.txt").unwrap();
+        let wide = text
+            .lines()
+            .map(|line| format!("{}{}\n", "0".repeat(40), line))
+            .collect::<String>();
+        let input = InputFile::from_text("wide.txt", &wide);
+        let readings = input
+            .lines()
+            .map(|line| Reading::parse(line, 45))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let input = (readings, 45);
+        let epsilon = (((1 << 40) - 1) << 5) | 9;
+        assert_eq!(
+            Power::Consumption(Product::from(22 * epsilon)),
+            Day03::part1(&input)
+        );
+        // the ratings skip the columns where every reading agrees
+        assert_eq!(Product::from(230), Day03::part2(&input));
+
+        // a 40-bit pattern three times and its inverse twice, so gamma is the pattern,
+        // epsilon its inverse, and the ratings end up on the same two
+        let pattern = "1100".repeat(10);
+        let inverse = "0011".repeat(10);
+        let text = [&pattern, &inverse, &pattern, &inverse, &pattern].map(|r| r.as_str());
+        let input = InputFile::from_text("wider.txt", &text.join("\n"));
+        let readings = input
+            .lines()
+            .map(|line| Reading::parse(line, 40))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let input = (readings, 40);
+        let gamma = u128::from_str_radix(&pattern, 2).unwrap();
+        let epsilon = u128::from_str_radix(&inverse, 2).unwrap();
+        let expected = (gamma * epsilon).to_string();
+        assert_eq!(expected, Day03::part1(&input).to_string());
+        assert_eq!(expected, Day03::part2(&input).to_string());
+    }
+
+    /// The rating the puzzle describes, whittling down the lines as text, except that
+    /// a column where every line agrees is skipped rather than emptying the list.
+    fn naive_rating(lines: &[String], oxygen: bool) -> u64 {
+        let mut left = lines.to_vec();
+        for column in 0..lines[0].len() {
+            let ones = left.iter().filter(|l| l.as_bytes()[column] == b'1').count();
+            if left.len() == 1 || ones == 0 || ones == left.len() {
+                continue;
+            }
+            let keep = if oxygen {
+                ones * 2 >= left.len()
+            } else {
+                ones * 2 < left.len()
+            };
+            left.retain(|l| (l.as_bytes()[column] == b'1') == keep);
+        }
+        u64::from_str_radix(&left[0], 2).unwrap()
+    }
+
+    #[test]
+    fn test_random_reports() {
+        check("day03 random reports", 200, |rng| {
+            let width = 1 + rng.index(20);
+            let lines = (0..1 + rng.index(30))
+                .map(|_| (0..width).map(|_| *rng.choose(&['0', '1'])).collect())
+                .collect::<Vec<String>>();
+            let text = lines.join("\n");
+
+            let mut gamma = 0;
+            let mut tied = Vec::new();
+            for column in 0..width {
+                let ones = lines
+                    .iter()
+                    .filter(|l| l.as_bytes()[column] == b'1')
+                    .count();
+                gamma = gamma << 1 | (ones * 2 > lines.len()) as u64;
+                if ones * 2 == lines.len() {
+                    tied.push(column + 1);
+                }
+            }
+            let epsilon = !gamma & ((1 << width) - 1);
+            let power = if tied.is_empty() {
+                Power::Consumption(Product::from(gamma * epsilon))
+            } else {
+                Power::Tied(tied)
+            };
+            let life_support = naive_rating(&lines, true) * naive_rating(&lines, false);
+
+            let input = parse_text(&text);
+            assert_eq!(power, Day03::part1(&input), "\n{}", text);
+            assert_eq!(
+                Product::from(life_support),
+                Day03::part2(&input),
+                "\n{}",
+                text
+            );
+            let streamed = solve_stream(LineReader::new("random", text.as_bytes()));
+            assert_eq!(
+                Ok((power, Product::from(life_support))),
+                streamed,
+                "\n{}",
+                text
+            );
+        });
+    }
+
+    fn parse_text(text: &str) -> (Vec<Reading>, usize) {
+        let input = InputFile::from_text("random", text);
+        let width = input.first_line().unwrap().len();
+        let readings = input.lines().map(|line| Reading::parse(line, width));
+        (readings.collect::<Result<_, _>>().unwrap(), width)
+    }
+}
@@ -0,0 +1,119 @@
+use crate::reading::Reading;
+use std::fmt;
+
+/// Two readings multiplied together, which is what both parts answer with. Readings
+/// can be any width, so their product can outgrow every integer type, and is kept
+/// as 64-bit words like a reading is, lowest first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Product {
+    /// Without any zero words on the end, so equal products compare equal.
+    words: Vec<u64>,
+}
+
+impl Product {
+    pub fn of(a: &Reading, b: &Reading) -> Self {
+        let (a, b) = (a.words(), b.words());
+        let mut words = vec![0; a.len() + b.len()];
+        for (i, &x) in a.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &y) in b.iter().enumerate() {
+                let sum = x as u128 * y as u128 + words[i + j] as u128 + carry;
+                words[i + j] = sum as u64;
+                carry = sum >> 64;
+            }
+            words[i + b.len()] = carry as u64;
+        }
+        Self::trimmed(words)
+    }
+
+    fn trimmed(mut words: Vec<u64>) -> Self {
+        while words.last() == Some(&0) {
+            words.pop();
+        }
+        Self { words }
+    }
+
+    /// The product as a number, if it fits in one.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.words[..] {
+            [] => Some(0),
+            [word] => Some(word),
+            _ => None,
+        }
+    }
+}
+
+impl From<u64> for Product {
+    fn from(value: u64) -> Self {
+        Self::trimmed(vec![value])
+    }
+}
+
+/// The largest power of 10 that fits in a word, so the digits can be split off 19 at
+/// a time.
+const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+impl fmt::Display for Product {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut words = self.words.clone();
+        let mut chunks = Vec::new();
+        while !words.is_empty() {
+            let mut remainder = 0u128;
+            for word in words.iter_mut().rev() {
+                let value = remainder << 64 | *word as u128;
+                *word = (value / CHUNK as u128) as u64;
+                remainder = value % CHUNK as u128;
+            }
+            chunks.push(remainder as u64);
+            while words.last() == Some(&0) {
+                words.pop();
+            }
+        }
+
+        match chunks.split_last() {
+            None => write!(f, "0"),
+            Some((first, rest)) => {
+                write!(f, "{}", first)?;
+                rest.iter()
+                    .rev()
+                    .try_for_each(|chunk| write!(f, "{:019}", chunk))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ones(width: usize) -> Reading {
+        Reading::zeros(width).inverted()
+    }
+
+    #[test]
+    fn test_product() {
+        let product = Product::of(&Reading::from_u64(22, 5), &Reading::from_u64(9, 5));
+        assert_eq!(Product::from(198), product);
+        assert_eq!(Some(198), product.to_u64());
+        assert_eq!("198", product.to_string());
+        assert_eq!("0", Product::of(&ones(100), &Reading::zeros(3)).to_string());
+
+        // (2^64 - 1)^2 and (2^128 - 1)^2
+        let wide = Product::of(&ones(64), &ones(64));
+        assert_eq!(None, wide.to_u64());
+        assert_eq!(
+            ((u64::MAX as u128) * (u64::MAX as u128)).to_string(),
+            wide.to_string()
+        );
+        assert_eq!(
+            "115792089237316195423570985008687907852589419931798687112530834793049593217025",
+            Product::of(&ones(128), &ones(128)).to_string()
+        );
+        // chunks of digits after the first that start with zeros
+        let chunk = Reading::from_u64(CHUNK, 64);
+        assert_eq!(
+            format!("1{}", "0".repeat(38)),
+            Product::of(&chunk, &chunk).to_string()
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use crate::reading::{ColumnStats, Reading};
+
+/// Which digit a criterion keeps in each column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keep {
+    MostCommon,
+    LeastCommon,
+}
+
+/// Which end of the readings a criterion starts from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitOrder {
+    MsbFirst,
+    LsbFirst,
+}
+
+/// How to whittle a list of readings down to a single rating, one column at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Criterion {
+    pub keep: Keep,
+    /// The digit to keep when a column has as many of each.
+    pub on_tie: bool,
+    pub order: BitOrder,
+}
+
+/// One column's worth of whittling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub bit: usize,
+    /// The counts among the readings still left before this step.
+    pub column: ColumnStats,
+    pub kept_digit: bool,
+    /// The indices of the readings this step got rid of.
+    pub eliminated: Vec<usize>,
+}
+
+/// The reading that survived, and how it got there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rating {
+    pub reading: Reading,
+    /// The index of the reading in the list it was picked from.
+    pub index: usize,
+    pub trace: Vec<Step>,
+}
+
+impl Criterion {
+    pub const OXYGEN: Self = Self {
+        keep: Keep::MostCommon,
+        on_tie: true,
+        order: BitOrder::MsbFirst,
+    };
+
+    pub const CO2: Self = Self {
+        keep: Keep::LeastCommon,
+        on_tie: false,
+        order: BitOrder::MsbFirst,
+    };
+
+    /// The digit this criterion keeps in a column.
+    pub fn digit(&self, column: &ColumnStats) -> bool {
+        let digit = match self.keep {
+            Keep::MostCommon => column.most_common(),
+            Keep::LeastCommon => column.least_common(),
+        };
+        digit.unwrap_or(self.on_tie)
+    }
+
+    /// Goes through the columns in order, keeping only the readings with the chosen
+    /// digit, until there's just one left. Columns where the readings left all agree
+    /// are skipped, so a criterion wanting the least common digit doesn't get rid of
+    /// everything, and only the columns that got rid of something are in the trace.
+    /// Readings that are all the same can't be split, so the first of them wins.
+    /// `None` if there aren't any readings.
+    pub fn rate(&self, readings: &[Reading], width: usize) -> Option<Rating> {
+        let mut left = (0..readings.len()).collect::<Vec<_>>();
+        let mut trace = Vec::new();
+        let bits: Box<dyn Iterator<Item = usize>> = match self.order {
+            BitOrder::MsbFirst => Box::new((0..width).rev()),
+            BitOrder::LsbFirst => Box::new(0..width),
+        };
+
+        for bit in bits {
+            if left.len() <= 1 {
+                break;
+            }
+            let ones = left.iter().filter(|&&i| readings[i].bit(bit)).count();
+            let column = ColumnStats {
+                ones,
+                zeros: left.len() - ones,
+            };
+            let kept_digit = self.digit(&column);
+            let kept_count = if kept_digit {
+                column.ones
+            } else {
+                column.zeros
+            };
+            if kept_count == 0 || kept_count == left.len() {
+                continue;
+            }
+
+            let (kept, eliminated) = left
+                .iter()
+                .partition(|&&i| readings[i].bit(bit) == kept_digit);
+            left = kept;
+            trace.push(Step {
+                bit,
+                column,
+                kept_digit,
+                eliminated,
+            });
+        }
+
+        let &index = left.first()?;
+        Some(Rating {
+            reading: readings[index].clone(),
+            index,
+            trace,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn readings(values: &[u64], width: usize) -> Vec<Reading> {
+        values
+            .iter()
+            .map(|&v| Reading::from_u64(v, width))
+            .collect()
+    }
+
+    #[test]
+    fn test_trace() {
+        let readings = readings(&[0b100, 0b110, 0b111, 0b001, 0b011], 3);
+        let rating = Criterion::OXYGEN.rate(&readings, 3).unwrap();
+        assert_eq!((2, Some(0b111)), (rating.index, rating.reading.to_u64()));
+        let steps = rating
+            .trace
+            .iter()
+            .map(|s| (s.bit, s.column.ones, s.kept_digit, s.eliminated.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (2, 3, true, vec![3, 4]),
+                (1, 2, true, vec![0]),
+                (0, 1, true, vec![1]),
+            ],
+            steps
+        );
+
+        let co2 = Criterion::CO2.rate(&readings, 3).unwrap();
+        assert_eq!(Some(0b001), co2.reading.to_u64());
+        assert_eq!(2, co2.trace.len());
+    }
+
+    #[test]
+    fn test_criteria() {
+        // two readings tie in every column they differ in
+        let readings = readings(&[0b01, 0b10], 2);
+        let rate = |keep, on_tie, order| {
+            let criterion = Criterion {
+                keep,
+                on_tie,
+                order,
+            };
+            criterion.rate(&readings, 2).unwrap().index
+        };
+        assert_eq!(1, rate(Keep::MostCommon, true, BitOrder::MsbFirst));
+        assert_eq!(0, rate(Keep::MostCommon, false, BitOrder::MsbFirst));
+        assert_eq!(0, rate(Keep::MostCommon, true, BitOrder::LsbFirst));
+        assert_eq!(1, rate(Keep::LeastCommon, false, BitOrder::LsbFirst));
+    }
+
+    #[test]
+    fn test_unsplittable() {
+        // every reading starts with a 0, so the least common 1 can't be kept
+        let readings = readings(&[0b001, 0b011, 0b011], 3);
+        let rating = Criterion::CO2.rate(&readings, 3).unwrap();
+        assert_eq!(0, rating.index);
+        assert_eq!(
+            vec![1],
+            rating.trace.iter().map(|s| s.bit).collect::<Vec<_>>()
+        );
+
+        let same = Criterion::OXYGEN.rate(&readings[1..], 3).unwrap();
+        assert_eq!((0, 0), (same.index, same.trace.len()));
+        assert_eq!(None, Criterion::OXYGEN.rate(&[], 3));
+    }
+}
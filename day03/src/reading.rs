@@ -0,0 +1,200 @@
+use helpers::{parse::Span, ParseError};
+use std::fmt;
+
+/// A diagnostic reading of any width, as a bitset. Bit 0 is the last digit of the
+/// line, like the bits of a number.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Reading {
+    width: usize,
+    words: Vec<u64>,
+}
+
+impl Reading {
+    /// A reading of `width` zeros.
+    pub fn zeros(width: usize) -> Self {
+        Self {
+            width,
+            words: vec![0; width.div_ceil(64)],
+        }
+    }
+
+    /// The lowest `width` bits of `value`.
+    pub fn from_u64(value: u64, width: usize) -> Self {
+        let mut reading = Self::zeros(width);
+        for bit in 0..width.min(64) {
+            reading.set(bit, value >> bit & 1 == 1);
+        }
+        reading
+    }
+
+    pub fn parse(line: Span, width: usize) -> Result<Self, ParseError> {
+        if line.len() != width {
+            return Err(line.error(&format!("a {}-bit binary number", width)));
+        }
+        let mut reading = Self::zeros(width);
+        for (i, (c, span)) in line.chars().enumerate() {
+            match c {
+                '0' => {}
+                '1' => reading.set(width - 1 - i, true),
+                _ => return Err(span.error("a binary digit")),
+            }
+        }
+        Ok(reading)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn bit(&self, bit: usize) -> bool {
+        assert!(
+            bit < self.width,
+            "bit {} of a {}-bit reading",
+            bit,
+            self.width
+        );
+        self.words[bit / 64] >> (bit % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, bit: usize, value: bool) {
+        assert!(
+            bit < self.width,
+            "bit {} of a {}-bit reading",
+            bit,
+            self.width
+        );
+        let mask = 1 << (bit % 64);
+        if value {
+            self.words[bit / 64] |= mask;
+        } else {
+            self.words[bit / 64] &= !mask;
+        }
+    }
+
+    /// The reading with every bit flipped.
+    pub fn inverted(&self) -> Self {
+        let mut inverted = self.clone();
+        for bit in 0..self.width {
+            inverted.set(bit, !self.bit(bit));
+        }
+        inverted
+    }
+
+    /// The bits, 64 at a time, lowest first. Any past the width are 0.
+    pub(crate) fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// The reading as a number, if it fits in one.
+    pub fn to_u64(&self) -> Option<u64> {
+        let (low, high) = self.words.split_first()?;
+        high.iter().all(|&w| w == 0).then_some(*low)
+    }
+}
+
+impl fmt::Display for Reading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for bit in (0..self.width).rev() {
+            f.write_str(if self.bit(bit) { "1" } else { "0" })?;
+        }
+        Ok(())
+    }
+}
+
+/// How many readings have each digit in one column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ColumnStats {
+    pub ones: usize,
+    pub zeros: usize,
+}
+
+impl ColumnStats {
+    pub fn of(readings: &[Reading], bit: usize) -> Self {
+        let ones = readings.iter().filter(|r| r.bit(bit)).count();
+        Self {
+            ones,
+            zeros: readings.len() - ones,
+        }
+    }
+
+    /// Every column of the readings, indexed by bit, so the first is the last digit.
+    pub fn columns(readings: &[Reading], width: usize) -> Vec<Self> {
+        let mut columns = vec![Self::default(); width];
+        for reading in readings {
+            for (bit, column) in columns.iter_mut().enumerate() {
+                if reading.bit(bit) {
+                    column.ones += 1;
+                } else {
+                    column.zeros += 1;
+                }
+            }
+        }
+        columns
+    }
+
+    pub fn is_tie(&self) -> bool {
+        self.ones == self.zeros
+    }
+
+    /// The digit most of the readings have, or `None` if it's a tie.
+    pub fn most_common(&self) -> Option<bool> {
+        (!self.is_tie()).then_some(self.ones > self.zeros)
+    }
+
+    /// The digit fewest of the readings have, or `None` if it's a tie.
+    pub fn least_common(&self) -> Option<bool> {
+        self.most_common().map(|digit| !digit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::parse::InputFile;
+
+    #[test]
+    fn test_wide_readings() {
+        let line = format!("1{}01", "0".repeat(97));
+        let input = InputFile::from_text("wide.txt", &line);
+        let reading = Reading::parse(input.first_line().unwrap(), 100).unwrap();
+        assert_eq!(100, reading.width());
+        assert!(reading.bit(0) && !reading.bit(1) && reading.bit(99));
+        assert_eq!(None, reading.to_u64());
+        assert_eq!(line, reading.to_string());
+        let inverted = reading.inverted();
+        assert!(!inverted.bit(0) && inverted.bit(1) && !inverted.bit(99));
+
+        let error = Reading::parse(input.first_line().unwrap(), 99).unwrap_err();
+        assert_eq!(1, error.column);
+        let input = InputFile::from_text("digits.txt", "0120");
+        let error = Reading::parse(input.first_line().unwrap(), 4).unwrap_err();
+        assert_eq!((3, "`2`"), (error.column, error.found.as_str()));
+    }
+
+    #[test]
+    fn test_columns() {
+        let readings = [0b110, 0b011, 0b111, 0b000]
+            .iter()
+            .map(|&r| Reading::from_u64(r, 3))
+            .collect::<Vec<_>>();
+        let columns = ColumnStats::columns(&readings, 3);
+        assert_eq!(
+            vec![
+                ColumnStats { ones: 2, zeros: 2 },
+                ColumnStats { ones: 3, zeros: 1 },
+                ColumnStats { ones: 2, zeros: 2 },
+            ],
+            columns
+        );
+        assert_eq!(columns[1], ColumnStats::of(&readings, 1));
+        assert!(columns[0].is_tie());
+        assert_eq!(
+            (None, None),
+            (columns[0].most_common(), columns[0].least_common())
+        );
+        assert_eq!(
+            (Some(true), Some(false)),
+            (columns[1].most_common(), columns[1].least_common())
+        );
+    }
+}
@@ -1,69 +1,174 @@
-#[derive(Debug)]
+use crate::rules::{Pattern, Rules, WinRule};
+
+/// An NxN bingo board. How many cells are marked in each row, column and the other
+/// patterns is kept up to date, so marking a cell and checking whether that won are
+/// O(1), apart from custom masks.
+#[derive(Debug, Clone)]
 pub struct Board {
     pub index: usize,
-    rows: [[Number; 5]; 5],
+    size: usize,
+    /// The rows one after another.
+    cells: Vec<Number>,
+    row_hits: Vec<usize>,
+    column_hits: Vec<usize>,
+    /// The diagonal, then the anti-diagonal.
+    diagonal_hits: [usize; 2],
+    corner_hits: usize,
+    marked: usize,
+    unmarked_total: i32,
 }
 
 impl Board {
-    pub fn new<I: Iterator<Item = i32>>(index: usize, mut numbers: I) -> Self {
-        let mut rows: [[Number; 5]; 5] = [[Number::default(); 5]; 5];
-        for row in 0..5 {
-            for col in 0..5 {
-                rows[row][col] = numbers.next().unwrap().into();
-            }
+    pub fn new<I: Iterator<Item = i32>>(index: usize, size: usize, mut numbers: I) -> Self {
+        let cells = (0..size * size)
+            .map(|_| numbers.next().unwrap().into())
+            .collect::<Vec<Number>>();
+        Self {
+            index,
+            size,
+            unmarked_total: cells.iter().map(Number::value).sum(),
+            cells,
+            row_hits: vec![0; size],
+            column_hits: vec![0; size],
+            diagonal_hits: [0; 2],
+            corner_hits: 0,
+            marked: 0,
         }
-        Self { index, rows }
     }
 
-    pub fn mark(&mut self, number: i32) {
-        for row in 0..5 {
-            for col in 0..5 {
-                if self.rows[row][col] == Number::Unmarked(number) {
-                    self.rows[row][col] = Number::Marked(number);
-                    return;
-                }
-            }
-        }
+    pub fn size(&self) -> usize {
+        self.size
     }
 
-    fn columns(&self) -> [[Number; 5]; 5] {
-        let mut columns = [[Number::default(); 5]; 5];
-        for row in 0..5 {
-            for col in 0..5 {
-                columns[col][row] = self.rows[row][col];
-            }
-        }
-
-        columns
+    pub fn number(&self, row: usize, col: usize) -> i32 {
+        self.cells[row * self.size + col].value()
     }
 
-    pub fn bingo(&self) -> bool {
-        self.has_full_row() || self.has_full_column()
+    pub fn is_marked(&self, row: usize, col: usize) -> bool {
+        self.cells[row * self.size + col].is_marked()
     }
 
-    fn has_full_row(&self) -> bool {
-        self.rows
+    /// Each number with where it is on the board, row by row.
+    pub fn numbers(&self) -> impl Iterator<Item = (i32, usize, usize)> + '_ {
+        let size = self.size;
+        self.cells
             .iter()
-            .any(|row| row.iter().all(Number::is_marked))
+            .enumerate()
+            .map(move |(i, cell)| (cell.value(), i / size, i % size))
     }
 
-    fn has_full_column(&self) -> bool {
-        self.columns()
+    /// Marks the first unmarked cell with this number, if there is one.
+    pub fn mark(&mut self, number: i32) {
+        let cell = self
+            .cells
             .iter()
-            .any(|col| col.iter().all(Number::is_marked))
+            .position(|&c| c == Number::Unmarked(number));
+        if let Some(i) = cell {
+            self.mark_at(i / self.size, i % self.size);
+        }
     }
 
-    pub fn unmarked_total(&self) -> i32 {
-        let mut score = 0;
-        for row in self.rows {
-            for number in row {
-                if let Number::Unmarked(num) = number {
-                    score += num;
+    /// Marks one cell, returning whether it wasn't already.
+    pub fn mark_at(&mut self, row: usize, col: usize) -> bool {
+        let n = self.size;
+        let cell = &mut self.cells[row * n + col];
+        let number = match *cell {
+            Number::Unmarked(number) => number,
+            Number::Marked(_) => return false,
+        };
+        *cell = Number::Marked(number);
+
+        self.row_hits[row] += 1;
+        self.column_hits[col] += 1;
+        if row == col {
+            self.diagonal_hits[0] += 1;
+        }
+        if row + col == n - 1 {
+            self.diagonal_hits[1] += 1;
+        }
+        if self.is_corner(row, col) {
+            self.corner_hits += 1;
+        }
+        self.marked += 1;
+        self.unmarked_total -= number;
+        true
+    }
+
+    /// The pattern the board has won with under these rules, if it has.
+    pub fn bingo(&self, rules: &Rules) -> Option<Pattern> {
+        rules.patterns.iter().find_map(|rule| self.completed(rule))
+    }
+
+    /// The pattern through this cell that the board has won with, if any. Any new
+    /// win has to go through the cell just marked, so this is all a board that
+    /// hadn't won yet needs checking after each mark.
+    pub fn bingo_at(&self, row: usize, col: usize, rules: &Rules) -> Option<Pattern> {
+        let n = self.size;
+        rules.patterns.iter().find_map(|rule| match rule {
+            WinRule::Rows => (self.row_hits[row] == n).then_some(Pattern::Row(row)),
+            WinRule::Columns => (self.column_hits[col] == n).then_some(Pattern::Column(col)),
+            WinRule::Diagonals => {
+                if row == col && self.diagonal_hits[0] == n {
+                    Some(Pattern::Diagonal)
+                } else if row + col == n - 1 && self.diagonal_hits[1] == n {
+                    Some(Pattern::AntiDiagonal)
+                } else {
+                    None
+                }
+            }
+            WinRule::Corners => {
+                (self.is_corner(row, col) && self.all_corners_marked()).then_some(Pattern::Corners)
+            }
+            WinRule::Blackout => (self.marked == n * n).then_some(Pattern::Blackout),
+            WinRule::Mask(mask) => mask
+                .cells
+                .contains(&(row, col))
+                .then(|| self.completed(rule))
+                .flatten(),
+        })
+    }
+
+    fn completed(&self, rule: &WinRule) -> Option<Pattern> {
+        let n = self.size;
+        match rule {
+            WinRule::Rows => (0..n)
+                .find(|&row| self.row_hits[row] == n)
+                .map(Pattern::Row),
+            WinRule::Columns => (0..n)
+                .find(|&col| self.column_hits[col] == n)
+                .map(Pattern::Column),
+            WinRule::Diagonals => {
+                if self.diagonal_hits[0] == n {
+                    Some(Pattern::Diagonal)
+                } else if self.diagonal_hits[1] == n {
+                    Some(Pattern::AntiDiagonal)
+                } else {
+                    None
                 }
             }
+            WinRule::Corners => self.all_corners_marked().then_some(Pattern::Corners),
+            WinRule::Blackout => (self.marked == n * n).then_some(Pattern::Blackout),
+            WinRule::Mask(mask) => {
+                let mut cells = mask.cells.iter();
+                (mask.fits(n) && cells.all(|&(row, col)| self.is_marked(row, col)))
+                    .then(|| Pattern::Mask(mask.name.clone()))
+            }
         }
+    }
+
+    fn is_corner(&self, row: usize, col: usize) -> bool {
+        let last = self.size - 1;
+        (row == 0 || row == last) && (col == 0 || col == last)
+    }
 
-        score
+    fn all_corners_marked(&self) -> bool {
+        // on a 1x1 board the four corners are the same cell
+        let corners = if self.size == 1 { 1 } else { 4 };
+        self.corner_hits == corners
+    }
+
+    pub fn unmarked_total(&self) -> i32 {
+        self.unmarked_total
     }
 }
 
@@ -77,6 +182,12 @@ impl Number {
     fn is_marked(&self) -> bool {
         matches!(self, Number::Marked(_))
     }
+
+    fn value(&self) -> i32 {
+        match *self {
+            Number::Unmarked(n) | Number::Marked(n) => n,
+        }
+    }
 }
 
 impl From<i32> for Number {
@@ -85,8 +196,99 @@ impl From<i32> for Number {
     }
 }
 
-impl Default for Number {
-    fn default() -> Self {
-        Number::Unmarked(0)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Mask;
+    use helpers::rng;
+
+    /// 1 to 9 in rows of three.
+    fn board() -> Board {
+        Board::new(0, 3, 1..=9)
+    }
+
+    fn marked(numbers: &[i32]) -> Board {
+        let mut board = board();
+        numbers.iter().for_each(|&n| board.mark(n));
+        board
+    }
+
+    #[test]
+    fn test_standard() {
+        let rules = Rules::standard();
+        assert_eq!(None, marked(&[1, 2, 5, 9]).bingo(&rules));
+        assert_eq!(Some(Pattern::Row(1)), marked(&[4, 5, 6]).bingo(&rules));
+        assert_eq!(Some(Pattern::Column(2)), marked(&[3, 6, 9]).bingo(&rules));
+        assert_eq!(45 - 18, marked(&[3, 6, 9]).unmarked_total());
+    }
+
+    #[test]
+    fn test_variants() {
+        let rules = "diagonals,corners,blackout".parse::<Rules>().unwrap();
+        assert_eq!(None, marked(&[4, 5, 6]).bingo(&rules));
+        assert_eq!(Some(Pattern::Diagonal), marked(&[1, 5, 9]).bingo(&rules));
+        assert_eq!(
+            Some(Pattern::AntiDiagonal),
+            marked(&[3, 5, 7]).bingo(&rules)
+        );
+        assert_eq!(Some(Pattern::Corners), marked(&[1, 3, 7, 9]).bingo(&rules));
+
+        let blackout = "blackout".parse::<Rules>().unwrap();
+        assert_eq!(None, marked(&[1, 2, 3, 4, 5, 6, 7, 8]).bingo(&blackout));
+        let all = (1..=9).collect::<Vec<_>>();
+        assert_eq!(Some(Pattern::Blackout), marked(&all).bingo(&blackout));
+        // the first rule wins when a number completes several
+        assert_eq!(Some(Pattern::Diagonal), marked(&all).bingo(&rules));
+    }
+
+    #[test]
+    fn test_masks() {
+        let plus = Mask::from_picture("plus", ".#.\n###\n.#.").unwrap();
+        let too_big = Mask::from_picture("too big", "...\n...\n...\n#..").unwrap();
+        let rules = Rules {
+            patterns: vec![WinRule::Mask(too_big), WinRule::Mask(plus)],
+        };
+        assert_eq!(None, marked(&[2, 4, 5, 6]).bingo(&rules));
+        assert_eq!(
+            Some(Pattern::Mask(String::from("plus"))),
+            marked(&[2, 4, 5, 6, 8]).bingo(&rules)
+        );
+        assert_eq!(
+            "mask `plus`",
+            Pattern::Mask(String::from("plus")).to_string()
+        );
+    }
+
+    #[test]
+    fn test_counters_match_a_full_check() {
+        let rules = "rows,columns,diagonals,corners,blackout"
+            .parse::<Rules>()
+            .unwrap();
+        rng::check("board counters", 50, |rng| {
+            let size = rng.range(1..=5) as usize;
+            let mut numbers = (0..(size * size) as i32).collect::<Vec<_>>();
+            rng.shuffle(&mut numbers);
+            let mut board = Board::new(0, size, numbers.iter().copied());
+            let total = numbers.iter().sum::<i32>();
+
+            let mut order = (0..size * size).collect::<Vec<_>>();
+            rng.shuffle(&mut order);
+            let mut marked_total = 0;
+            for i in order {
+                let (row, col) = (i / size, i % size);
+                let before = board.bingo(&rules);
+                assert!(board.mark_at(row, col));
+                assert!(!board.mark_at(row, col));
+                marked_total += board.number(row, col);
+                assert_eq!(total - marked_total, board.unmarked_total());
+                if before.is_none() {
+                    assert_eq!(board.bingo(&rules), board.bingo_at(row, col, &rules));
+                }
+            }
+            assert_eq!(
+                Some(Pattern::Blackout),
+                board.bingo(&"blackout".parse().unwrap())
+            );
+        });
     }
 }
@@ -0,0 +1,172 @@
+use crate::{
+    bingo::Board,
+    index::{Cell, NumberIndex},
+    rules::{Pattern, Rules},
+};
+use helpers::table::Table;
+
+/// A board getting bingo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Win {
+    pub board: usize,
+    /// Which number in the draw it won on, from 0.
+    pub draw: usize,
+    pub number: i32,
+    pub pattern: Pattern,
+    /// The sum of the numbers left unmarked, times the number that won.
+    pub score: i32,
+}
+
+/// How a whole game went: every board that won, in the order they won, and the
+/// boards that never did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timeline {
+    /// Boards that win on the same number are in board order.
+    pub wins: Vec<Win>,
+    pub never_won: Vec<usize>,
+}
+
+impl Timeline {
+    /// Calls every number in turn, until the numbers or the boards still playing
+    /// run out. Boards stop being marked once they've won. Each number only
+    /// touches the cells it's in, like `Board::mark` it marks the first of them on
+    /// each board that isn't already marked.
+    pub fn play(numbers: &[i32], boards: &[Board], rules: &Rules) -> Self {
+        let index = NumberIndex::new(boards);
+        let mut boards = boards.to_vec();
+        let mut won = vec![false; boards.len()];
+        let mut playing = boards.len();
+        let mut wins = Vec::new();
+
+        for (draw, &number) in numbers.iter().enumerate() {
+            if playing == 0 {
+                break;
+            }
+            let mut last_marked = None;
+            for &Cell { board: i, row, col } in index.cells(number) {
+                if won[i] || last_marked == Some(i) || !boards[i].mark_at(row, col) {
+                    continue;
+                }
+                last_marked = Some(i);
+                let board = &boards[i];
+                if let Some(pattern) = board.bingo_at(row, col, rules) {
+                    wins.push(Win {
+                        board: board.index,
+                        draw,
+                        number,
+                        pattern,
+                        score: board.unmarked_total() * number,
+                    });
+                    won[i] = true;
+                    playing -= 1;
+                }
+            }
+        }
+
+        let never_won = boards.iter().zip(won).filter(|(_, won)| !won);
+        Self {
+            wins,
+            never_won: never_won.map(|(board, _)| board.index).collect(),
+        }
+    }
+
+    pub fn first(&self) -> Option<&Win> {
+        self.wins.first()
+    }
+
+    pub fn last(&self) -> Option<&Win> {
+        self.wins.last()
+    }
+
+    /// The `n`th board to win, counting from 1.
+    pub fn nth(&self, n: usize) -> Option<&Win> {
+        n.checked_sub(1).and_then(|i| self.wins.get(i))
+    }
+
+    /// When a particular board won, if it did.
+    pub fn board(&self, board: usize) -> Option<&Win> {
+        self.wins.iter().find(|win| win.board == board)
+    }
+
+    /// The wins as a plain-text table, then the boards that never won.
+    pub fn table(&self) -> String {
+        let mut table = Table::new(&["Place", "Board", "Draw", "Number", "Pattern", "Score"]);
+        for (place, win) in self.wins.iter().enumerate() {
+            table.push(vec![
+                (place + 1).to_string(),
+                win.board.to_string(),
+                (win.draw + 1).to_string(),
+                win.number.to_string(),
+                win.pattern.to_string(),
+                win.score.to_string(),
+            ]);
+        }
+        let never_won = if self.never_won.is_empty() {
+            String::from("none")
+        } else {
+            let boards = self.never_won.iter().map(usize::to_string);
+            boards.collect::<Vec<_>>().join(", ")
+        };
+        format!("{}\nNever won: {}\n", table, never_won)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boards() -> Vec<Board> {
+        vec![
+            Board::new(0, 2, vec![1, 2, 3, 4].into_iter()),
+            Board::new(1, 2, vec![5, 6, 7, 8].into_iter()),
+            Board::new(2, 2, vec![1, 5, 9, 9].into_iter()),
+        ]
+    }
+
+    #[test]
+    fn test_timeline() {
+        let timeline = Timeline::play(&[1, 5, 2, 6, 3], &boards(), &Rules::standard());
+        let order = timeline
+            .wins
+            .iter()
+            .map(|w| (w.board, w.draw, w.score))
+            .collect::<Vec<_>>();
+        // board 2 wins on 5 with its top row, board 0 on 2, then board 1 on 6
+        assert_eq!(vec![(2, 1, 18 * 5), (0, 2, 7 * 2), (1, 3, 15 * 6)], order);
+        assert_eq!(Pattern::Row(0), timeline.first().unwrap().pattern);
+        assert_eq!(Some(1), timeline.last().map(|w| w.board));
+        assert_eq!(Some(0), timeline.nth(2).map(|w| w.board));
+        assert_eq!(None, timeline.nth(0));
+        assert_eq!(Some(3), timeline.board(1).map(|w| w.draw));
+        assert!(timeline.never_won.is_empty());
+    }
+
+    #[test]
+    fn test_repeated_numbers() {
+        // a number twice on a board is marked once per call, like Board::mark does
+        let boards = vec![Board::new(0, 2, vec![1, 1, 2, 3].into_iter())];
+        let timeline = Timeline::play(&[1, 3, 1], &boards, &Rules::standard());
+        assert_eq!(Some(2), timeline.first().map(|w| w.draw));
+        assert_eq!(Pattern::Row(0), timeline.wins[0].pattern);
+    }
+
+    #[test]
+    fn test_never_won() {
+        let timeline = Timeline::play(&[1, 3], &boards(), &Rules::standard());
+        assert_eq!(
+            vec![0],
+            timeline.wins.iter().map(|w| w.board).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![1, 2], timeline.never_won);
+        assert_eq!(None, timeline.board(2));
+
+        let expected = "\
+Place | Board | Draw | Number | Pattern  | Score
+------+-------+------+--------+----------+------
+1     | 0     | 2    | 3      | column 1 | 18
+
+Never won: 1, 2
+";
+        assert_eq!(expected, timeline.table());
+    }
+}
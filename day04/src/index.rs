@@ -0,0 +1,59 @@
+use crate::bingo::Board;
+use std::collections::HashMap;
+
+/// Where a number is on one of the boards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    /// The board's position in the list the index was built from.
+    pub board: usize,
+    pub row: usize,
+    pub col: usize,
+}
+
+/// Every cell of every board, by number, so calling a number goes straight to the
+/// cells it's in instead of searching each board.
+#[derive(Debug, Clone, Default)]
+pub struct NumberIndex {
+    cells: HashMap<i32, Vec<Cell>>,
+}
+
+impl NumberIndex {
+    pub fn new(boards: &[Board]) -> Self {
+        let mut cells = HashMap::<i32, Vec<Cell>>::new();
+        for (board, b) in boards.iter().enumerate() {
+            for (number, row, col) in b.numbers() {
+                cells
+                    .entry(number)
+                    .or_default()
+                    .push(Cell { board, row, col });
+            }
+        }
+        Self { cells }
+    }
+
+    /// The cells with this number, in board order, then row by row.
+    pub fn cells(&self, number: i32) -> &[Cell] {
+        self.cells.get(&number).map_or(&[], Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index() {
+        let boards = vec![
+            Board::new(0, 2, vec![1, 2, 3, 1].into_iter()),
+            Board::new(1, 2, vec![4, 5, 6, 1].into_iter()),
+        ];
+        let index = NumberIndex::new(&boards);
+        let cell = |board, row, col| Cell { board, row, col };
+        assert_eq!(
+            &[cell(0, 0, 0), cell(0, 1, 1), cell(1, 1, 1)],
+            index.cells(1)
+        );
+        assert_eq!(&[cell(1, 1, 0)], index.cells(6));
+        assert!(index.cells(7).is_empty());
+    }
+}
@@ -0,0 +1,201 @@
+mod bingo;
+mod game;
+mod index;
+mod odds;
+mod rules;
+
+pub use bingo::Board;
+pub use game::{Timeline, Win};
+use helpers::{
+    parse::{self, InputFile, Span},
+    ParseError, Solution,
+};
+pub use index::{Cell, NumberIndex};
+pub use odds::{BoardOdds, Odds};
+pub use rules::{Mask, Pattern, Rules, WinRule};
+use std::fmt;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = (Vec<i32>, Vec<Board>);
+    type Part1 = Score;
+    type Part2 = Score;
+
+    fn parse(file_name: &str) -> Result<(Vec<i32>, Vec<Board>), ParseError> {
+        parse_numbers_and_boards(file_name)
+    }
+
+    fn part1((numbers, boards): &(Vec<i32>, Vec<Board>)) -> Score {
+        let timeline = Timeline::play(numbers, boards, &Rules::standard());
+        Score::of(timeline.first())
+    }
+
+    fn part2((numbers, boards): &(Vec<i32>, Vec<Board>)) -> Score {
+        let timeline = Timeline::play(numbers, boards, &Rules::standard());
+        Score::of(timeline.last())
+    }
+}
+
+/// Both parts' answer, which needs a board that wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Score {
+    Won(i32),
+    /// Every number was called without any board getting bingo.
+    NoWinner,
+}
+
+impl Score {
+    fn of(win: Option<&Win>) -> Self {
+        win.map_or(Score::NoWinner, |win| Score::Won(win.score))
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Score::Won(score) => write!(f, "{}", score),
+            Score::NoWinner => write!(f, "none, no board ever wins"),
+        }
+    }
+}
+
+/// Plays a whole game of bingo from a file.
+pub fn timeline_from_file(file_name: &str, rules: &Rules) -> Result<Timeline, ParseError> {
+    let (numbers, boards) = parse_numbers_and_boards(file_name)?;
+    Ok(Timeline::play(&numbers, &boards, rules))
+}
+
+/// Estimates each board's odds over `trials` random orders of the numbers in a file.
+pub fn odds_from_file(
+    file_name: &str,
+    rules: &Rules,
+    trials: usize,
+    seed: u64,
+) -> Result<Odds, ParseError> {
+    let (numbers, boards) = parse_numbers_and_boards(file_name)?;
+    Ok(Odds::simulate(&numbers, &boards, rules, trials, seed))
+}
+
+pub fn parse_numbers_and_boards(file_name: &str) -> Result<(Vec<i32>, Vec<Board>), ParseError> {
+    parse_input(&InputFile::open(file_name)?)
+}
+
+/// The called numbers, then the boards. Boards can be any size, as long as they're
+/// square.
+fn parse_input(input: &InputFile) -> Result<(Vec<i32>, Vec<Board>), ParseError> {
+    let mut chunks = input.chunks().into_iter();
+
+    let header = chunks
+        .next()
+        .ok_or_else(|| input.error("a line of called numbers", "an empty file"))?;
+    if header.len() > 1 {
+        return Err(header[1].error("a blank line before the first board"));
+    }
+    let numbers = parse::comma_separated(header[0], "a called number")?;
+
+    let boards = chunks
+        .enumerate()
+        .map(|(i, chunk)| parse_board(i, &chunk))
+        .collect::<Result<_, _>>()?;
+    Ok((numbers, boards))
+}
+
+fn parse_board(index: usize, chunk: &[Span]) -> Result<Board, ParseError> {
+    let size = chunk[0].split_whitespace().count();
+    if size == 0 {
+        return Err(chunk[0].error("a row of board numbers"));
+    }
+    if chunk.len() < size {
+        let expected = format!("a board of {} rows", size);
+        return Err(chunk[chunk.len() - 1].missing(&expected));
+    }
+    if chunk.len() > size {
+        return Err(chunk[size].error("a blank line between boards"));
+    }
+
+    let mut numbers = Vec::with_capacity(size * size);
+    for line in chunk {
+        let row = line
+            .split_whitespace()
+            .map(|n| n.parse("a board number"))
+            .collect::<Result<Vec<i32>, _>>()?;
+        if row.len() != size {
+            return Err(line.error(&format!("a row of {} numbers", size)));
+        }
+        numbers.extend(row);
+    }
+    Ok(Board::new(index, size, numbers.into_iter()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Score::Won(4512), Day04::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(Score::Won(69579), Day04::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Score::Won(1924), Day04::solve_part2("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(Score::Won(14877), Day04::solve_part2("input.txt"));
+    }
+
+    #[test]
+    fn test_timeline() {
+        let timeline = timeline_from_file("input.txt", &Rules::standard()).unwrap();
+        assert_eq!(100, timeline.wins.len() + timeline.never_won.len());
+        assert_eq!(Some(69579), timeline.first().map(|w| w.score));
+        assert_eq!(Some(14877), timeline.last().map(|w| w.score));
+        let draws = timeline.wins.iter().map(|w| w.draw).collect::<Vec<_>>();
+        assert!(draws.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        // blackout is harder to get than a row or column
+        let blackout = timeline_from_file("test_input.txt", &"blackout".parse().unwrap()).unwrap();
+        let standard = timeline_from_file("test_input.txt", &Rules::standard()).unwrap();
+        assert!(blackout.wins.len() <= standard.wins.len());
+        assert!(blackout
+            .first()
+            .is_none_or(|w| w.draw > standard.first().unwrap().draw));
+    }
+
+    #[test]
+    fn test_no_winner() {
+        // 1 and 4 only make a diagonal, which doesn't count
+        let text = "1,4\n\n1 2\n3 4";
+        let input = parse_input(&InputFile::from_text("diagonal.txt", text)).unwrap();
+        assert_eq!(Score::NoWinner, Day04::part1(&input));
+        assert_eq!(Score::NoWinner, Day04::part2(&input));
+        assert_eq!("none, no board ever wins", Score::NoWinner.to_string());
+    }
+
+    #[test]
+    fn test_board_sizes() {
+        let text = "3,1,2\n\n1 2\n3 4\n\n1 2 3\n4 5 6\n7 8 9";
+        let (numbers, boards) = parse_input(&InputFile::from_text("sizes.txt", text)).unwrap();
+        assert_eq!(vec![3, 1, 2], numbers);
+        assert_eq!(
+            vec![2, 3],
+            boards.iter().map(Board::size).collect::<Vec<_>>()
+        );
+        assert_eq!(8, boards[1].number(2, 1));
+
+        let error = |text| parse_input(&InputFile::from_text("boards.txt", text)).unwrap_err();
+        assert_eq!(4, error("1\n\n1 2 3\n4 5 6").line);
+        assert_eq!(5, error("1\n\n1 2\n3 4\n5 6").line);
+        assert_eq!(4, error("1\n\n1 2\n3 4 5").line);
+    }
+}
@@ -1,128 +1,91 @@
-mod bingo;
+use day04::Rules;
+use helpers::{is_file_arg, parse_number};
 
-use std::collections::VecDeque;
-use bingo::Board;
+const USAGE: &str = "Usage:
+    day04 [FILE]
+    day04 timeline [FILE] [--rules <RULES>]
+    day04 odds [FILE] [--rules <RULES>] [--trials <N>] [--seed <N>]
 
-fn main() {
-    println!("Answer one: {}", part1("input.txt"));
-    println!("Answer two: {}", part2("input.txt"));
-}
+timeline plays the whole game in FILE (or the cached input) and lists every board in
+the order they won, with the number and pattern they won on and their score, then the
+boards that never won.
 
-fn part1(file_name: &str) -> i32 {
-    let (numbers, mut boards) = parse_numbers_and_boards(file_name);
+odds plays --trials games (10000 by default) with the same numbers called in random
+orders, shuffled from --seed (2021 by default), and estimates each board's chance of
+winning first, of winning last, and how many numbers it takes to win on average.
 
-    println!("Loaded {} boards, starting the game.", boards.len());
+--rules is a comma-separated list of the ways to win: rows, columns, diagonals,
+corners and blackout (rows,columns by default).";
 
-    for number in numbers {
-        println!("Calling: {}", number);
+enum Mode {
+    Timeline,
+    Odds { trials: usize, seed: u64 },
+}
 
-        for board in boards.iter_mut() {
-            board.mark(number);
-            if board.bingo() {
-                println!("Bingo! Board {} has won!", board.index);
-                return board.unmarked_total() * number;
-            }
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let mode = match args.first().map(String::as_str) {
+        Some("timeline") => Mode::Timeline,
+        Some("odds") => Mode::Odds {
+            trials: 10_000,
+            seed: 2021,
+        },
+        _ => {
+            helpers::run_main(&day04::Day04);
+            return;
         }
+    };
+
+    let (file_name, rules, mode) = match parse_args(&args[1..], mode) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = helpers::parallel::init_from_env() {
+        eprintln!("{}", e);
+        std::process::exit(2);
     }
+    let file_name = file_name.unwrap_or_else(|| helpers::resolve_or_exit(4));
 
-    panic!("Shit!")
+    let output = match mode {
+        Mode::Timeline => day04::timeline_from_file(&file_name, &rules).map(|t| t.table()),
+        Mode::Odds { trials, seed } => {
+            day04::odds_from_file(&file_name, &rules, trials, seed).map(|odds| odds.table())
+        }
+    };
+    match output {
+        Ok(output) => print!("{}", output),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
 }
 
-fn part2(file_name: &str) -> i32 {
-    let (numbers, boards) = parse_numbers_and_boards(file_name);
-    let mut boards: VecDeque<_> = boards.into();
-
-    println!(
-        "Loaded {} boards, beginning search for worst board.",
-        boards.len()
-    );
-
-    for number in numbers {
-        println!("Calling: {}", number);
-
-        if boards.len() > 1 {
-            for _ in 0..boards.len() {
-                let mut board = boards.pop_front().unwrap();
-                board.mark(number);
-                if !board.bingo() {
-                    boards.push_back(board);
-                } else {
-                    println!("Removing board {}", board.index);
+fn parse_args(args: &[String], mut mode: Mode) -> Result<(Option<String>, Rules, Mode), String> {
+    let mut file_name = None;
+    let mut rules = Rules::standard();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match (arg.as_str(), &mut mode) {
+            ("--rules" | "-r", _) => rules = value()?.parse()?,
+            ("--trials" | "-t", Mode::Odds { trials, .. }) => {
+                *trials = parse_number(arg, value()?)?;
+                if *trials == 0 {
+                    return Err(String::from("--trials must be at least 1"));
                 }
             }
-        } else {
-            let board = boards.get_mut(0).unwrap();
-            board.mark(number);
-            if board.bingo() {
-                println!(
-                    "Found the worst board! Board {} is the biggest stinker.",
-                    board.index
-                );
-                return board.unmarked_total() * number;
-            }
+            ("--seed", Mode::Odds { seed, .. }) => *seed = parse_number(arg, value()?)?,
+            _ if file_name.is_none() && is_file_arg(arg) => file_name = Some(arg.clone()),
+            _ => return Err(format!("Unexpected argument `{}`", arg)),
         }
     }
-
-    panic!("Shit!")
-}
-
-fn parse_numbers_and_boards(file_name: &str) -> (Vec<i32>, Vec<Board>) {
-    let mut lines = helpers::read_lines_panicky(file_name);
-    let numbers = parse_called_numbers(&mut lines);
-    let boards = parse_boards(lines);
-    (numbers, boards)
-}
-
-fn parse_called_numbers<I: Iterator<Item = String>>(lines: &mut I) -> Vec<i32> {
-    lines
-        .next()
-        .unwrap()
-        .split(",")
-        .map(|n| n.parse().unwrap())
-        .collect()
-}
-
-fn parse_boards<I: Iterator<Item = String>>(lines: I) -> Vec<Board> {
-    let lines: Vec<_> = lines
-        .filter(|l| !l.is_empty())
-        .collect();
-
-    lines
-        .chunks_exact(5)
-        .map(flatten_single_board)
-        .enumerate()
-        .map(|(i, nums)| Board::new(i, nums))
-        .collect()
-}
-
-fn flatten_single_board(chunk: &[String]) -> impl Iterator<Item = i32> + '_ {
-    chunk
-        .iter()
-        .map(|l| l.split_whitespace().map(|n| n.parse().unwrap()))
-        .flatten()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(4512, part1("test_input.txt"));
-    }
-
-    #[test]
-    fn final_part1() {
-        assert_eq!(69579, part1("input.txt"));
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(1924, part2("test_input.txt"));
-    }
-
-    #[test]
-    fn final_part2() {
-        assert_eq!(14877, part2("input.txt"));
-    }
+    Ok((file_name, rules, mode))
 }
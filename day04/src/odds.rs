@@ -0,0 +1,181 @@
+use crate::{bingo::Board, game::Timeline, rules::Rules};
+use helpers::{parallel, rng::Rng, table::Table};
+use std::collections::HashMap;
+
+/// How one board tends to do when the numbers are called in a random order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardOdds {
+    pub board: usize,
+    /// The chance of winning before any other board. Boards that tie for first
+    /// share the credit, so the chances across all boards add up to 1.
+    pub first: f64,
+    /// The chance of being the last board to win, shared the same way.
+    pub last: f64,
+    /// The chance of winning at all before the numbers run out.
+    pub wins: f64,
+    /// How many numbers it takes to win on average, in the games it did win.
+    pub mean_draws: Option<f64>,
+}
+
+/// Each board's odds over many games, each calling the same numbers in a different
+/// order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Odds {
+    pub trials: usize,
+    pub seed: u64,
+    pub boards: Vec<BoardOdds>,
+}
+
+/// Running totals for one board.
+#[derive(Clone, Default)]
+struct Tally {
+    first: f64,
+    last: f64,
+    wins: usize,
+    draws: usize,
+}
+
+impl Odds {
+    /// Plays `trials` games, each shuffling the numbers with its own generator,
+    /// seeded from a stream of numbers started from `seed`. The estimate is the same
+    /// every time for a seed, whatever the number of threads, and nearby seeds give
+    /// unrelated games.
+    pub fn simulate(
+        numbers: &[i32],
+        boards: &[Board],
+        rules: &Rules,
+        trials: usize,
+        seed: u64,
+    ) -> Self {
+        let mut seeds = Rng::new(seed);
+        let games = (0..trials).map(|_| seeds.next_u64()).collect::<Vec<_>>();
+        let timelines = parallel::map(&games, |&game| {
+            let mut rng = Rng::new(game);
+            let mut numbers = numbers.to_vec();
+            rng.shuffle(&mut numbers);
+            Timeline::play(&numbers, boards, rules)
+        });
+
+        let positions = boards
+            .iter()
+            .enumerate()
+            .map(|(position, board)| (board.index, position))
+            .collect::<HashMap<_, _>>();
+        let mut tallies = vec![Tally::default(); boards.len()];
+        for timeline in timelines.iter() {
+            let (first, last) = match (timeline.first(), timeline.last()) {
+                (Some(first), Some(last)) => (first.draw, last.draw),
+                _ => continue,
+            };
+            let share = |draw| {
+                let tied = timeline.wins.iter().filter(|w| w.draw == draw).count();
+                1.0 / tied as f64
+            };
+            let (first_share, last_share) = (share(first), share(last));
+            for win in timeline.wins.iter() {
+                let tally = &mut tallies[positions[&win.board]];
+                tally.wins += 1;
+                tally.draws += win.draw + 1;
+                if win.draw == first {
+                    tally.first += first_share;
+                }
+                if win.draw == last {
+                    tally.last += last_share;
+                }
+            }
+        }
+
+        let trials_f = trials.max(1) as f64;
+        let boards = boards
+            .iter()
+            .zip(tallies)
+            .map(|(board, tally)| BoardOdds {
+                board: board.index,
+                first: tally.first / trials_f,
+                last: tally.last / trials_f,
+                wins: tally.wins as f64 / trials_f,
+                mean_draws: (tally.wins > 0).then(|| tally.draws as f64 / tally.wins as f64),
+            })
+            .collect();
+        Self {
+            trials,
+            seed,
+            boards,
+        }
+    }
+
+    /// The odds as a plain-text table, most likely first winner at the top.
+    pub fn table(&self) -> String {
+        let mut boards = self.boards.iter().collect::<Vec<_>>();
+        boards.sort_by(|a, b| b.first.total_cmp(&a.first).then(a.board.cmp(&b.board)));
+
+        let mut table = Table::new(&["Board", "First", "Last", "Wins", "Mean draws"]);
+        for odds in boards {
+            table.push(vec![
+                odds.board.to_string(),
+                format!("{:.2}%", odds.first * 100.0),
+                format!("{:.2}%", odds.last * 100.0),
+                format!("{:.2}%", odds.wins * 100.0),
+                odds.mean_draws
+                    .map_or_else(|| String::from("-"), |mean| format!("{:.2}", mean)),
+            ]);
+        }
+        format!("{} games with seed {}\n\n{}", self.trials, self.seed, table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_odds() {
+        // board 0 wins as soon as 1 is called, board 1 once 3 and 4 both have been,
+        // and board 2 never can
+        let boards = vec![
+            Board::new(0, 1, vec![1].into_iter()),
+            Board::new(1, 2, vec![3, 4, 9, 9].into_iter()),
+            Board::new(2, 1, vec![5].into_iter()),
+        ];
+        let numbers = [1, 2, 3, 4];
+        let odds = Odds::simulate(&numbers, &boards, &Rules::standard(), 2000, 7);
+        let threaded = parallel::with_threads(4, || {
+            Odds::simulate(&numbers, &boards, &Rules::standard(), 2000, 7)
+        });
+        assert_eq!(odds, threaded);
+
+        let [one, two, never] = [&odds.boards[0], &odds.boards[1], &odds.boards[2]];
+        assert_eq!((1.0, 1.0), (one.wins, two.wins));
+        assert_eq!(
+            (0.0, 0.0, None),
+            (never.first, never.last, never.mean_draws)
+        );
+        let total_first = odds.boards.iter().map(|b| b.first).sum::<f64>();
+        assert!((total_first - 1.0).abs() < 1e-9);
+
+        // 1 is called after both 3 and 4 a third of the time
+        assert!((one.first - 2.0 / 3.0).abs() < 0.05, "{:?}", one);
+        assert!((two.last - 2.0 / 3.0).abs() < 0.05, "{:?}", two);
+        // 1 comes at 2.5 on average, the later of 3 and 4 at 10/3
+        assert!((one.mean_draws.unwrap() - 2.5).abs() < 0.1, "{:?}", one);
+        assert!(
+            (two.mean_draws.unwrap() - 10.0 / 3.0).abs() < 0.1,
+            "{:?}",
+            two
+        );
+
+        // the next seed along plays different games, not the same ones shifted
+        let next = Odds::simulate(&numbers, &boards, &Rules::standard(), 3, 8);
+        let shifted = Odds::simulate(&numbers, &boards, &Rules::standard(), 3, 7);
+        assert_ne!(next.boards, shifted.boards);
+
+        let table = odds.table();
+        assert!(table.starts_with("2000 games with seed 7"), "{}", table);
+        let last_row = table.lines().last().unwrap();
+        assert!(
+            last_row.starts_with("2 ") && last_row.ends_with('-'),
+            "{}",
+            table
+        );
+    }
+}
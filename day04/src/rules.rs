@@ -0,0 +1,167 @@
+use std::{fmt, str::FromStr};
+
+/// A way for a board to win, which may cover several patterns (every row, say).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WinRule {
+    Rows,
+    Columns,
+    /// Either of the two diagonals.
+    Diagonals,
+    /// All four corners.
+    Corners,
+    /// Every number on the board.
+    Blackout,
+    Mask(Mask),
+}
+
+/// A custom pattern of cells that all have to be marked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    pub name: String,
+    /// (row, column) of each cell, from the top left.
+    pub cells: Vec<(usize, usize)>,
+}
+
+impl Mask {
+    /// A mask drawn with a line per row, `#` for the cells it needs and `.` for the
+    /// rest, like
+    ///
+    /// ```text
+    /// #...#
+    /// .#.#.
+    /// ..#..
+    /// ```
+    pub fn from_picture(name: &str, picture: &str) -> Result<Self, String> {
+        let mut cells = Vec::new();
+        for (row, line) in picture.lines().enumerate() {
+            for (col, c) in line.trim().chars().enumerate() {
+                match c {
+                    '#' => cells.push((row, col)),
+                    '.' => {}
+                    _ => {
+                        return Err(format!(
+                            "Masks are drawn with `#` and `.`, not `{}` (row {}, column {})",
+                            c,
+                            row + 1,
+                            col + 1
+                        ))
+                    }
+                }
+            }
+        }
+        if cells.is_empty() {
+            return Err(format!("The mask `{}` doesn't need any cells", name));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            cells,
+        })
+    }
+
+    /// Whether every cell of the mask is on a board of this size. A mask that
+    /// doesn't fit can't be won.
+    pub fn fits(&self, size: usize) -> bool {
+        self.cells
+            .iter()
+            .all(|&(row, col)| row < size && col < size)
+    }
+}
+
+/// Which pattern a board won with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pattern {
+    Row(usize),
+    Column(usize),
+    /// From the top left to the bottom right.
+    Diagonal,
+    /// From the top right to the bottom left.
+    AntiDiagonal,
+    Corners,
+    Blackout,
+    Mask(String),
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pattern::Row(row) => write!(f, "row {}", row + 1),
+            Pattern::Column(col) => write!(f, "column {}", col + 1),
+            Pattern::Diagonal => f.write_str("diagonal"),
+            Pattern::AntiDiagonal => f.write_str("anti-diagonal"),
+            Pattern::Corners => f.write_str("four corners"),
+            Pattern::Blackout => f.write_str("blackout"),
+            Pattern::Mask(name) => write!(f, "mask `{}`", name),
+        }
+    }
+}
+
+/// The ways a board can win in one variant of bingo. When a number completes
+/// several patterns at once, the board wins with the first rule's, and the first
+/// row or column within a rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    pub patterns: Vec<WinRule>,
+}
+
+impl Rules {
+    /// The puzzle's rules: any full row or column.
+    pub fn standard() -> Self {
+        Self {
+            patterns: vec![WinRule::Rows, WinRule::Columns],
+        }
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// A comma-separated list like `rows,columns,diagonals`, of `rows`, `columns`,
+/// `diagonals`, `corners` and `blackout`. Masks have to be added in code.
+impl FromStr for Rules {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let patterns = s
+            .split(',')
+            .map(|rule| match rule.trim() {
+                "rows" => Ok(WinRule::Rows),
+                "columns" => Ok(WinRule::Columns),
+                "diagonals" => Ok(WinRule::Diagonals),
+                "corners" => Ok(WinRule::Corners),
+                "blackout" => Ok(WinRule::Blackout),
+                other => Err(format!(
+                    "Rules are rows, columns, diagonals, corners or blackout, not `{}`",
+                    other
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { patterns })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rules() {
+        assert_eq!(Ok(Rules::standard()), "rows, columns".parse());
+        assert_eq!(
+            Ok(vec![WinRule::Corners, WinRule::Blackout]),
+            "corners,blackout".parse().map(|r: Rules| r.patterns)
+        );
+        assert!("rows,stripes".parse::<Rules>().is_err());
+    }
+
+    #[test]
+    fn test_mask() {
+        let mask = Mask::from_picture("x", "#.#\n.#.\n#.#").unwrap();
+        assert_eq!(vec![(0, 0), (0, 2), (1, 1), (2, 0), (2, 2)], mask.cells);
+        assert!(mask.fits(3) && !mask.fits(2));
+        assert!(Mask::from_picture("bad", "#x").is_err());
+        assert!(Mask::from_picture("empty", "...").is_err());
+    }
+}
@@ -0,0 +1,271 @@
+use helpers::{parse::InputFile, stream::LineReader, table::Table, ParseError, Solution};
+use std::{
+    collections::HashMap,
+    io::BufRead,
+    time::{Duration, Instant},
+};
+
+mod line;
+mod sweep;
+
+pub use line::{Line, Point};
+pub use sweep::count_overlaps;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = Vec<Line>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(file_name: &str) -> Result<Vec<Line>, ParseError> {
+        parse_lines(file_name)
+    }
+
+    fn part1(lines: &Vec<Line>) -> usize {
+        Counter::Sweep.count(Day05::straight(lines))
+    }
+
+    fn part2(lines: &Vec<Line>) -> usize {
+        Counter::Sweep.count(lines.iter())
+    }
+
+    fn stream(file_name: &str) -> Option<Result<(usize, usize), ParseError>> {
+        Some(LineReader::open(file_name).and_then(solve_stream))
+    }
+}
+
+impl Day05 {
+    /// Only the horizontal and vertical lines, which part 1 looks at.
+    fn straight(lines: &[Line]) -> impl Iterator<Item = &Line> {
+        lines
+            .iter()
+            .filter(|l| l.is_horizontal() || l.is_vertical())
+    }
+}
+
+/// Ways of counting the points where at least two lines overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Counter {
+    /// Lists every point on every line and counts them, which takes as long as the
+    /// lines are.
+    Points,
+    /// Finds where the lines overlap and cross, see `count_overlaps`.
+    Sweep,
+}
+
+impl Counter {
+    pub const ALL: [Counter; 2] = [Counter::Points, Counter::Sweep];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Counter::Points => "points",
+            Counter::Sweep => "sweep",
+        }
+    }
+
+    pub fn count<'a, I: Iterator<Item = &'a Line>>(self, lines: I) -> usize {
+        match self {
+            Counter::Points => count_most_dangerous_points(lines),
+            Counter::Sweep => count_overlaps(lines),
+        }
+    }
+}
+
+/// How long one way of counting took for both parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub counter: Counter,
+    pub part1: usize,
+    pub part2: usize,
+    /// The fastest of the runs, for both parts together.
+    pub best: Duration,
+}
+
+/// Times each of `counters` on the lines in a file, with every coordinate
+/// multiplied by `scale`, taking the best of `iterations` runs. A line that would
+/// no longer fit in an `i32` once scaled is an error.
+pub fn compare_from_file(
+    file_name: &str,
+    counters: &[Counter],
+    scale: i32,
+    iterations: usize,
+) -> Result<Vec<Timing>, ParseError> {
+    let input = InputFile::open(file_name)?;
+    let too_big = format!("coordinates that fit in an i32 multiplied by {}", scale);
+    let lines = input
+        .lines()
+        .map(|span| {
+            Line::parse(span)?
+                .scaled(scale)
+                .ok_or_else(|| span.error(&too_big))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let timings = counters.iter().map(|&counter| {
+        let mut best = Duration::MAX;
+        let (mut part1, mut part2) = (0, 0);
+        for _ in 0..iterations.max(1) {
+            let start = Instant::now();
+            part1 = counter.count(Day05::straight(&lines));
+            part2 = counter.count(lines.iter());
+            best = best.min(start.elapsed());
+        }
+        Timing {
+            counter,
+            part1,
+            part2,
+            best,
+        }
+    });
+    Ok(timings.collect())
+}
+
+/// Timings as a plain-text table.
+pub fn timing_table(timings: &[Timing]) -> String {
+    let mut table = Table::new(&["Counter", "Part 1", "Part 2", "Best"]);
+    for timing in timings {
+        table.push(vec![
+            timing.counter.name().to_string(),
+            timing.part1.to_string(),
+            timing.part2.to_string(),
+            format!("{:?}", timing.best),
+        ]);
+    }
+    table.to_string()
+}
+
+fn count_most_dangerous_points<'a, I: Iterator<Item = &'a Line>>(lines: I) -> usize {
+    let mut vents = Vents::default();
+    lines.for_each(|l| vents.add(l));
+    vents.most_dangerous()
+}
+
+/// Both parts in one pass, a line of the file at a time. The vent lines are kept,
+/// but not the points on them, so memory grows with the file however long the lines
+/// are.
+pub fn solve_stream<R: BufRead>(lines: LineReader<R>) -> Result<(usize, usize), ParseError> {
+    let lines = lines
+        .parse_lines(Line::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((
+        Counter::Sweep.count(Day05::straight(&lines)),
+        Counter::Sweep.count(lines.iter()),
+    ))
+}
+
+/// How many vent lines cross each point.
+#[derive(Default)]
+struct Vents {
+    point_counts: HashMap<Point, usize>,
+}
+
+impl Vents {
+    fn add(&mut self, line: &Line) {
+        for point in line.points_on_line() {
+            *self.point_counts.entry(point).or_insert(0) += 1;
+        }
+    }
+
+    /// Points where at least two lines overlap.
+    fn most_dangerous(&self) -> usize {
+        self.point_counts.values().filter(|&c| *c > 1).count()
+    }
+}
+
+fn parse_lines(file_name: &str) -> Result<Vec<Line>, ParseError> {
+    let input = InputFile::open(file_name)?;
+    let lines = input.lines().map(Line::parse);
+    lines.collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(5, Day05::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(7318, Day05::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(12, Day05::solve_part2("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(19939, Day05::solve_part2("input.txt"));
+    }
+
+    #[test]
+    fn test_stream() {
+        let lines = LineReader::open("input.txt").unwrap();
+        assert_eq!(Ok((7318, 19939)), solve_stream(lines));
+
+        // far too many points to list, but only two lines to keep
+        let text = "0,0 -> 0,2000000000\n0,5 -> 0,10\n-2000000000,7 -> 2000000000,7\n";
+        let lines = LineReader::new("test", text.as_bytes());
+        assert_eq!(Ok((6, 6)), solve_stream(lines));
+    }
+
+    #[test]
+    fn test_compare() {
+        let timings = compare_from_file("input.txt", &Counter::ALL, 1, 1).unwrap();
+        let answers = timings
+            .iter()
+            .map(|t| (t.counter, t.part1, t.part2))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (Counter::Points, 7318, 19939),
+                (Counter::Sweep, 7318, 19939)
+            ],
+            answers
+        );
+
+        // scaling the lines up keeps them crossing in the same places but multiplies
+        // their overlaps
+        let timings = compare_from_file("test_input.txt", &Counter::ALL, 1000, 1).unwrap();
+        assert_eq!(timings[0].part2, timings[1].part2);
+        assert!(timings[1].part2 > 12, "{:?}", timings);
+        assert!(timing_table(&timings).starts_with("Counter | Part 1"));
+
+        // an even scale lands a crossing between points on a point of its own
+        let input = InputFile::from_text("cross.txt", "0,0 -> 1,1\n0,1 -> 1,0");
+        let lines = input
+            .lines()
+            .map(|l| Line::parse(l).unwrap())
+            .collect::<Vec<_>>();
+        let scaled = lines
+            .iter()
+            .map(|l| l.scaled(2).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!((0, 1), (Day05::part2(&lines), Day05::part2(&scaled)));
+
+        // every point right up to the edge of an i32 counts the same either way
+        let max = i32::MAX;
+        let text = format!("{0},{1} -> {1},{1}\n{1},{0} -> {1},{1}", max - 7, max);
+        let input = InputFile::from_text("edge.txt", &text);
+        let lines = input
+            .lines()
+            .map(|l| Line::parse(l).unwrap())
+            .collect::<Vec<_>>();
+        let counts = Counter::ALL.map(|counter| counter.count(lines.iter()));
+        assert_eq!([1, 1], counts);
+
+        // the largest coordinate in the input is 990, so this would overflow
+        let error = compare_from_file("input.txt", &Counter::ALL, 5_000_000, 1).unwrap_err();
+        assert!(
+            error.to_string().contains("multiplied by 5000000"),
+            "{}",
+            error
+        );
+    }
+}
@@ -1,12 +1,42 @@
-use std::{num::ParseIntError, str::FromStr};
+use helpers::{parse::Span, ParseError};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Line {
     a: Point,
     b: Point,
 }
 
 impl Line {
+    /// Parses `x1,y1 -> x2,y2`.
+    pub fn parse(line: Span) -> Result<Self, ParseError> {
+        let (a, b) = line.split_once(" -> ")?;
+        Ok(Self {
+            a: Point::parse(a)?,
+            b: Point::parse(b)?,
+        })
+    }
+
+    /// The two ends, in the order they were given.
+    pub fn ends(&self) -> (Point, Point) {
+        (self.a, self.b)
+    }
+
+    /// The same line with every coordinate multiplied by `factor`, which keeps it
+    /// straight or at 45 degrees but makes it `factor` times longer, or `None` if a
+    /// coordinate would no longer fit in an `i32`.
+    pub fn scaled(&self, factor: i32) -> Option<Self> {
+        let scale = |p: Point| {
+            Some(Point {
+                x: p.x.checked_mul(factor)?,
+                y: p.y.checked_mul(factor)?,
+            })
+        };
+        Some(Self {
+            a: scale(self.a)?,
+            b: scale(self.b)?,
+        })
+    }
+
     pub fn is_horizontal(&self) -> bool {
         self.a.y == self.b.y
     }
@@ -18,12 +48,12 @@ impl Line {
     pub fn points_on_line(&self) -> Vec<Point> {
         if self.is_horizontal() {
             let (left, right) = self.left_to_right();
-            (left.x..right.x + 1)
+            (left.x..=right.x)
                 .map(|x| Point { x, y: self.a.y })
                 .collect()
         } else if self.is_vertical() {
             let (top, bottom) = self.top_to_bottom();
-            (top.y..bottom.y + 1)
+            (top.y..=bottom.y)
                 .map(|y| Point { x: self.a.x, y })
                 .collect()
         } else {
@@ -36,14 +66,14 @@ impl Line {
         let (top, bottom) = self.top_to_bottom();
         if left == top {
             // top-left to bottom-right
-            (left.x..right.x + 1)
-                .zip(top.y..bottom.y + 1)
+            (left.x..=right.x)
+                .zip(top.y..=bottom.y)
                 .map(Point::from)
                 .collect()
         } else {
             // bottom-left to top-right
-            (left.x..right.x + 1)
-                .zip((top.y..bottom.y + 1).rev())
+            (left.x..=right.x)
+                .zip((top.y..=bottom.y).rev())
                 .map(Point::from)
                 .collect()
         }
@@ -66,31 +96,19 @@ impl Line {
     }
 }
 
-impl FromStr for Line {
-    type Err = ParseIntError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(" -> ");
-        let a = Point::from_str(parts.next().unwrap())?;
-        let b = Point::from_str(parts.next().unwrap())?;
-        Ok(Self { a, b })
-    }
-}
-
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub struct Point {
-    x: i32,
-    y: i32,
+    pub x: i32,
+    pub y: i32,
 }
 
-impl FromStr for Point {
-    type Err = ParseIntError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(",");
-        let x = parts.next().unwrap().parse()?;
-        let y = parts.next().unwrap().parse()?;
-        Ok(Self { x, y })
+impl Point {
+    fn parse(span: Span) -> Result<Self, ParseError> {
+        let (x, y) = span.split_once(",")?;
+        Ok(Self {
+            x: x.parse("an x coordinate")?,
+            y: y.parse("a y coordinate")?,
+        })
     }
 }
 
@@ -123,4 +141,30 @@ mod tests {
         ];
         assert_eq!(expected, line.points_on_line());
     }
+
+    #[test]
+    fn points_at_the_edge() {
+        let max = i32::MAX;
+        let line = Line {
+            a: Point { x: max, y: max },
+            b: Point { x: max - 2, y: max },
+        };
+        assert_eq!(3, line.points_on_line().len());
+        let line = Line {
+            a: Point { x: max, y: max - 1 },
+            b: Point { x: max - 1, y: max },
+        };
+        assert_eq!(
+            vec![Point { x: max - 1, y: max }, Point { x: max, y: max - 1 }],
+            line.points_on_line()
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        let input = helpers::parse::InputFile::from_text("vents.txt", "0,9 -> 5,x");
+        let error = Line::parse(input.first_line().unwrap()).unwrap_err();
+        assert_eq!(10, error.column);
+        assert_eq!("a y coordinate", error.expected);
+    }
 }
@@ -1,61 +1,96 @@
-use std::{collections::HashMap, str::FromStr};
+use day05::Counter;
+use helpers::{is_file_arg, parse_number};
 
-mod line;
-use line::Line;
+const USAGE: &str = "Usage:
+    day05 [FILE]
+    day05 compare [FILE] [--counter <points|sweep>] [--scale <N>] [--iterations <N>]
 
-fn main() {
-    println!("Answer one: {}", part1("input.txt"));
-    println!("Answer two: {}", part2("input.txt"));
-}
+compare counts the dangerous points in FILE (or the cached input) for both parts with
+each way of counting, and shows the answers and the best time of --iterations runs
+(5 by default). points lists every point on every line, sweep only looks at where
+lines overlap and cross. --counter picks just one of them.
 
-fn part1(file_name: &str) -> usize {
-    let lines = parse_lines(file_name).filter(|l| l.is_horizontal() || l.is_vertical());
-
-    count_most_dangerous_points(lines)
-}
+--scale multiplies every coordinate (1 by default), so the lines get longer and
+overlap for longer. They mostly cross in the same places, but an even scale also
+lands diagonals that crossed between two points on a point. It's an error for a
+coordinate to outgrow an i32 once scaled.";
 
-fn part2(file_name: &str) -> usize {
-    let lines = parse_lines(file_name);
-    count_most_dangerous_points(lines)
+struct CompareArgs {
+    file_name: Option<String>,
+    counters: Vec<Counter>,
+    scale: i32,
+    iterations: usize,
 }
 
-fn count_most_dangerous_points<I: Iterator<Item = Line>>(lines: I) -> usize {
-    let all_points = lines.flat_map(|l| l.points_on_line());
-
-    let mut point_counts = HashMap::new();
-    for point in all_points {
-        let count = point_counts.entry(point).or_insert(0);
-        *count += 1;
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.first().map(String::as_str) != Some("compare") {
+        helpers::run_main(&day05::Day05);
+        return;
     }
 
-    point_counts.values().filter(|&c| *c > 1).count()
-}
-
-fn parse_lines(file_name: &str) -> impl Iterator<Item = Line> + '_ {
-    helpers::read_lines_panicky(file_name).map(|l| Line::from_str(&l).unwrap())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
+    let CompareArgs {
+        file_name,
+        counters,
+        scale,
+        iterations,
+    } = match parse_compare_args(&args[1..]) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    };
+    let file_name = file_name.unwrap_or_else(|| helpers::resolve_or_exit(5));
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(5, part1("test_input.txt"));
-    }
-
-    #[test]
-    fn final_part1() {
-        assert_eq!(7318, part1("input.txt"));
+    match day05::compare_from_file(&file_name, &counters, scale, iterations) {
+        Ok(timings) => print!("{}", day05::timing_table(&timings)),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
     }
+}
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(12, part2("test_input.txt"));
-    }
+fn parse_compare_args(args: &[String]) -> Result<CompareArgs, String> {
+    let mut parsed = CompareArgs {
+        file_name: None,
+        counters: Counter::ALL.to_vec(),
+        scale: 1,
+        iterations: 5,
+    };
+    let mut args = args.iter();
 
-    #[test]
-    fn final_part2() {
-        assert_eq!(19939, part2("input.txt"));
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--counter" | "-c" => {
+                let name = value()?;
+                let counter = Counter::ALL.iter().find(|c| c.name() == name);
+                let counter = counter
+                    .ok_or_else(|| format!("Counters are points or sweep, not `{}`", name))?;
+                parsed.counters = vec![*counter];
+            }
+            "--scale" | "-s" => {
+                parsed.scale = parse_number(arg, value()?)?;
+                if parsed.scale < 1 {
+                    return Err(String::from("--scale must be at least 1"));
+                }
+            }
+            "--iterations" | "-n" => {
+                parsed.iterations = parse_number(arg, value()?)?;
+                if parsed.iterations == 0 {
+                    return Err(String::from("--iterations must be at least 1"));
+                }
+            }
+            _ if parsed.file_name.is_none() && is_file_arg(arg) => {
+                parsed.file_name = Some(arg.clone())
+            }
+            _ => return Err(format!("Unexpected argument `{}`", arg)),
+        }
     }
+    Ok(parsed)
 }
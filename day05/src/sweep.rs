@@ -0,0 +1,370 @@
+use crate::line::Line;
+use std::collections::{BTreeSet, HashMap};
+
+/// An inclusive stretch along a line.
+type Stretch = (i64, i64);
+/// Takes a point in a sheared space back to a whole point, if there is one.
+type Unshear = fn(i64, i64) -> Option<(i64, i64)>;
+
+/// Counts the points at least two lines cover without listing the points on the
+/// lines, so the work depends on how many lines there are and how often they cross,
+/// not how long they are.
+///
+/// The lines are split into four families by direction (horizontal, vertical and
+/// the two diagonals). Lines in the same family only meet where they're collinear,
+/// so each family is merged into the stretches covered at least once and the
+/// stretches covered at least twice. Lines from different families meet at single
+/// points, which are found by shearing each pair of families so one runs across
+/// and the other down, then sweeping across them. Lines are assumed to be at 45
+/// degrees if they aren't straight, as the puzzle promises.
+pub fn count_overlaps<'a, I: IntoIterator<Item = &'a Line>>(lines: I) -> usize {
+    let mut families = [
+        Family::default(),
+        Family::default(),
+        Family::default(),
+        Family::default(),
+    ];
+    for line in lines {
+        let (direction, key, start, end) = along(line);
+        families[direction as usize]
+            .lines
+            .entry(key)
+            .or_default()
+            .push((start, end));
+    }
+    let families = families.map(Family::merge);
+
+    let doubled = families
+        .iter()
+        .flat_map(|family| family.doubled.values().flatten())
+        .map(|&(start, end)| (end - start + 1) as usize)
+        .sum::<usize>();
+
+    let mut crossings = Vec::new();
+    for (i, first) in DIRECTIONS.iter().enumerate() {
+        for second in DIRECTIONS[i + 1..].iter() {
+            crossings.extend(crossings_between(&families, *first, *second));
+        }
+    }
+    crossings.sort_unstable();
+    crossings.dedup();
+
+    // a crossing some family covers twice on its own has been counted already
+    let counted_twice = crossings
+        .iter()
+        .map(|&point| {
+            DIRECTIONS
+                .iter()
+                .filter(|&&direction| families[direction as usize].doubles(direction, point))
+                .count()
+        })
+        .sum::<usize>();
+    doubled + crossings.len() - counted_twice
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    /// Keyed by y, along x.
+    Horizontal,
+    /// Keyed by x, along y.
+    Vertical,
+    /// Going down to the right, keyed by x - y, along x.
+    Down,
+    /// Going up to the right, keyed by x + y, along x.
+    Up,
+}
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Horizontal,
+    Direction::Vertical,
+    Direction::Down,
+    Direction::Up,
+];
+
+impl Direction {
+    /// Where a point is in this direction's terms: which line, then how far along.
+    fn locate(self, (x, y): (i64, i64)) -> (i64, i64) {
+        match self {
+            Direction::Horizontal => (y, x),
+            Direction::Vertical => (x, y),
+            Direction::Down => (x - y, x),
+            Direction::Up => (x + y, x),
+        }
+    }
+}
+
+/// A line as its direction, key, and the stretch it covers along that.
+fn along(line: &Line) -> (Direction, i64, i64, i64) {
+    let (a, b) = line.ends();
+    let (a, b) = ((a.x as i64, a.y as i64), (b.x as i64, b.y as i64));
+    let direction = if line.is_horizontal() {
+        Direction::Horizontal
+    } else if line.is_vertical() {
+        Direction::Vertical
+    } else if (b.0 - a.0).signum() == (b.1 - a.1).signum() {
+        Direction::Down
+    } else {
+        Direction::Up
+    };
+    let (key, from) = direction.locate(a);
+    let (_, to) = direction.locate(b);
+    (direction, key, from.min(to), from.max(to))
+}
+
+/// All the lines going one way.
+#[derive(Default)]
+struct Family {
+    /// Each line's stretch, by key.
+    lines: HashMap<i64, Vec<Stretch>>,
+    /// Stretches covered at least twice, by key, sorted and not overlapping.
+    doubled: HashMap<i64, Vec<Stretch>>,
+}
+
+impl Family {
+    /// Merges each key's stretches into the ones covered at all, which replace
+    /// them, and the ones covered at least twice.
+    fn merge(self) -> Self {
+        let mut lines = HashMap::new();
+        let mut doubled = HashMap::new();
+        for (key, stretches) in self.lines {
+            let (once, twice) = coverage(stretches);
+            lines.insert(key, once);
+            if !twice.is_empty() {
+                doubled.insert(key, twice);
+            }
+        }
+        Self { lines, doubled }
+    }
+
+    fn doubles(&self, direction: Direction, point: (i64, i64)) -> bool {
+        let (key, t) = direction.locate(point);
+        self.doubled.get(&key).is_some_and(|stretches| {
+            let i = stretches.partition_point(|&(_, end)| end < t);
+            stretches.get(i).is_some_and(|&(start, _)| start <= t)
+        })
+    }
+}
+
+/// The stretches covered at least once and at least twice by some inclusive ones.
+fn coverage(stretches: Vec<Stretch>) -> (Vec<Stretch>, Vec<Stretch>) {
+    let mut events = stretches
+        .iter()
+        .flat_map(|&(start, end)| [(start, 1), (end + 1, -1)])
+        .collect::<Vec<_>>();
+    events.sort_unstable();
+
+    let (mut once, mut twice) = (Vec::new(), Vec::new());
+    let mut depth = 0;
+    let mut i = 0;
+    while i < events.len() {
+        let at = events[i].0;
+        let before = depth;
+        while i < events.len() && events[i].0 == at {
+            depth += events[i].1;
+            i += 1;
+        }
+        for (threshold, stretches) in [(1, &mut once), (2, &mut twice)] {
+            if before < threshold && depth >= threshold {
+                stretches.push((at, at));
+            } else if before >= threshold && depth < threshold {
+                stretches.last_mut().unwrap().1 = at - 1;
+            }
+        }
+    }
+    (once, twice)
+}
+
+/// Where lines of two families cross. The pair is sheared (or, for the two
+/// diagonals, turned 45 degrees) so the first family runs across and the second
+/// down.
+fn crossings_between(
+    families: &[Family; 4],
+    first: Direction,
+    second: Direction,
+) -> Vec<(i64, i64)> {
+    use Direction::*;
+
+    let stretches = |direction: Direction| {
+        families[direction as usize]
+            .lines
+            .iter()
+            .flat_map(|(&key, stretches)| {
+                stretches.iter().map(move |&(start, end)| (key, start, end))
+            })
+    };
+    // each line as (key, start, end) in the sheared space, and the way back
+    let (across, down, back): (Vec<_>, Vec<_>, Unshear) = match (first, second) {
+        (Horizontal, Vertical) => (
+            stretches(Horizontal).collect(),
+            stretches(Vertical).collect(),
+            |x, y| Some((x, y)),
+        ),
+        // (x - y, y), where the diagonals stand upright
+        (Horizontal, Down) => (
+            stretches(Horizontal)
+                .map(|(y, a, b)| (y, a - y, b - y))
+                .collect(),
+            stretches(Down).map(|(k, a, b)| (k, a - k, b - k)).collect(),
+            |u, y| Some((u + y, y)),
+        ),
+        // (x + y, y)
+        (Horizontal, Up) => (
+            stretches(Horizontal)
+                .map(|(y, a, b)| (y, a + y, b + y))
+                .collect(),
+            stretches(Up).map(|(k, a, b)| (k, k - b, k - a)).collect(),
+            |u, y| Some((u - y, y)),
+        ),
+        // (x, y - x), where the diagonals lie flat
+        (Vertical, Down) => (
+            stretches(Down).map(|(k, a, b)| (-k, a, b)).collect(),
+            stretches(Vertical)
+                .map(|(x, a, b)| (x, a - x, b - x))
+                .collect(),
+            |x, w| Some((x, w + x)),
+        ),
+        // (x, x + y)
+        (Vertical, Up) => (
+            stretches(Up).collect(),
+            stretches(Vertical)
+                .map(|(x, a, b)| (x, a + x, b + x))
+                .collect(),
+            |x, w| Some((x, w - x)),
+        ),
+        // (x - y, x + y), which only has whole points where both are even or odd
+        (Down, Up) => (
+            stretches(Up)
+                .map(|(k, a, b)| (k, 2 * a - k, 2 * b - k))
+                .collect(),
+            stretches(Down)
+                .map(|(k, a, b)| (k, 2 * a - k, 2 * b - k))
+                .collect(),
+            |u, v| ((u + v) % 2 == 0).then_some(((u + v) / 2, (v - u) / 2)),
+        ),
+        _ => unreachable!("directions are paired in order"),
+    };
+
+    orthogonal_crossings(&across, &down)
+        .into_iter()
+        .filter_map(|(x, y)| back(x, y))
+        .collect()
+}
+
+/// Where lines running across, as (y, x from, x to), cross lines running down, as
+/// (x, y from, y to), sweeping from left to right with the lines across that the
+/// sweep is part way along. No two lines across can share a point, so each y is
+/// only in the set once.
+fn orthogonal_crossings(across: &[(i64, i64, i64)], down: &[(i64, i64, i64)]) -> Vec<(i64, i64)> {
+    // at each x, lines across start, then lines down are checked, then lines across end
+    let mut events = Vec::with_capacity(across.len() * 2 + down.len());
+    for &(y, start, end) in across {
+        events.push((start, 0, y, 0));
+        events.push((end, 2, y, 0));
+    }
+    for &(x, start, end) in down {
+        events.push((x, 1, start, end));
+    }
+    events.sort_unstable();
+
+    let mut sweeping = BTreeSet::new();
+    let mut crossings = Vec::new();
+    for (x, kind, a, b) in events {
+        match kind {
+            0 => {
+                sweeping.insert(a);
+            }
+            1 => crossings.extend(sweeping.range(a..=b).map(|&y| (x, y))),
+            _ => {
+                sweeping.remove(&a);
+            }
+        }
+    }
+    crossings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::{parse::InputFile, rng};
+    use std::collections::HashMap;
+
+    fn lines(text: &str) -> Vec<Line> {
+        let input = InputFile::from_text("vents.txt", text);
+        input.lines().map(|l| Line::parse(l).unwrap()).collect()
+    }
+
+    fn by_points(lines: &[Line]) -> usize {
+        let mut counts = HashMap::new();
+        for point in lines.iter().flat_map(Line::points_on_line) {
+            *counts.entry(point).or_insert(0) += 1;
+        }
+        counts.values().filter(|&&c| c > 1).count()
+    }
+
+    #[test]
+    fn test_coverage() {
+        let (once, twice) = coverage(vec![(0, 4), (2, 6), (3, 3), (8, 9), (10, 10)]);
+        assert_eq!(vec![(0, 6), (8, 10)], once);
+        assert_eq!(vec![(2, 4)], twice);
+    }
+
+    #[test]
+    fn test_crossings() {
+        // a star through (5, 5), with every pair of directions crossing there
+        let star = lines("0,5 -> 10,5\n5,0 -> 5,10\n0,0 -> 10,10\n0,10 -> 10,0");
+        assert_eq!(1, count_overlaps(&star));
+        // diagonals that pass between whole points don't cross
+        assert_eq!(0, count_overlaps(&lines("0,0 -> 3,3\n0,3 -> 3,0")));
+        assert_eq!(
+            1,
+            count_overlaps(&lines("0,0 -> 3,3\n0,3 -> 3,0\n1,1 -> 1,1"))
+        );
+    }
+
+    #[test]
+    fn test_collinear() {
+        let text = "0,0 -> 10,0\n5,0 -> 15,0\n8,0 -> 20,0\n3,3 -> 0,0\n1,1 -> 2,2";
+        // 5 to 15 along the top, (1, 1) and (2, 2) on the diagonal, and (0, 0) where
+        // the two meet
+        assert_eq!(11 + 2 + 1, count_overlaps(&lines(text)));
+        // overlapping on a line that also crosses another family's overlap
+        let text = "0,2 -> 4,2\n2,2 -> 6,2\n2,0 -> 2,4\n2,1 -> 2,5";
+        assert_eq!(by_points(&lines(text)), count_overlaps(&lines(text)));
+    }
+
+    #[test]
+    fn test_huge_coordinates() {
+        let max = i32::MAX;
+        let text = format!(
+            "{0},{1} -> {1},{1}\n{0},{1} -> {0},{0}\n0,{1} -> {1},0\n{2},{1} -> {1},{1}",
+            -max,
+            max,
+            max - 10
+        );
+        // the two horizontals overlap for 11 points at the right, the vertical meets
+        // the first horizontal at the left and the diagonal meets it in the middle
+        assert_eq!(11 + 1 + 1, count_overlaps(&lines(&text)));
+    }
+
+    #[test]
+    fn test_random_lines() {
+        rng::check("sweep matches points", 200, |rng| {
+            let size = rng.range(2..=30);
+            let lines = (0..rng.range(1..=25))
+                .map(|_| {
+                    let (x1, y1) = (rng.range(0..=size), rng.range(0..=size));
+                    let length = rng.range(0..=size);
+                    let (x2, y2) = match rng.below(4) {
+                        0 => (x1 + length, y1),
+                        1 => (x1, y1 - length),
+                        2 => (x1 - length, y1 - length),
+                        _ => (x1 + length, y1 - length),
+                    };
+                    format!("{},{} -> {},{}", x1, y1, x2, y2)
+                })
+                .collect::<Vec<_>>();
+            let lines = self::lines(&lines.join("\n"));
+            assert_eq!(by_points(&lines), count_overlaps(&lines));
+        });
+    }
+}
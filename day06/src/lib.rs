@@ -0,0 +1,129 @@
+use helpers::{parse::InputFile, ParseError, Solution};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(file_name: &str) -> Result<Vec<usize>, ParseError> {
+        get_all_fish(file_name)
+    }
+
+    fn part1(all_fish: &Vec<usize>) -> usize {
+        both_parts(all_fish, 80)
+    }
+
+    fn part2(all_fish: &Vec<usize>) -> usize {
+        both_parts(all_fish, 256)
+    }
+}
+
+fn both_parts(all_fish: &[usize], days: usize) -> usize {
+    let mut population = Population::default();
+    for &fish in all_fish {
+        population.add(fish);
+    }
+
+    for _ in 0..days {
+        population.simulate_day();
+    }
+
+    population.total()
+}
+
+fn get_all_fish(file_name: &str) -> Result<Vec<usize>, ParseError> {
+    let input = InputFile::open(file_name)?;
+    let line = input.first_line()?;
+    line.split(',')
+        .map(|f| match f.trim().parse("a timer between 0 and 8")? {
+            fish if fish <= 8 => Ok(fish),
+            _ => Err(f.error("a timer between 0 and 8")),
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct Population {
+    buckets: [usize; 9],
+}
+
+impl Population {
+    fn add(&mut self, fish: usize) {
+        assert!(fish <= 8);
+        self.buckets[fish] += 1;
+    }
+
+    fn simulate_day(&mut self) {
+        let spawning_fish = self.buckets[0];
+        for i in 1..9 {
+            self.buckets[i - 1] = self.buckets[i];
+        }
+        self.buckets[6] += spawning_fish; // adults ready to spawn again in a week
+        self.buckets[8] = spawning_fish; // newly spawned fish
+    }
+
+    fn total(&self) -> usize {
+        self.buckets.iter().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng::check;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(5934, Day06::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(350605, Day06::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(26984457539, Day06::solve_part2("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(1592778185024, Day06::solve_part2("input.txt"));
+    }
+
+    #[test]
+    fn test_random_schools() {
+        check("day06 random schools", 100, |rng| {
+            let mut school = (0..1 + rng.index(10))
+                .map(|_| rng.index(9))
+                .collect::<Vec<_>>();
+            let text = school
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            let days = rng.index(60);
+            let counted = both_parts(&school, days);
+
+            // every fish one at a time, as the puzzle tells it
+            for _ in 0..days {
+                let mut born = 0;
+                for fish in school.iter_mut() {
+                    if *fish == 0 {
+                        *fish = 6;
+                        born += 1;
+                    } else {
+                        *fish -= 1;
+                    }
+                }
+                school.extend(std::iter::repeat_n(8, born));
+            }
+            assert_eq!(school.len(), counted, "{} after {} days", text, days);
+        });
+    }
+}
@@ -0,0 +1,117 @@
+use helpers::{
+    parallel,
+    parse::{self, InputFile},
+    ParseError, Solution,
+};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<i32>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(file_name: &str) -> Result<Vec<i32>, ParseError> {
+        parse_crabs(file_name)
+    }
+
+    fn part1(crabs: &Vec<i32>) -> i64 {
+        get_crab_align_cost(crabs, |c| c)
+    }
+
+    fn part2(crabs: &Vec<i32>) -> i64 {
+        get_crab_align_cost(crabs, gauss_sum)
+    }
+}
+
+fn parse_crabs(file_name: &str) -> Result<Vec<i32>, ParseError> {
+    let input = InputFile::open(file_name)?;
+    parse::comma_separated(input.first_line()?, "a crab position")
+}
+
+fn get_crab_align_cost<F: Fn(i64) -> i64 + Sync>(crabs: &[i32], cost_fn: F) -> i64 {
+    let &min = crabs.iter().min().unwrap();
+    let &max = crabs.iter().max().unwrap();
+
+    let positions = (min..=max).collect::<Vec<_>>();
+    let costs = parallel::map(&positions, |&position| {
+        total_cost(crabs, position, &cost_fn)
+    });
+
+    costs.into_iter().min().unwrap()
+}
+
+/// Summed in 64 bits, since the fuel for far off positions adds up fast.
+fn total_cost<F: Fn(i64) -> i64>(crabs: &[i32], position: i32, cost_fn: &F) -> i64 {
+    crabs
+        .iter()
+        .map(|&c| {
+            let distance = (i64::from(c) - i64::from(position)).abs();
+            cost_fn(distance)
+        })
+        .sum()
+}
+
+fn gauss_sum(n: i64) -> i64 {
+    n * (n + 1) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::parallel::with_threads;
+    use helpers::rng::check;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(37, Day07::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(347449, Day07::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(168, Day07::solve_part2("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(98039527, Day07::solve_part2("input.txt"));
+    }
+
+    #[test]
+    fn test_threads() {
+        let crabs = Day07::parse("input.txt").unwrap();
+        let serial = with_threads(1, || (Day07::part1(&crabs), Day07::part2(&crabs)));
+        let parallel = with_threads(4, || (Day07::part1(&crabs), Day07::part2(&crabs)));
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn test_random_crabs() {
+        check("day07 random crabs", 100, |rng| {
+            let mut crabs = (0..1 + rng.index(30))
+                .map(|_| rng.range(-50..=50) as i32)
+                .collect::<Vec<_>>();
+            let linear = Day07::part1(&crabs);
+            let triangular = Day07::part2(&crabs);
+
+            // moving one step at a time is cheapest at the median, and moving further
+            // each step cheapest within half a step of the mean
+            crabs.sort_unstable();
+            let median = crabs[(crabs.len() - 1) / 2];
+            assert_eq!(total_cost(&crabs, median, &|c| c), linear, "{:?}", crabs);
+            let sum = crabs.iter().map(|&c| i64::from(c)).sum::<i64>();
+            let mean = sum.div_euclid(crabs.len() as i64) as i32;
+            let near_mean = (mean..=mean + 1)
+                .map(|position| total_cost(&crabs, position, &gauss_sum))
+                .min();
+            assert_eq!(Some(triangular), near_mean, "{:?}", crabs);
+        });
+    }
+}
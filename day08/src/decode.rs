@@ -22,15 +22,15 @@ impl Decoder {
     pub fn build(inputs: Vec<String>) -> Self {
         let mut mappings = HashMap::new();
         let mut inputs: Vec<Vec<char>> = inputs.into_iter().map(|i| i.chars().collect()).collect();
-        inputs.sort_by(|x, y| x.len().cmp(&y.len()));
+        inputs.sort_by_key(|x| x.len());
 
         let one = set(&inputs[0]);
         let seven = set(&inputs[1]);
         let four = set(&inputs[2]);
         let eight = set(&inputs[9]);
 
-        let two_three_five = vec![set(&inputs[3]), set(&inputs[4]), set(&inputs[5])];
-        let zero_six_nine = vec![set(&inputs[6]), set(&inputs[7]), set(&inputs[8])];
+        let two_three_five = [set(&inputs[3]), set(&inputs[4]), set(&inputs[5])];
+        let zero_six_nine = [set(&inputs[6]), set(&inputs[7]), set(&inputs[8])];
 
         // Top segment is in 7, but not in 1
         let &seg_a = seven.difference(&one).next().unwrap();
@@ -40,8 +40,7 @@ impl Decoder {
         let combined = || two_three_five.iter().chain(zero_six_nine.iter());
         let &seg_g = eight
             .iter()
-            .filter(|&c| c != &seg_a && combined().filter(|d| d.contains(c)).count() == 6)
-            .next()
+            .find(|&c| c != &seg_a && combined().filter(|d| d.contains(c)).count() == 6)
             .unwrap();
         mappings.insert(seg_g, 'g');
 
@@ -58,8 +57,7 @@ impl Decoder {
         // Segment D is the only one (other than C) that is from 4 but only two of 0+6+9
         let &seg_d = four
             .difference(&set(&[seg_c]))
-            .filter(|&c| zero_six_nine.iter().filter(|d| d.contains(c)).count() == 2)
-            .next()
+            .find(|&c| zero_six_nine.iter().filter(|d| d.contains(c)).count() == 2)
             .unwrap();
         mappings.insert(seg_d, 'd');
 
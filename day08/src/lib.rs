@@ -0,0 +1,159 @@
+mod decode;
+use decode::Decoder;
+use helpers::{
+    parse::{InputFile, Span},
+    ParseError, Solution,
+};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input = Vec<Entry>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(file_name: &str) -> Result<Vec<Entry>, ParseError> {
+        let input = InputFile::open(file_name)?;
+        let entries = input.lines().map(Entry::parse);
+        entries.collect()
+    }
+
+    fn part1(entries: &Vec<Entry>) -> usize {
+        entries
+            .iter()
+            .map(|entry| {
+                entry
+                    .readings
+                    .iter()
+                    .filter(|s| matches!(s.len(), 2 | 3 | 4 | 7))
+                    .count()
+            })
+            .sum()
+    }
+
+    fn part2(entries: &Vec<Entry>) -> usize {
+        let mut num = 0;
+        for entry in entries {
+            let decoder = Decoder::build(entry.inputs.clone());
+            for (i, reading) in entry.readings.iter().take(4).enumerate() {
+                let decoded = decoder.decode(reading);
+                let factor = 1000 / 10usize.pow(i as u32);
+                num += decoded * factor;
+            }
+        }
+
+        num
+    }
+}
+
+pub struct Entry {
+    inputs: Vec<String>,
+    readings: Vec<String>,
+}
+
+impl Entry {
+    fn parse(line: Span) -> Result<Self, ParseError> {
+        let (inputs, readings) = line.split_once("|")?;
+        Ok(Self {
+            inputs: parse_patterns(inputs, 10)?,
+            readings: parse_patterns(readings, 4)?,
+        })
+    }
+}
+
+fn parse_patterns(span: Span, count: usize) -> Result<Vec<String>, ParseError> {
+    let patterns = span
+        .split_whitespace()
+        .map(
+            |pattern| match pattern.chars().find(|(c, _)| !('a'..='g').contains(c)) {
+                Some((_, bad)) => Err(bad.error("a segment between `a` and `g`")),
+                None => Ok(pattern.as_str().to_string()),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if patterns.len() != count {
+        return Err(span.trim().error(&format!("{} segment patterns", count)));
+    }
+    Ok(patterns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng::{check, Rng};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(26, Day08::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(237, Day08::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(61229, Day08::solve_part2("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(1009098, Day08::solve_part2("input.txt"));
+    }
+
+    const DIGITS: [&str; 10] = [
+        "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+    ];
+
+    /// A digit lit up on a display whose segments are wired to `wires`, with the
+    /// segments in any order.
+    fn scrambled(rng: &mut Rng, digit: usize, wires: &[char]) -> String {
+        let mut segments = DIGITS[digit]
+            .bytes()
+            .map(|segment| wires[(segment - b'a') as usize])
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut segments);
+        segments.into_iter().collect()
+    }
+
+    #[test]
+    fn test_random_wiring() {
+        check("day08 random wiring", 100, |rng| {
+            let mut lines = Vec::new();
+            let (mut easy, mut total) = (0, 0);
+            for _ in 0..1 + rng.index(5) {
+                let mut wires = "abcdefg".chars().collect::<Vec<_>>();
+                rng.shuffle(&mut wires);
+                let mut order = (0..10).collect::<Vec<_>>();
+                rng.shuffle(&mut order);
+                let inputs = order
+                    .iter()
+                    .map(|&digit| scrambled(rng, digit, &wires))
+                    .collect::<Vec<_>>();
+                let shown = (0..4).map(|_| rng.index(10)).collect::<Vec<_>>();
+                let readings = shown
+                    .iter()
+                    .map(|&digit| scrambled(rng, digit, &wires))
+                    .collect::<Vec<_>>();
+                lines.push(format!("{} | {}", inputs.join(" "), readings.join(" ")));
+
+                easy += shown.iter().filter(|d| matches!(d, 1 | 4 | 7 | 8)).count();
+                total += shown.iter().fold(0, |number, digit| number * 10 + digit);
+            }
+            let text = lines.join("\n");
+
+            let input = InputFile::from_text("random", &text);
+            let entries = input
+                .lines()
+                .map(Entry::parse)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap_or_else(|e| panic!("\n{}", e));
+            assert_eq!(easy, Day08::part1(&entries), "\n{}", text);
+            assert_eq!(total, Day08::part2(&entries), "\n{}", text);
+        });
+    }
+}
@@ -0,0 +1,153 @@
+use helpers::{
+    grid::{Grid, Point},
+    parse::{self, InputFile},
+    ParseError, Solution,
+};
+use std::collections::HashSet;
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = Grid<u8>;
+    type Part1 = u16;
+    type Part2 = usize;
+
+    fn parse(file_name: &str) -> Result<Grid<u8>, ParseError> {
+        let input = InputFile::open(file_name)?;
+        input.first_line()?;
+        parse::digit_grid(input.lines())
+    }
+
+    fn part1(heights: &Grid<u8>) -> u16 {
+        low_points(heights)
+            .into_iter()
+            .map(|point| (heights[point] + 1) as u16)
+            .sum()
+    }
+
+    fn part2(heights: &Grid<u8>) -> usize {
+        let mut basins: Vec<HashSet<Point>> = Vec::new();
+        for low_point in low_points(heights) {
+            let mut basin = HashSet::new();
+            fill_basin_recursive(heights, low_point, &mut basin);
+            basins.push(basin);
+        }
+
+        basins.sort_by_key(|b| std::cmp::Reverse(b.len()));
+        basins.into_iter().take(3).map(|b| b.len()).product()
+    }
+}
+
+fn fill_basin_recursive(heights: &Grid<u8>, point: Point, basin: &mut HashSet<Point>) {
+    if basin.insert(point) {
+        for neighbor in heights.neighbors4(point).filter(|&p| heights[p] < 9) {
+            fill_basin_recursive(heights, neighbor, basin)
+        }
+    }
+}
+
+fn low_points(heights: &Grid<u8>) -> Vec<Point> {
+    heights
+        .cells()
+        .filter(|&(point, &height)| heights.neighbors4(point).all(|n| height < heights[n]))
+        .map(|(point, _)| point)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng::check;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(15, Day09::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(462, Day09::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(1134, Day09::solve_part2("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(1397760, Day09::solve_part2("input.txt"));
+    }
+
+    /// Both parts on rows of heights, stepping through the neighbours by hand and
+    /// filling each basin from its low point with a stack.
+    fn naive(rows: &[Vec<u8>]) -> (u16, usize) {
+        let (width, height) = (rows[0].len(), rows.len());
+        let neighbours = |x: usize, y: usize| {
+            let mut around = Vec::new();
+            if x > 0 {
+                around.push((x - 1, y));
+            }
+            if x + 1 < width {
+                around.push((x + 1, y));
+            }
+            if y > 0 {
+                around.push((x, y - 1));
+            }
+            if y + 1 < height {
+                around.push((x, y + 1));
+            }
+            around
+        };
+
+        let mut risk = 0;
+        let mut sizes = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let here = rows[y][x];
+                if !neighbours(x, y).iter().all(|&(nx, ny)| here < rows[ny][nx]) {
+                    continue;
+                }
+                risk += u16::from(here) + 1;
+
+                let mut seen = vec![vec![false; width]; height];
+                let mut stack = vec![(x, y)];
+                let mut size = 0;
+                while let Some((x, y)) = stack.pop() {
+                    if seen[y][x] || rows[y][x] == 9 {
+                        continue;
+                    }
+                    seen[y][x] = true;
+                    size += 1;
+                    stack.extend(neighbours(x, y));
+                }
+                sizes.push(size);
+            }
+        }
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        (risk, sizes.iter().take(3).product())
+    }
+
+    #[test]
+    fn test_random_heights() {
+        check("day09 random heights", 100, |rng| {
+            let (width, height) = (1 + rng.index(12), 1 + rng.index(12));
+            let rows = (0..height)
+                .map(|_| (0..width).map(|_| rng.index(10) as u8).collect())
+                .collect::<Vec<Vec<u8>>>();
+            let text = rows
+                .iter()
+                .map(|row| row.iter().map(u8::to_string).collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let input = InputFile::from_text("random", &text);
+            let heights = parse::digit_grid(input.lines()).unwrap();
+            let (risk, basins) = naive(&rows);
+            assert_eq!(risk, Day09::part1(&heights), "\n{}", text);
+            assert_eq!(basins, Day09::part2(&heights), "\n{}", text);
+        });
+    }
+}
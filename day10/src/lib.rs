@@ -0,0 +1,315 @@
+use helpers::{
+    parse::{InputFile, Span},
+    stream::LineReader,
+    ParseError, Solution,
+};
+use std::{fmt, io::BufRead};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<String>;
+    type Part1 = u32;
+    type Part2 = Completion;
+
+    fn parse(file_name: &str) -> Result<Vec<String>, ParseError> {
+        let input = InputFile::open(file_name)?;
+        input
+            .lines()
+            .map(|line| Ok(check_chunks(line)?.to_string()))
+            .collect()
+    }
+
+    fn part1(lines: &Vec<String>) -> u32 {
+        lines.iter().map(|l| get_illegal_closing_score(l)).sum()
+    }
+
+    fn part2(lines: &Vec<String>) -> Completion {
+        let scores = lines
+            .iter()
+            .filter(|l| get_illegal_closing_score(l) == 0)
+            .map(|l| get_completion_string_score(l))
+            .collect::<Vec<_>>();
+
+        middle_score(scores)
+    }
+
+    fn stream(file_name: &str) -> Option<Result<(u32, Completion), ParseError>> {
+        Some(LineReader::open(file_name).and_then(solve_stream))
+    }
+}
+
+/// Part 2's answer, which needs a line that's incomplete rather than corrupted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Completion {
+    /// The middle score of the incomplete lines' completions.
+    Middle(usize),
+    /// Every line is corrupted or complete, so there's nothing to complete.
+    NoneIncomplete,
+}
+
+impl fmt::Display for Completion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Completion::Middle(score) => write!(f, "{}", score),
+            Completion::NoneIncomplete => write!(f, "none, no line is incomplete"),
+        }
+    }
+}
+
+/// Both parts in one pass. Only the scores of incomplete lines are kept, since part 2
+/// needs the middle one.
+pub fn solve_stream<R: BufRead>(lines: LineReader<R>) -> Result<(u32, Completion), ParseError> {
+    let mut illegal = 0;
+    let mut completions = Vec::new();
+    let scores = lines.parse_lines(|line| {
+        let line = check_chunks(line)?;
+        Ok(match get_illegal_closing_score(line) {
+            0 => (0, Some(get_completion_string_score(line))),
+            score => (score, None),
+        })
+    });
+    for score in scores {
+        let (score, completion) = score?;
+        illegal += score;
+        completions.extend(completion);
+    }
+    Ok((illegal, middle_score(completions)))
+}
+
+fn check_chunks(line: Span<'_>) -> Result<&str, ParseError> {
+    match line.chars().find(|(c, _)| !"()[]{}<>".contains(*c)) {
+        Some((_, bad)) => Err(bad.error("one of `()[]{}<>`")),
+        None => Ok(line.as_str()),
+    }
+}
+
+/// The middle of the completion scores, leaving out the lines that are already
+/// complete, which score 0.
+fn middle_score(mut scores: Vec<usize>) -> Completion {
+    scores.retain(|&score| score > 0);
+    // the prompt promises that there will be an odd number of lines, so this will pick the middle score.
+    scores.sort();
+    match scores.get(scores.len() / 2) {
+        Some(&score) => Completion::Middle(score),
+        None => Completion::NoneIncomplete,
+    }
+}
+
+fn get_completion_string_score(line: &str) -> usize {
+    let mut opening_tags = Vec::new();
+    for tag in line.chars().map(Tag::from) {
+        match tag {
+            Tag::Open(_) => opening_tags.push(tag),
+            Tag::Close(_) => {
+                opening_tags.pop().unwrap();
+            }
+        }
+    }
+
+    opening_tags
+        .iter()
+        .rev()
+        .map(Tag::get_matching_close)
+        .map(completion_char_score)
+        .fold(0, |total, score| total * 5 + score)
+}
+
+fn completion_char_score(tag: Tag) -> usize {
+    let close = tag.into_inner();
+    [')', ']', '}', '>']
+        .iter()
+        .position(|&c| c == close)
+        .unwrap()
+        + 1
+}
+
+fn get_illegal_closing_score(line: &str) -> u32 {
+    let mut stack = Vec::new();
+    for tag in line.chars().map(Tag::from) {
+        match tag {
+            Tag::Open(_) => stack.push(tag),
+            Tag::Close(_) => {
+                if !stack.pop().unwrap().matches(&tag) {
+                    return tag.illegal_close_score();
+                }
+            }
+        }
+    }
+
+    0
+}
+
+#[derive(PartialEq, Eq)]
+enum Tag {
+    Open(char),
+    Close(char),
+}
+
+impl Tag {
+    fn matches(&self, other: &Tag) -> bool {
+        match (self, other) {
+            (Tag::Open(_), Tag::Close(_)) => other == &self.get_matching_close(),
+            (Tag::Close(_), Tag::Open(_)) => self == &other.get_matching_close(),
+            _ => false,
+        }
+    }
+
+    fn get_matching_close(&self) -> Tag {
+        match self {
+            Tag::Close(_) => panic!("Don't do that!"),
+            Tag::Open(open) => match *open {
+                '(' => ')'.into(),
+                '[' => ']'.into(),
+                '{' => '}'.into(),
+                '<' => '>'.into(),
+                _ => panic!("Unrecognized open char"),
+            },
+        }
+    }
+
+    fn illegal_close_score(&self) -> u32 {
+        match self {
+            Tag::Open(_) => 0,
+            Tag::Close(close) => match *close {
+                ')' => 3,
+                ']' => 57,
+                '}' => 1197,
+                '>' => 25137,
+                _ => 0,
+            },
+        }
+    }
+
+    fn into_inner(self) -> char {
+        match self {
+            Tag::Open(c) => c,
+            Tag::Close(c) => c,
+        }
+    }
+}
+
+impl From<char> for Tag {
+    fn from(c: char) -> Self {
+        match c {
+            '(' | '[' | '{' | '<' => Tag::Open(c),
+            ')' | ']' | '}' | '>' => Tag::Close(c),
+            _ => panic!("Unrecognized char"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng::{check, Rng};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(26397, Day10::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(290691, Day10::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            Completion::Middle(288957),
+            Day10::solve_part2("test_input.txt")
+        );
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(
+            Completion::Middle(2768166558),
+            Day10::solve_part2("input.txt")
+        );
+    }
+
+    #[test]
+    fn test_stream() {
+        let lines = LineReader::open("input.txt").unwrap();
+        let answers = (290691, Completion::Middle(2768166558));
+        assert_eq!(Ok(answers), solve_stream(lines));
+    }
+
+    #[test]
+    fn test_none_incomplete() {
+        let lines = LineReader::new("test", "()\n{(]\n".as_bytes());
+        let answers = solve_stream(lines).unwrap();
+        assert_eq!((57, Completion::NoneIncomplete), answers);
+        assert_eq!("none, no line is incomplete", answers.1.to_string());
+        assert_eq!(Completion::NoneIncomplete, middle_score(Vec::new()));
+    }
+
+    const PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+    /// A random line of chunks with its score for part 1, or for part 2 if it isn't
+    /// corrupted.
+    fn random_line(rng: &mut Rng) -> (String, u32, usize) {
+        let mut line = String::new();
+        let mut open: Vec<usize> = Vec::new();
+        for _ in 0..rng.index(30) {
+            match open.last() {
+                Some(&pair) if rng.chance(0.4) => {
+                    let (_, close) = PAIRS[pair];
+                    if rng.chance(0.1) {
+                        let wrong = (pair + 1 + rng.index(3)) % 4;
+                        line.push(PAIRS[wrong].1);
+                        let score = [3, 57, 1197, 25137][wrong];
+                        return (line, score, 0);
+                    }
+                    line.push(close);
+                    open.pop();
+                }
+                _ => {
+                    let pair = rng.index(4);
+                    line.push(PAIRS[pair].0);
+                    open.push(pair);
+                }
+            }
+        }
+        let completion = open
+            .iter()
+            .rev()
+            .fold(0, |total, pair| total * 5 + pair + 1);
+        (line, 0, completion)
+    }
+
+    #[test]
+    fn test_random_chunks() {
+        check("day10 random chunks", 100, |rng| {
+            let lines = (0..1 + rng.index(20))
+                .map(|_| random_line(rng))
+                .collect::<Vec<_>>();
+            let text = lines
+                .iter()
+                .map(|(line, _, _)| line.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let illegal = lines.iter().map(|&(_, score, _)| score).sum::<u32>();
+            let mut completions = lines
+                .iter()
+                .map(|&(_, _, completion)| completion)
+                .filter(|&completion| completion > 0)
+                .collect::<Vec<_>>();
+            completions.sort_unstable();
+            let middle = match completions.get(completions.len() / 2) {
+                Some(&score) => Completion::Middle(score),
+                None => Completion::NoneIncomplete,
+            };
+
+            let strings = lines.iter().map(|(line, _, _)| line.clone()).collect();
+            assert_eq!(illegal, Day10::part1(&strings), "\n{}", text);
+            assert_eq!(middle, Day10::part2(&strings), "\n{}", text);
+            let streamed = solve_stream(LineReader::new("random", text.as_bytes()));
+            assert_eq!(Ok((illegal, middle)), streamed, "\n{}", text);
+        });
+    }
+}
@@ -0,0 +1,209 @@
+use helpers::{
+    grid::Grid,
+    parse::{self, InputFile},
+    trace::{self, Event},
+    ParseError, Solution,
+};
+use std::collections::{HashSet, VecDeque};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Grid<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(file_name: &str) -> Result<Grid<u8>, ParseError> {
+        let input = InputFile::open(file_name)?;
+        input.first_line()?;
+        parse::digit_grid(input.lines())
+    }
+
+    fn part1(octopuses: &Grid<u8>) -> usize {
+        let mut octopuses = octopuses.clone();
+        trace_step(0, 0, &octopuses);
+
+        let mut flash_count = 0;
+
+        for i in 1..=100 {
+            let flashes = step(&mut octopuses);
+            flash_count += flashes;
+            trace_step(i, flashes, &octopuses);
+        }
+
+        flash_count
+    }
+
+    fn part2(octopuses: &Grid<u8>) -> usize {
+        first_synchronised_step(octopuses).unwrap_or_else(|| panic!("Too many loops!"))
+    }
+}
+
+/// The most steps part 2 waits for the octopuses to flash together.
+pub const MAX_STEPS: usize = 500;
+
+/// The first step on which every octopus flashes at once, if there is one within
+/// `MAX_STEPS`.
+pub fn first_synchronised_step(octopuses: &Grid<u8>) -> Option<usize> {
+    let mut octopuses = octopuses.clone();
+    trace_step(0, 0, &octopuses);
+
+    for i in 1..=MAX_STEPS {
+        let flashes = step(&mut octopuses);
+        trace_step(i, flashes, &octopuses);
+        if flashes == octopuses.len() {
+            return Some(i);
+        }
+    }
+    None
+}
+
+fn trace_step(step: usize, flashes: usize, octopuses: &Grid<u8>) {
+    trace::emit(|| {
+        Event::new("step")
+            .step(step)
+            .counter("flashes", flashes as i64)
+            .snapshot(octopuses)
+            // octopuses that just flashed are at 0, so light those up
+            .frame(octopuses.map(|&energy| if energy == 0 { 255 } else { energy * 20 }))
+    });
+}
+
+/// Runs a single step of the simulation, returning how many octopuses flashed.
+fn step(octopuses: &mut Grid<u8>) -> usize {
+    let mut flash_queue = VecDeque::new();
+    let mut flashes = HashSet::new();
+
+    for (point, energy) in octopuses.cells_mut() {
+        *energy += 1;
+        if *energy > 9 {
+            flash_queue.push_back(point);
+        }
+    }
+
+    while let Some(point) = flash_queue.pop_front() {
+        if flashes.insert(point) {
+            for neighbor in octopuses.neighbors8(point) {
+                let energy = &mut octopuses[neighbor];
+                if *energy < 10 {
+                    *energy += 1;
+                }
+                if *energy == 10 {
+                    flash_queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+
+    for energy in octopuses.iter_mut() {
+        if *energy > 9 {
+            *energy = 0;
+        }
+    }
+
+    flashes.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng::check;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(1656, Day11::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(1700, Day11::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(195, Day11::solve_part2("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(273, Day11::solve_part2("input.txt"));
+    }
+
+    /// A step as the puzzle tells it, on rows of energies: everything goes up by one,
+    /// then flashes are looked for over and over until no new octopus flashes.
+    fn naive_step(rows: &mut [Vec<u8>]) -> usize {
+        let (width, height) = (rows[0].len() as isize, rows.len() as isize);
+        rows.iter_mut().flatten().for_each(|energy| *energy += 1);
+        let mut flashed = vec![vec![false; width as usize]; height as usize];
+        loop {
+            let mut any = false;
+            for y in 0..height {
+                for x in 0..width {
+                    let (ux, uy) = (x as usize, y as usize);
+                    if rows[uy][ux] <= 9 || flashed[uy][ux] {
+                        continue;
+                    }
+                    flashed[uy][ux] = true;
+                    any = true;
+                    for (nx, ny) in (-1..=1).flat_map(|dx| (-1..=1).map(move |dy| (x + dx, y + dy)))
+                    {
+                        if (0..width).contains(&nx) && (0..height).contains(&ny) {
+                            rows[ny as usize][nx as usize] += 1;
+                        }
+                    }
+                }
+            }
+            if !any {
+                break;
+            }
+        }
+        rows.iter_mut()
+            .flatten()
+            .filter(|e| **e > 9)
+            .for_each(|e| *e = 0);
+        flashed.iter().flatten().filter(|&&f| f).count()
+    }
+
+    #[test]
+    fn test_random_octopuses() {
+        check("day11 random octopuses", 50, |rng| {
+            let (width, height) = (1 + rng.index(8), 1 + rng.index(8));
+            // some mostly 9s, which tend to flash together in time, and some not
+            let nines = rng.f64();
+            let mut rows = (0..height)
+                .map(|_| {
+                    let mut energy = || match rng.chance(nines) {
+                        true => 9,
+                        false => rng.index(10) as u8,
+                    };
+                    (0..width).map(|_| energy()).collect()
+                })
+                .collect::<Vec<Vec<u8>>>();
+            let octopuses = Grid::from_rows(rows.clone());
+
+            let mut flashes = 0;
+            let mut synchronised = None;
+            for i in 1..=MAX_STEPS {
+                let flashed = naive_step(&mut rows);
+                if i <= 100 {
+                    flashes += flashed;
+                }
+                if flashed == width * height {
+                    synchronised = synchronised.or(Some(i));
+                }
+                if i >= 100 && synchronised.is_some() {
+                    break;
+                }
+            }
+            assert_eq!(flashes, Day11::part1(&octopuses), "\n{}", octopuses);
+            assert_eq!(
+                synchronised,
+                first_synchronised_step(&octopuses),
+                "\n{}",
+                octopuses
+            );
+        });
+    }
+}
@@ -0,0 +1,326 @@
+use helpers::{
+    parse::{InputFile, Span},
+    ParseError, Solution,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    iter::FromIterator,
+};
+
+/// A tunnel between two caves.
+type Edge = (String, String);
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Vec<Edge>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(file_name: &str) -> Result<Vec<Edge>, ParseError> {
+        parse_edges(&InputFile::open(file_name)?)
+    }
+
+    fn part1(edges: &Vec<Edge>) -> usize {
+        part1(edges)
+    }
+
+    fn part2(edges: &Vec<Edge>) -> usize {
+        part2(edges)
+    }
+}
+
+/// One `start-A` style tunnel per line.
+fn parse_edges(file: &InputFile) -> Result<Vec<Edge>, ParseError> {
+    let edges = file
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (a, b) = line.trim().split_once("-")?;
+            Ok((parse_cave(a)?, parse_cave(b)?))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let ends = |name: &str| edges.iter().any(|(a, b)| a == name || b == name);
+    if !ends("start") || !ends("end") {
+        return Err(file.error(
+            "tunnels from `start` and to `end`",
+            "a cave system without them",
+        ));
+    }
+    Ok(edges)
+}
+
+fn parse_cave(span: Span) -> Result<String, ParseError> {
+    let name = span.as_str();
+    let same_case = name.chars().all(|c| c.is_ascii_lowercase())
+        || name.chars().all(|c| c.is_ascii_uppercase());
+    if name.is_empty() || !same_case {
+        return Err(span.error("a cave name in all lower or all upper case"));
+    }
+    Ok(name.to_string())
+}
+
+fn part1(edges: &[Edge]) -> usize {
+    let caves = parse_caves_graph(edges);
+
+    let mut path_count = 0;
+    let mut queue = caves["start"]
+        .iter()
+        .map(|&c| vec!["start", c])
+        .collect::<VecDeque<_>>();
+
+    while let Some(path) = queue.pop_front() {
+        let &cave = path.iter().last().unwrap();
+        if cave == "end" {
+            path_count += 1;
+            continue;
+        }
+
+        if is_lowercase(cave) && path.iter().filter(|&&p| p == cave).count() > 1 {
+            continue;
+        }
+
+        for &next in caves[cave].iter() {
+            let mut path = clone_vec(&path);
+            path.push(next);
+            queue.push_back(path);
+        }
+    }
+
+    path_count
+}
+
+pub fn non_recursive(edges: &[Edge]) -> usize {
+    let caves = parse_caves_graph(edges);
+
+    let mut path_count = 0;
+
+    let first_caves = caves["start"].iter().map(|&cave| (1, cave, false));
+    let mut stack = Vec::from_iter(first_caves);
+    let mut current_path = vec!["start"];
+    while let Some((depth, cave, mut seen_twice)) = stack.pop() {
+        if current_path.len() != depth {
+            current_path.truncate(depth);
+        }
+
+        if cave == "end" {
+            path_count += 1;
+            continue;
+        }
+
+        if is_lowercase(cave) && current_path.contains(&cave) {
+            if seen_twice {
+                continue;
+            }
+            seen_twice = true;
+        }
+
+        let next_caves = &caves[cave];
+        if !next_caves.is_empty() {
+            current_path.push(cave);
+            for &next in caves[cave].iter() {
+                stack.push((depth + 1, next, seen_twice));
+            }
+        }
+    }
+
+    path_count
+}
+
+fn part2(edges: &[Edge]) -> usize {
+    let caves = parse_caves_graph(edges);
+
+    let mut parts = Vec::new();
+    count_paths_recursive("start", false, &mut parts, &caves)
+}
+
+fn count_paths_recursive<'a>(
+    current: &'a str,
+    mut seen_twice: bool,
+    parts: &mut Vec<&'a str>,
+    caves: &HashMap<&str, Vec<&'a str>>,
+) -> usize {
+    // inspiration from (read: basically stolen from) my AoC Rust hero: AxlLind
+    // https://github.com/AxlLind/AdventOfCode2021/blob/main/src/bin/12.rs
+
+    if current == "end" {
+        return 1;
+    }
+
+    if is_lowercase(current) && parts.contains(&current) {
+        if seen_twice {
+            return 0;
+        }
+        seen_twice = true;
+    }
+
+    parts.push(current);
+    let count = caves[current]
+        .iter()
+        .map(|&cave| count_paths_recursive(cave, seen_twice, parts, caves))
+        .sum();
+
+    parts.pop();
+    count
+}
+
+fn parse_caves_graph(edges: &[Edge]) -> HashMap<&str, Vec<&str>> {
+    let mut caves: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (a, b) in edges.iter().map(|(a, b)| (a.as_str(), b.as_str())) {
+        // a cave whose only tunnel is from `start` is a dead end, but it's still a cave
+        caves.entry(a).or_default();
+        caves.entry(b).or_default();
+        if a != "end" && b != "start" {
+            let cave = caves.entry(a).or_default();
+            cave.push(b);
+        }
+        if a != "start" && b != "end" {
+            let cave = caves.entry(b).or_default();
+            cave.push(a);
+        }
+    }
+    caves
+}
+
+fn is_lowercase(s: &str) -> bool {
+    s.chars().all(|c| c.is_lowercase())
+}
+
+fn clone_vec<T>(vec: &[T]) -> Vec<T>
+where
+    T: Clone,
+{
+    vec.to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng::{check, Rng};
+
+    /// The examples, written with commas rather than newlines between tunnels.
+    fn edges(list: &str) -> Vec<Edge> {
+        parse_edges(&InputFile::from_text("example", &list.replace(',', "\n"))).unwrap()
+    }
+
+    #[test]
+    fn test_part1_dead_simple() {
+        assert_eq!(1, part1(&edges("start-A,A-b,b-end")));
+    }
+
+    #[test]
+    fn test_part1_example1() {
+        assert_eq!(10, part1(&edges("start-A,start-b,A-c,A-b,b-d,A-end,b-end")));
+    }
+
+    #[test]
+    fn test_part1_example2() {
+        assert_eq!(
+            19,
+            part1(&edges(
+                "dc-end,HN-start,start-kj,dc-start,dc-HN,LN-dc,HN-end,kj-sa,kj-HN,kj-dc"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_part1_example3() {
+        assert_eq!(226, part1(&edges("fs-end,he-DX,fs-he,start-DX,pj-DX,end-zg,zg-sl,zg-pj,pj-he,RW-he,fs-DX,pj-RW,zg-RW,start-pj,he-WI,zg-he,pj-fs,start-RW")));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(4186, Day12::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2_example1() {
+        assert_eq!(36, part2(&edges("start-A,start-b,A-c,A-b,b-d,A-end,b-end")));
+    }
+
+    #[test]
+    fn test_part2_example2() {
+        assert_eq!(
+            103,
+            part2(&edges(
+                "dc-end,HN-start,start-kj,dc-start,dc-HN,LN-dc,HN-end,kj-sa,kj-HN,kj-dc"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_part2_example3() {
+        assert_eq!(3509, part2(&edges("fs-end,he-DX,fs-he,start-DX,pj-DX,end-zg,zg-sl,zg-pj,pj-he,RW-he,fs-DX,pj-RW,zg-RW,start-pj,he-WI,zg-he,pj-fs,start-RW")));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(92111, Day12::solve_part2("input.txt"));
+    }
+
+    #[test]
+    fn test_non_recursive_example1() {
+        assert_eq!(
+            36,
+            non_recursive(&edges("start-A,start-b,A-c,A-b,b-d,A-end,b-end"))
+        );
+    }
+
+    /// A random cave system in the puzzle's style. Big caves are never joined to each
+    /// other, which would make for endless paths.
+    fn random_caves(rng: &mut Rng) -> String {
+        let small = ["ab", "cd", "ef", "gh", "ij"][..2 + rng.index(4)].to_vec();
+        let big = ["KL", "MN", "OP"][..1 + rng.index(3)].to_vec();
+        let caves = [&small[..], &big[..]].concat();
+
+        let mut tunnels = Vec::new();
+        for (i, a) in caves.iter().enumerate() {
+            for b in caves[i + 1..].iter() {
+                let both_big = big.contains(a) && big.contains(b);
+                if !both_big && rng.chance(0.3) {
+                    tunnels.push(format!("{}-{}", a, b));
+                }
+            }
+            // keeps every cave in the graph, even the ones with no other tunnels
+            let other = rng.choose(&small);
+            if other != a {
+                tunnels.push(format!("{}-{}", a, other));
+            }
+        }
+        for _ in 0..1 + rng.index(2) {
+            tunnels.push(format!("start-{}", rng.choose(&caves)));
+            tunnels.push(format!("{}-end", rng.choose(&caves)));
+        }
+        rng.shuffle(&mut tunnels);
+        tunnels.join("\n")
+    }
+
+    #[test]
+    fn test_random_caves() {
+        check("day12 random caves", 100, |rng| {
+            let text = random_caves(rng);
+            let edges = parse_edges(&InputFile::from_text("random", &text)).unwrap();
+            let caves = parse_caves_graph(&edges);
+
+            let once = part1(&edges);
+            let twice = part2(&edges);
+            // a small cave that's already been seen twice can't be visited again
+            let visit_once = count_paths_recursive("start", true, &mut Vec::new(), &caves);
+            assert_eq!(once, visit_once, "\n{}", text);
+            assert_eq!(twice, non_recursive(&edges), "\n{}", text);
+            assert!(once <= twice, "\n{}", text);
+        });
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parse = |text| parse_edges(&InputFile::from_text("example", text));
+        let error = parse("start-A\nA-bB\nA-end").unwrap_err();
+        assert_eq!((2, 3), (error.line, error.column));
+        assert_eq!("`-`", parse("start-A\nA end").unwrap_err().expected);
+        assert_eq!(0, parse("A-b\nb-end").unwrap_err().line);
+    }
+}
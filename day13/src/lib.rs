@@ -0,0 +1,248 @@
+use helpers::{
+    grid::Grid,
+    parse::{InputFile, Span},
+    trace::{self, Event},
+    ParseError, Solution,
+};
+use std::collections::HashSet;
+
+type Coord = (u32, u32);
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = (HashSet<Coord>, Vec<Fold>);
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(file_name: &str) -> Result<(HashSet<Coord>, Vec<Fold>), ParseError> {
+        let input = InputFile::open(file_name)?;
+        match input.chunks().as_slice() {
+            [coords, folds] => Ok((
+                coords
+                    .iter()
+                    .map(parse_coordinate)
+                    .collect::<Result<_, _>>()?,
+                folds.iter().map(parse_fold).collect::<Result<_, _>>()?,
+            )),
+            [_] => Err(input.error("fold instructions", "only coordinates")),
+            [] => Err(input.error("coordinates", "an empty file")),
+            [_, _, extra, ..] => Err(extra[0].error("end of file")),
+        }
+    }
+
+    fn part1((coords, folds): &(HashSet<Coord>, Vec<Fold>)) -> usize {
+        let mut coords = coords.clone();
+
+        match folds[0] {
+            Fold::X(x) => fold_x(x, &mut coords),
+            Fold::Y(y) => fold_y(y, &mut coords),
+        }
+
+        coords.len()
+    }
+
+    fn part2((coords, folds): &(HashSet<Coord>, Vec<Fold>)) -> String {
+        let mut coords = coords.clone();
+        trace_fold(0, &coords);
+
+        for (i, fold) in folds.iter().enumerate() {
+            match *fold {
+                Fold::X(x) => fold_x(x, &mut coords),
+                Fold::Y(y) => fold_y(y, &mut coords),
+            }
+            trace_fold(i + 1, &coords);
+        }
+
+        let code = dots(&coords).map(|&dot| if dot { '#' } else { ' ' });
+        format!("\n{}", code)
+    }
+}
+
+/// The paper with a dot wherever there's a coordinate, just big enough to fit them all.
+fn dots(coords: &HashSet<Coord>) -> Grid<bool> {
+    let width = coords
+        .iter()
+        .map(|&(x, _)| x as usize + 1)
+        .max()
+        .unwrap_or(0);
+    let height = coords
+        .iter()
+        .map(|&(_, y)| y as usize + 1)
+        .max()
+        .unwrap_or(0);
+    Grid::from_fn(width, height, |(x, y)| {
+        coords.contains(&(x as u32, y as u32))
+    })
+}
+
+fn trace_fold(step: usize, coords: &HashSet<Coord>) {
+    trace::emit(|| {
+        let dots = dots(coords);
+        Event::new("fold")
+            .step(step)
+            .counter("dots", coords.len() as i64)
+            .snapshot(dots.map(|&dot| if dot { '#' } else { '.' }))
+            .frame(dots.map(|&dot| if dot { 255 } else { 0 }))
+    });
+}
+
+fn fold_x(fold_x: u32, coords: &mut HashSet<Coord>) {
+    let temp: Vec<Coord> = coords
+        .iter()
+        .filter(|&&(x, _)| x > fold_x)
+        .cloned()
+        .collect();
+    for coord in temp {
+        let (mut x, y) = coords.take(&coord).unwrap();
+        x = fold_x - (x - fold_x);
+        coords.insert((x, y));
+    }
+}
+
+fn fold_y(fold_y: u32, coords: &mut HashSet<Coord>) {
+    let temp: Vec<Coord> = coords
+        .iter()
+        .filter(|&&(_, y)| y > fold_y)
+        .cloned()
+        .collect();
+    for coord in temp {
+        let (x, mut y) = coords.take(&coord).unwrap();
+        y = fold_y - (y - fold_y);
+        coords.insert((x, y));
+    }
+}
+
+fn parse_coordinate(line: &Span) -> Result<Coord, ParseError> {
+    let (x, y) = line.split_once(",")?;
+    Ok((x.parse("an x coordinate")?, y.parse("a y coordinate")?))
+}
+
+fn parse_fold(line: &Span) -> Result<Fold, ParseError> {
+    let (axis, num) = line.strip_prefix("fold along ")?.split_once("=")?;
+    let num = num.parse("a fold position")?;
+    match axis.as_str() {
+        "x" => Ok(Fold::X(num)),
+        "y" => Ok(Fold::Y(num)),
+        _ => Err(axis.error("`x` or `y`")),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Fold {
+    X(u32),
+    Y(u32),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng::{check, Rng};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(17, Day13::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(751, Day13::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        let expected = "
+#####
+#   #
+#   #
+#   #
+#####
+";
+        assert_eq!(expected, Day13::solve_part2("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part2() {
+        let expected = "
+###   ##  #  # ###  #  # #    #  # #   
+#  # #  # #  # #  # # #  #    # #  #   
+#  # #    #### #  # ##   #    ##   #   
+###  # ## #  # ###  # #  #    # #  #   
+#    #  # #  # # #  # #  #    # #  #   
+#     ### #  # #  # #  # #### #  # ####
+";
+        assert_eq!(expected, Day13::solve_part2("input.txt"));
+    }
+
+    /// Random dots on paper up to 40 across and down, with folds that never go past
+    /// the paper's edge.
+    fn random_paper(rng: &mut Rng) -> String {
+        let (mut width, mut height) = (2 + rng.index(39) as u32, 2 + rng.index(39) as u32);
+        let dots = (0..1 + rng.index(30))
+            .map(|_| format!("{},{}", rng.below(width.into()), rng.below(height.into())))
+            .collect::<Vec<_>>();
+        let mut folds = Vec::new();
+        for _ in 0..1 + rng.index(4) {
+            let across = width > 1 && (height == 1 || rng.chance(0.5));
+            let (axis, size) = if across {
+                ("x", &mut width)
+            } else {
+                ("y", &mut height)
+            };
+            if *size == 1 {
+                break;
+            }
+            // at least halfway, so nothing folds over past 0
+            let along = *size / 2 + rng.below(u64::from(*size - *size / 2)) as u32;
+            folds.push(format!("fold along {}={}", axis, along));
+            *size = along + 1;
+        }
+        format!("{}\n\n{}", dots.join("\n"), folds.join("\n"))
+    }
+
+    #[test]
+    fn test_random_folds() {
+        check("day13 random folds", 100, |rng| {
+            let text = random_paper(rng);
+            let input = InputFile::from_text("random", &text);
+            let chunks = input.chunks();
+            let mut dots = chunks[0]
+                .iter()
+                .map(|line| parse_coordinate(line).unwrap())
+                .collect::<Vec<_>>();
+            let folds = chunks[1]
+                .iter()
+                .map(|line| parse_fold(line).unwrap())
+                .collect::<Vec<_>>();
+            let paper = (dots.iter().copied().collect(), folds.clone());
+
+            let mut after_first = None;
+            for fold in folds.iter() {
+                for (x, y) in dots.iter_mut() {
+                    match *fold {
+                        Fold::X(along) if *x > along => *x = 2 * along - *x,
+                        Fold::Y(along) if *y > along => *y = 2 * along - *y,
+                        _ => {}
+                    }
+                }
+                dots.sort_unstable();
+                dots.dedup();
+                after_first = after_first.or(Some(dots.len()));
+            }
+            assert_eq!(after_first, Some(Day13::part1(&paper)), "\n{}", text);
+
+            let width = dots.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+            let height = dots.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+            let mut code = String::from("\n");
+            for y in 0..height {
+                for x in 0..width {
+                    code.push(if dots.contains(&(x, y)) { '#' } else { ' ' });
+                }
+                code.push('\n');
+            }
+            assert_eq!(code, Day13::part2(&paper), "\n{}", text);
+        });
+    }
+}
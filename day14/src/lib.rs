@@ -0,0 +1,205 @@
+use helpers::{
+    parse::{InputFile, Span},
+    ParseError, Solution,
+};
+use std::collections::HashMap;
+
+type Pair = (char, char);
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(file_name: &str) -> Result<Vec<String>, ParseError> {
+        let input = InputFile::open(file_name)?;
+        validate_input(&input)?;
+        Ok(input.lines().map(|l| l.as_str().to_string()).collect())
+    }
+
+    fn part1(lines: &Vec<String>) -> usize {
+        both_parts(lines, 10)
+    }
+
+    fn part2(lines: &Vec<String>) -> usize {
+        both_parts(lines, 40)
+    }
+}
+
+fn both_parts(lines: &[String], step_count: usize) -> usize {
+    let pair_mappings = parse_pair_mappings(lines);
+
+    let mut pair_counts = parse_initial_pairs(lines);
+    let mut temp_pair_counts = HashMap::new();
+
+    for _ in 0..step_count {
+        // for each pair, create two new pairs with the same count
+        for (pair, count) in pair_counts.iter() {
+            for new_pair in get_new_pairs(pair, &pair_mappings) {
+                *temp_pair_counts.entry(new_pair).or_insert(0) += count;
+            }
+        }
+
+        // clear the old pairings, the temp collection is now our main pairing counts
+        pair_counts.clear();
+        std::mem::swap(&mut pair_counts, &mut temp_pair_counts);
+    }
+
+    // Count the occurrances of each character by counting the first char in each pair.
+    let mut char_counts = HashMap::new();
+    for ((c, _), count) in pair_counts {
+        *char_counts.entry(c).or_insert(0) += count;
+    }
+
+    // Since we counted chars using the first char in each pair, we are missing
+    // one occurrance of the last char in the original string. Add it manually.
+    let last_char = parse_last_char(lines);
+    *char_counts.entry(last_char).or_insert(0) += 1;
+
+    let max = char_counts.values().max().unwrap();
+    let min = char_counts.values().min().unwrap();
+    max - min
+}
+
+/// Checks the template / blank line / `AB -> C` rules layout the parsers below rely on.
+fn validate_input(input: &InputFile) -> Result<(), ParseError> {
+    let mut lines = input.lines();
+    let template = input.first_line()?;
+    lines.next();
+    if template.is_empty() {
+        return Err(template.error("a polymer template"));
+    }
+    validate_elements(template)?;
+
+    if let Some(blank) = lines.next() {
+        if !blank.is_empty() {
+            return Err(blank.error("a blank line"));
+        }
+    }
+
+    for line in lines {
+        let (pair, element) = line.split_once(" -> ")?;
+        if pair.as_str().chars().count() != 2 {
+            return Err(pair.error("a pair of elements"));
+        }
+        if element.as_str().chars().count() != 1 {
+            return Err(element.error("a single element"));
+        }
+        validate_elements(pair)?;
+        validate_elements(element)?;
+    }
+    Ok(())
+}
+
+fn validate_elements(span: Span) -> Result<(), ParseError> {
+    match span.chars().find(|(c, _)| !c.is_ascii_uppercase()) {
+        Some((_, bad)) => Err(bad.error("an element letter")),
+        None => Ok(()),
+    }
+}
+
+fn get_new_pairs(pair: &Pair, pair_mappings: &HashMap<Pair, char>) -> [Pair; 2] {
+    let &splitter = pair_mappings.get(pair).unwrap();
+    [(pair.0, splitter), (splitter, pair.1)]
+}
+
+fn parse_initial_pairs(lines: &[String]) -> HashMap<Pair, usize> {
+    let line = lines[0].chars().collect::<Vec<_>>();
+    let mut pair_counts = HashMap::new();
+    for pair in line.windows(2) {
+        let pair = (pair[0], pair[1]);
+        *pair_counts.entry(pair).or_insert(0) += 1;
+    }
+    pair_counts
+}
+
+fn parse_pair_mappings(lines: &[String]) -> HashMap<Pair, char> {
+    let mut mappings = HashMap::new();
+    for line in lines.iter().skip(2) {
+        let mut chars = line.chars();
+        let pair = (chars.next().unwrap(), chars.next().unwrap());
+        let value = chars.nth(4).unwrap();
+        mappings.insert(pair, value);
+    }
+    mappings
+}
+
+fn parse_last_char(lines: &[String]) -> char {
+    lines[0].chars().last().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng::check;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(1588, Day14::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(3831, Day14::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(2188189693529, Day14::solve_part2("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(5725739914282, Day14::solve_part2("input.txt"));
+    }
+
+    #[test]
+    fn test_random_polymers() {
+        check("day14 random polymers", 100, |rng| {
+            let elements = &['B', 'C', 'H', 'N'][..2 + rng.index(3)];
+            let template = (0..1 + rng.index(8))
+                .map(|_| *rng.choose(elements))
+                .collect::<String>();
+            let mut rules = HashMap::new();
+            let mut lines = vec![template.clone(), String::new()];
+            for &a in elements {
+                for &b in elements {
+                    let between = *rng.choose(elements);
+                    rules.insert((a, b), between);
+                    lines.push(format!("{}{} -> {}", a, b, between));
+                }
+            }
+            let text = lines.join("\n");
+            validate_input(&InputFile::from_text("random", &text))
+                .unwrap_or_else(|e| panic!("\n{}", e));
+
+            // the whole polymer written out, a step at a time
+            let mut polymer = template.chars().collect::<Vec<_>>();
+            for steps in 0..=10 {
+                let mut counts = HashMap::new();
+                for &element in polymer.iter() {
+                    *counts.entry(element).or_insert(0) += 1;
+                }
+                let spread = counts.values().max().unwrap() - counts.values().min().unwrap();
+                assert_eq!(
+                    spread,
+                    both_parts(&lines, steps),
+                    "{} steps\n{}",
+                    steps,
+                    text
+                );
+
+                let mut grown = vec![polymer[0]];
+                for pair in polymer.windows(2) {
+                    grown.push(rules[&(pair[0], pair[1])]);
+                    grown.push(pair[1]);
+                }
+                polymer = grown;
+            }
+        });
+    }
+}
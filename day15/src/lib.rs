@@ -0,0 +1,179 @@
+use helpers::{
+    grid::{Grid, Point},
+    parse::{self, InputFile},
+    search::{self, SearchProblem},
+    ParseError, Solution,
+};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = Grid<u8>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(file_name: &str) -> Result<Grid<u8>, ParseError> {
+        parse_grid(file_name)
+    }
+
+    fn part1(risks: &Grid<u8>) -> u32 {
+        both_parts(risks, 1)
+    }
+
+    fn part2(risks: &Grid<u8>) -> u32 {
+        both_parts(risks, 5)
+    }
+}
+
+fn both_parts(risks: &Grid<u8>, grid_multiplier: usize) -> u32 {
+    let risks = expand_grid(risks, grid_multiplier);
+    let end = (risks.width() - 1, risks.height() - 1);
+    let cave = Cave { risks, end };
+
+    search::astar(&cave, (0, 0)).unwrap().cost
+}
+
+struct Cave {
+    risks: Grid<u8>,
+    end: Point,
+}
+
+impl SearchProblem for Cave {
+    type State = Point;
+    type Cost = u32;
+
+    fn is_goal(&self, &point: &Point) -> bool {
+        point == self.end
+    }
+
+    fn neighbors(&self, &point: &Point) -> Vec<(Point, u32)> {
+        self.risks
+            .neighbors4(point)
+            .map(|next| (next, self.risks[next] as u32))
+            .collect()
+    }
+
+    // every step costs at least 1, so the manhattan distance never overestimates
+    fn heuristic(&self, &(x, y): &Point) -> u32 {
+        ((self.end.0 - x) + (self.end.1 - y)) as u32
+    }
+}
+
+/// Tiles the grid `multiplier` times in each direction, each tile one riskier than
+/// the one above or to the left of it, wrapping from 9 back round to 1.
+fn expand_grid(risks: &Grid<u8>, multiplier: usize) -> Grid<u8> {
+    let width = risks.width() * multiplier;
+    let height = risks.height() * multiplier;
+    Grid::from_fn(width, height, |(x, y)| {
+        let (&risk, (tile_x, tile_y)) = risks.tiled(x as isize, y as isize);
+        let result = risk as usize + (tile_x + tile_y) as usize;
+        if result < 10 {
+            result as u8
+        } else {
+            (result % 10 + 1) as u8
+        }
+    })
+}
+
+fn parse_grid(file_name: &str) -> Result<Grid<u8>, ParseError> {
+    let input = InputFile::open(file_name)?;
+    input.first_line()?;
+    parse::digit_grid(input.lines())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng::check;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(40, Day15::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(748, Day15::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(315, Day15::solve_part2("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(3045, Day15::solve_part2("input.txt"));
+    }
+
+    #[test]
+    fn test_grid_expand() {
+        let expected = parse_grid("expanded_grid.txt").unwrap();
+        let risks = parse_grid("test_input.txt").unwrap();
+        assert_eq!(expected, expand_grid(&risks, 5));
+    }
+
+    /// The lowest total risk from the top left to the bottom right of rows of risks,
+    /// lowering each cell's total from its neighbours' until none of them change.
+    fn naive(rows: &[Vec<u32>]) -> u32 {
+        let (width, height) = (rows[0].len(), rows.len());
+        let mut totals = vec![vec![u32::MAX; width]; height];
+        totals[0][0] = 0;
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for y in 0..height {
+                for x in 0..width {
+                    let around = [
+                        (x.wrapping_sub(1), y),
+                        (x + 1, y),
+                        (x, y.wrapping_sub(1)),
+                        (x, y + 1),
+                    ];
+                    for (nx, ny) in around {
+                        if nx >= width || ny >= height || totals[ny][nx] == u32::MAX {
+                            continue;
+                        }
+                        let total = totals[ny][nx] + rows[y][x];
+                        if total < totals[y][x] {
+                            totals[y][x] = total;
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+        totals[height - 1][width - 1]
+    }
+
+    #[test]
+    fn test_random_caves() {
+        check("day15 random caves", 50, |rng| {
+            let (width, height) = (1 + rng.index(8), 1 + rng.index(8));
+            let rows = (0..height)
+                .map(|_| (0..width).map(|_| 1 + rng.index(9) as u32).collect())
+                .collect::<Vec<Vec<u32>>>();
+            let risks = Grid::from_rows(
+                rows.iter()
+                    .map(|row| row.iter().map(|&r| r as u8).collect())
+                    .collect(),
+            );
+            assert_eq!(naive(&rows), Day15::part1(&risks), "\n{}", risks);
+
+            // each tile one riskier than the last, from 9 round to 1
+            let expanded = (0..height * 5)
+                .map(|y| {
+                    (0..width * 5)
+                        .map(|x| {
+                            let tile = (x / width + y / height) as u32;
+                            (rows[y % height][x % width] - 1 + tile) % 9 + 1
+                        })
+                        .collect()
+                })
+                .collect::<Vec<Vec<u32>>>();
+            assert_eq!(naive(&expanded), Day15::part2(&risks), "\n{}", risks);
+        });
+    }
+}
@@ -0,0 +1,425 @@
+use helpers::{
+    parse::InputFile,
+    trace::{self, Event},
+    ParseError, Solution,
+};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(file_name: &str) -> Result<String, ParseError> {
+        parse_transmission(&InputFile::open(file_name)?)
+    }
+
+    fn part1(input: &String) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &String) -> usize {
+        part2(input)
+    }
+}
+
+/// The transmission is a single line of hex digits, a whole number of bytes long.
+fn parse_transmission(file: &InputFile) -> Result<String, ParseError> {
+    let line = file.first_line()?.trim();
+    if let Some((_, span)) = line.chars().find(|(c, _)| !c.is_ascii_hexdigit()) {
+        return Err(span.error("a hexadecimal digit"));
+    }
+    if line.is_empty() || line.len() % 2 != 0 {
+        return Err(line.missing("another hexadecimal digit to make whole bytes"));
+    }
+    Ok(line.as_str().to_string())
+}
+
+fn part1(input: &str) -> usize {
+    let mut binary = Binary::from_hex(input);
+    let packet = parse_packet(&mut binary);
+
+    sum_versions(&packet)
+}
+
+fn part2(input: &str) -> usize {
+    let mut binary = Binary::from_hex(input);
+    let packet = parse_packet(&mut binary);
+
+    perform_op(&packet)
+}
+
+fn perform_op(packet: &Packet) -> usize {
+    match &packet.payload {
+        Payload::Literal(num) => *num,
+        Payload::Operator(op, packets) => match op {
+            0 => packets.iter().map(perform_op).sum(),
+            1 => packets.iter().map(perform_op).product(),
+            2 => packets.iter().map(perform_op).min().unwrap(),
+            3 => packets.iter().map(perform_op).max().unwrap(),
+            5 => bool_to_num(perform_op(&packets[0]) > perform_op(&packets[1])),
+            6 => bool_to_num(perform_op(&packets[0]) < perform_op(&packets[1])),
+            7 => bool_to_num(perform_op(&packets[0]) == perform_op(&packets[1])),
+            _ => unreachable!(),
+        },
+    }
+}
+
+pub fn part2_stack(input: &str) -> usize {
+    let mut binary = Binary::from_hex(input);
+    let packet = parse_packet(&mut binary);
+
+    // This was a "fun" exercise in rewriting recursion using a stack...
+    let mut stack = vec![Recurs::FirstTime(&packet.payload)];
+    let mut values = Vec::new();
+    while let Some(current) = stack.pop() {
+        match current {
+            Recurs::FirstTime(payload) => {
+                match payload {
+                    Payload::Literal(num) => {
+                        trace::emit(|| Event::new("literal").counter("value", *num as i64));
+                        values.push(*num)
+                    }
+                    Payload::Operator(op, children) => {
+                        trace::emit(|| trace_values("push", *op, &values));
+                        let mut vals = Vec::new();
+                        std::mem::swap(&mut vals, &mut values);
+                        stack.push(Recurs::Consolidate(payload, vals));
+                        for child in children.iter().rev() {
+                            stack.push(Recurs::FirstTime(&child.payload));
+                        }
+                    }
+                };
+            }
+            Recurs::Consolidate(payload, mut vals) => match payload {
+                Payload::Literal(_) => unreachable!(),
+                Payload::Operator(op, _) => {
+                    trace::emit(|| trace_values("consolidate", *op, &values));
+                    vals.push(match op {
+                        0 => values.iter().sum::<usize>(),
+                        1 => values.iter().product::<usize>(),
+                        2 => values.iter().copied().min().unwrap(),
+                        3 => values.iter().copied().max().unwrap(),
+                        5 => bool_to_num(values[0] > values[1]),
+                        6 => bool_to_num(values[0] < values[1]),
+                        7 => bool_to_num(values[0] == values[1]),
+                        _ => unreachable!(),
+                    });
+                    trace::emit(|| trace_values("reset", *op, &vals));
+                    values = vals;
+                }
+            },
+        }
+    }
+
+    values[0]
+}
+
+/// The stack machine's working values, as they are when an operator is reached.
+fn trace_values(name: &'static str, op: usize, values: &[usize]) -> Event {
+    let values = values.iter().map(usize::to_string).collect::<Vec<_>>();
+    Event::new(name)
+        .counter("op", op as i64)
+        .snapshot(format!("[{}]", values.join(",")))
+}
+
+enum Recurs<'a> {
+    FirstTime(&'a Payload),
+    Consolidate(&'a Payload, Vec<usize>),
+}
+
+fn bool_to_num(b: bool) -> usize {
+    match b {
+        true => 1,
+        false => 0,
+    }
+}
+
+fn sum_versions(packet: &Packet) -> usize {
+    match &packet.payload {
+        Payload::Literal(_) => packet.version,
+        Payload::Operator(_, packets) => {
+            packet.version + packets.iter().map(sum_versions).sum::<usize>()
+        }
+    }
+}
+
+fn parse_packet(binary: &mut Binary) -> Packet {
+    let version = parse_binary_num(binary.take(3));
+    let type_id = parse_binary_num(binary.take(3));
+    let payload = match type_id {
+        4 => get_literal_payload(binary),
+        i => get_operator_payload(i, binary),
+    };
+    trace::emit(|| {
+        Event::new("packet")
+            .counter("version", version as i64)
+            .counter("type", type_id as i64)
+            .counter("end", binary.position as i64)
+    });
+    Packet { version, payload }
+}
+
+fn get_literal_payload(binary: &mut Binary) -> Payload {
+    let mut bits = String::new();
+    loop {
+        let group = binary.take(5);
+        let label = &group[0..1];
+        bits.push_str(&group[1..5]);
+        if label == "0" {
+            break;
+        }
+    }
+    let num = parse_binary_num(&bits);
+    Payload::Literal(num)
+}
+
+fn get_operator_payload(type_id: usize, binary: &mut Binary) -> Payload {
+    let mut packets = Vec::new();
+    let length_type = binary.take(1);
+    if length_type == "0" {
+        // length is in bits
+        let length = parse_binary_num(binary.take(15));
+        trace::emit(|| Event::new("sub-packet bits").counter("length", length as i64));
+        let current_pos = binary.position;
+        while binary.position < current_pos + length {
+            packets.push(parse_packet(binary));
+        }
+    } else {
+        let length = parse_binary_num(binary.take(11));
+        trace::emit(|| Event::new("sub-packets").counter("count", length as i64));
+        for _ in 0..length {
+            packets.push(parse_packet(binary));
+        }
+    };
+
+    Payload::Operator(type_id, packets)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Packet {
+    version: usize,
+    payload: Payload,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Payload {
+    Literal(usize),
+    Operator(usize, Vec<Packet>),
+}
+
+struct Binary {
+    data: String,
+    position: usize,
+}
+
+impl Binary {
+    fn from_hex(hex: &str) -> Self {
+        let bytes = hex::decode(hex).unwrap();
+        let mut data = String::new();
+        for byte in bytes {
+            data.push_str(&format!("{:08b}", byte));
+        }
+        Self { data, position: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> &str {
+        let start = self.position;
+        self.position += n;
+        &self.data[start..start + n]
+    }
+}
+
+fn parse_binary_num(input: &str) -> usize {
+    usize::from_str_radix(input, 2).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng::{check, Rng};
+
+    #[test]
+    fn test_part1_example1() {
+        assert_eq!(16, part1("8A004A801A8002F478"));
+    }
+
+    #[test]
+    fn test_part1_example2() {
+        assert_eq!(12, part1("620080001611562C8802118E34"));
+    }
+
+    #[test]
+    fn test_part1_example3() {
+        assert_eq!(23, part1("C0015000016115A2E0802F182340"));
+    }
+
+    #[test]
+    fn test_part1_example4() {
+        assert_eq!(31, part1("A0016C880162017C3686B18A3D4780"));
+    }
+
+    #[test]
+    fn test_part1_example5() {
+        // literal value
+        assert_eq!(6, part1("D2FE28"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(875, Day16::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2_sum() {
+        assert_eq!(3, part2("C200B40A82"));
+    }
+
+    #[test]
+    fn test_part2_product() {
+        assert_eq!(54, part2("04005AC33890"));
+    }
+
+    #[test]
+    fn test_part2_minimum() {
+        assert_eq!(7, part2("880086C3E88112"));
+    }
+
+    #[test]
+    fn test_part2_maximum() {
+        assert_eq!(9, part2("CE00C43D881120"));
+    }
+
+    #[test]
+    fn test_part2_less_than() {
+        assert_eq!(1, part2("D8005AC2A8F0"));
+    }
+
+    #[test]
+    fn test_part2_greater_than() {
+        assert_eq!(0, part2("F600BC2D8F"));
+    }
+
+    #[test]
+    fn test_part2_equal_to() {
+        assert_eq!(0, part2("9C005AC2F8F0"));
+    }
+
+    #[test]
+    fn test_part2_compund() {
+        // (1 + 3 = 2 * 2)
+        assert_eq!(1, part2("9C0141080250320F1802104A08"));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(1264857437203, Day16::solve_part2("input.txt"));
+    }
+
+    #[test]
+    fn final_part2_stack() {
+        let input = Day16::parse("input.txt").unwrap();
+        assert_eq!(1264857437203, part2_stack(&input));
+    }
+
+    #[test]
+    fn test_parse_literal() {
+        let mut binary = Binary::from_hex("D2FE28");
+        assert_eq!(
+            Packet {
+                version: 6,
+                payload: Payload::Literal(2021)
+            },
+            parse_packet(&mut binary)
+        );
+    }
+
+    /// A random packet as bits, with its version sum and value, or `None` if the value
+    /// doesn't fit in a `usize`.
+    fn random_packet(rng: &mut Rng, depth: usize) -> Option<(String, usize, usize)> {
+        let version = rng.below(8) as usize;
+        let mut bits = format!("{:03b}", version);
+        if depth == 0 || rng.chance(0.3) {
+            let value = if rng.chance(0.2) {
+                rng.below(1 << 40)
+            } else {
+                rng.below(16)
+            } as usize;
+            bits.push_str("100");
+            let groups = format!("{:b}", value).len().div_ceil(4);
+            for group in (0..groups).rev() {
+                let last = if group == 0 { '0' } else { '1' };
+                bits.push(last);
+                bits.push_str(&format!("{:04b}", (value >> (group * 4)) & 0xf));
+            }
+            return Some((bits, version, value));
+        }
+
+        let op = *rng.choose(&[0, 1, 2, 3, 5, 6, 7]);
+        let count = if op >= 5 { 2 } else { 1 + rng.index(3) };
+        let mut children = String::new();
+        let mut versions = version;
+        let mut values = Vec::new();
+        for _ in 0..count {
+            let (child, child_versions, value) = random_packet(rng, depth - 1)?;
+            children.push_str(&child);
+            versions += child_versions;
+            values.push(value);
+        }
+        let value = match op {
+            0 => values.iter().try_fold(0usize, |a, &b| a.checked_add(b))?,
+            1 => values.iter().try_fold(1usize, |a, &b| a.checked_mul(b))?,
+            2 => *values.iter().min().unwrap(),
+            3 => *values.iter().max().unwrap(),
+            5 => bool_to_num(values[0] > values[1]),
+            6 => bool_to_num(values[0] < values[1]),
+            _ => bool_to_num(values[0] == values[1]),
+        };
+
+        bits.push_str(&format!("{:03b}", op));
+        if rng.chance(0.5) {
+            bits.push_str(&format!("0{:015b}", children.len()));
+        } else {
+            bits.push_str(&format!("1{:011b}", count));
+        }
+        bits.push_str(&children);
+        Some((bits, versions, value))
+    }
+
+    fn to_hex(mut bits: String) -> String {
+        while !bits.len().is_multiple_of(8) {
+            bits.push('0');
+        }
+        (0..bits.len())
+            .step_by(4)
+            .map(|i| format!("{:X}", parse_binary_num(&bits[i..i + 4])))
+            .collect()
+    }
+
+    #[test]
+    fn test_random_transmissions() {
+        check("day16 random transmissions", 200, |rng| {
+            let (bits, versions, value) = loop {
+                if let Some(packet) = random_packet(rng, 4) {
+                    break packet;
+                }
+            };
+            let hex = parse_transmission(&InputFile::from_text("random", &to_hex(bits))).unwrap();
+            assert_eq!(versions, part1(&hex), "{}", hex);
+            assert_eq!(value, part2(&hex), "{}", hex);
+            assert_eq!(value, part2_stack(&hex), "{}", hex);
+        });
+    }
+
+    #[test]
+    fn test_parse_transmission() {
+        let parse = |text| parse_transmission(&InputFile::from_text("example", text));
+        assert_eq!("D2FE28", parse(" D2FE28 \n").unwrap());
+        assert_eq!(4, parse("D2FX28").unwrap_err().column);
+        assert_eq!(6, parse("D2FE2").unwrap_err().column);
+        assert!(parse("").is_err());
+    }
+}
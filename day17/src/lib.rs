@@ -0,0 +1,243 @@
+use helpers::{
+    parallel,
+    parse::{InputFile, Span},
+    ParseError, Solution,
+};
+use std::cmp::max;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input = TargetArea;
+    type Part1 = i32;
+    type Part2 = usize;
+
+    fn parse(file_name: &str) -> Result<TargetArea, ParseError> {
+        let file = InputFile::open(file_name)?;
+        TargetArea::parse(file.first_line()?.trim())
+    }
+
+    fn part1(target: &TargetArea) -> i32 {
+        part1(target)
+    }
+
+    fn part2(target: &TargetArea) -> usize {
+        part2(target)
+    }
+}
+
+fn part1(target: &TargetArea) -> i32 {
+    let x_vels = (1..=target.max_x).collect::<Vec<_>>();
+    let heights = parallel::map(&x_vels, |&x_vel| {
+        let mut max_y = i32::MIN;
+        for y_vel in 1..-target.min_y {
+            if let Some(height) = get_height_if_hit(x_vel, y_vel, target) {
+                max_y = max(max_y, height);
+            }
+        }
+        max_y
+    });
+    heights.into_iter().max().unwrap_or(i32::MIN)
+}
+
+fn part2(target: &TargetArea) -> usize {
+    let x_vels = (1..=target.max_x).collect::<Vec<_>>();
+    let hits = parallel::map(&x_vels, |&x_vel| {
+        // Any faster and the probe overshoots on its first step, or on the way back
+        // down through y=0.
+        (target.min_y..-target.min_y)
+            .filter(|&y_vel| get_height_if_hit(x_vel, y_vel, target).is_some())
+            .count()
+    });
+    hits.into_iter().sum()
+}
+
+fn get_height_if_hit(x_vel: i32, y_vel: i32, target: &TargetArea) -> Option<i32> {
+    let trajectory = Trajectory::new(x_vel, y_vel);
+    let mut max_y = i32::MIN;
+    for (x, y) in trajectory {
+        max_y = max(max_y, y);
+        if target.contains(x, y) {
+            return Some(max_y);
+        }
+        if target.is_past(x, y) {
+            return None;
+        }
+    }
+
+    unreachable!()
+}
+
+struct Trajectory {
+    x_vel: i32,
+    y_vel: i32,
+    position: (i32, i32),
+}
+
+impl Trajectory {
+    fn new(x_vel: i32, y_vel: i32) -> Self {
+        Self {
+            x_vel,
+            y_vel,
+            position: (0, 0),
+        }
+    }
+}
+
+impl Iterator for Trajectory {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (mut x, mut y) = self.position;
+        x += self.x_vel;
+        y += self.y_vel;
+        self.x_vel -= self.x_vel.signum();
+        self.y_vel -= 1;
+        self.position = (x, y);
+        Some((x, y))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetArea {
+    min_x: i32,
+    max_x: i32,
+    min_y: i32,
+    max_y: i32,
+}
+
+impl TargetArea {
+    /// `target area: x=20..30, y=-10..-5`, which has to be ahead of and below the
+    /// launcher for the velocity search to find it.
+    fn parse(line: Span) -> Result<Self, ParseError> {
+        let (x, y) = line.strip_prefix("target area: x=")?.split_once(", y=")?;
+        let (min_x, max_x) = Self::parse_range(x)?;
+        let (min_y, max_y) = Self::parse_range(y)?;
+        if min_x <= 0 {
+            return Err(x.error("a range of positive x"));
+        }
+        if max_y >= 0 {
+            return Err(y.error("a range of negative y"));
+        }
+        Ok(Self {
+            min_x,
+            max_x,
+            min_y,
+            max_y,
+        })
+    }
+
+    fn parse_range(span: Span) -> Result<(i32, i32), ParseError> {
+        let (min, max) = span.split_once("..")?;
+        let (min, max) = (min.parse("a number")?, max.parse("a number")?);
+        if min > max {
+            return Err(span.error("a range from low to high"));
+        }
+        Ok((min, max))
+    }
+
+    fn contains(&self, x: i32, y: i32) -> bool {
+        self.min_x <= x && x <= self.max_x && self.min_y <= y && y <= self.max_y
+    }
+
+    fn is_past(&self, x: i32, y: i32) -> bool {
+        self.max_x < x || y < self.min_y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::parallel::with_threads;
+    use helpers::rng::check;
+
+    const TEST_TARGET: &TargetArea = &TargetArea {
+        min_x: 20,
+        max_x: 30,
+        min_y: -10,
+        max_y: -5,
+    };
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(45, part1(TEST_TARGET));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(33670, Day17::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(112, part2(TEST_TARGET));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(4903, Day17::solve_part2("input.txt"));
+    }
+
+    #[test]
+    fn test_parse() {
+        let file = InputFile::from_text("example", "target area: x=20..30, y=-10..-5");
+        let target = TargetArea::parse(file.first_line().unwrap()).unwrap();
+        assert_eq!(TEST_TARGET, &target);
+
+        let file = InputFile::from_text("example", "target area: x=20..30, y=5..10");
+        let error = TargetArea::parse(file.first_line().unwrap()).unwrap_err();
+        assert_eq!((1, 26), (error.line, error.column));
+    }
+
+    #[test]
+    fn test_threads() {
+        let target = Day17::parse("input.txt").unwrap();
+        let serial = with_threads(1, || (part1(&target), part2(&target)));
+        let parallel = with_threads(4, || (part1(&target), part2(&target)));
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn test_random_targets() {
+        check("day17 random targets", 100, |rng| {
+            let min_x = rng.range(1..=30) as i32;
+            let max_x = min_x + rng.range(0..=10) as i32;
+            let min_y = rng.range(-30..=-1) as i32;
+            let max_y = rng.range(min_y.into()..=-1) as i32;
+            let line = format!(
+                "target area: x={}..{}, y={}..{}",
+                min_x, max_x, min_y, max_y
+            );
+            let file = InputFile::from_text("random", &line);
+            let target = TargetArea::parse(file.first_line().unwrap()).unwrap();
+
+            // every launch in a box well past the ones that can hit, step by step
+            let mut hits = 0;
+            let mut highest = i32::MIN;
+            for x_vel in 0..=max_x + 10 {
+                for y_vel in min_y - 10..=-min_y + 10 {
+                    let (mut x, mut y, mut dx, mut dy, mut top) = (0, 0, x_vel, y_vel, 0);
+                    while x <= max_x && y >= min_y {
+                        x += dx;
+                        y += dy;
+                        dx -= dx.signum();
+                        dy -= 1;
+                        top = top.max(y);
+                        if (min_x..=max_x).contains(&x) && (min_y..=max_y).contains(&y) {
+                            hits += 1;
+                            // part 1 only looks at launches that go up
+                            if y_vel > 0 {
+                                highest = highest.max(top);
+                            }
+                            break;
+                        }
+                    }
+                }
+            }
+            assert_eq!(highest, part1(&target), "{}", line);
+            assert_eq!(hits, part2(&target), "{}", line);
+        });
+    }
+}
@@ -1,5 +1,5 @@
-use crate::pair::*;
 use crate::explode::explode;
+use crate::pair::*;
 use crate::split::split;
 
 pub fn add(left: Pair, right: Pair) -> Pair {
@@ -36,4 +36,28 @@ mod tests {
         let expected = "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]";
         assert_eq!(expected, &format!("{}", reduce(pair)));
     }
-}
\ No newline at end of file
+
+    /// The deepest a pair is nested, and the largest regular number.
+    fn shape(element: &Element) -> (usize, usize) {
+        match element {
+            Element::Number(n) => (0, *n),
+            Element::Pair(pair) => {
+                let (left, right) = (shape(&pair.0), shape(&pair.1));
+                (1 + left.0.max(right.0), left.1.max(right.1))
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_sums_are_reduced() {
+        helpers::rng::check("day18 random sums are reduced", 200, |rng| {
+            let left = random_pair(rng, 4);
+            let right = random_pair(rng, 4);
+            let text = format!("{} + {}", left, right);
+            let sum = Element::Pair(Box::new(add(left, right)));
+            let (depth, largest) = shape(&sum);
+            assert!(depth <= 4, "{}", text);
+            assert!(largest < 10, "{}", text);
+        });
+    }
+}
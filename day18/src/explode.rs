@@ -55,14 +55,14 @@ fn add_number_to_left(element: &mut Element, add: usize) -> bool {
     match element {
         Element::Number(num) => {
             *num += add;
-            return true;
+            true
         }
         Element::Pair(ref mut pair) => {
             if add_number_to_left(&mut pair.0, add) {
-                return true;
+                true
             } else {
                 add_number_to_left(&mut pair.1, add);
-                return true;
+                true
             }
         }
     }
@@ -72,14 +72,14 @@ fn add_number_to_right(element: &mut Element, add: usize) -> bool {
     match element {
         Element::Number(num) => {
             *num += add;
-            return true;
+            true
         }
         Element::Pair(ref mut pair) => {
             if add_number_to_right(&mut pair.1, add) {
-                return true;
+                true
             } else {
                 add_number_to_right(&mut pair.0, add);
-                return true;
+                true
             }
         }
     }
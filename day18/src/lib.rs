@@ -0,0 +1,83 @@
+use helpers::{parse::InputFile, ParseError, Solution};
+use std::collections::VecDeque;
+
+mod adding;
+mod explode;
+mod magnitude;
+mod pair;
+mod parse;
+mod split;
+
+use adding::add;
+use magnitude::magnitude;
+use pair::Pair;
+use parse::parse_pair_span;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input = Vec<Pair>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(file_name: &str) -> Result<Vec<Pair>, ParseError> {
+        let input = InputFile::open(file_name)?;
+        input.first_line()?;
+        let pairs = input.lines().map(parse_pair_span);
+        pairs.collect()
+    }
+
+    fn part1(pairs: &Vec<Pair>) -> usize {
+        let mut pairs = pairs.iter().cloned().collect::<VecDeque<_>>();
+
+        let mut current = pairs.pop_front().unwrap();
+        while let Some(pair) = pairs.pop_front() {
+            current = add(current, pair);
+        }
+
+        magnitude(&current)
+    }
+
+    fn part2(pairs: &Vec<Pair>) -> usize {
+        let mut max = 0;
+        for i in 0..pairs.len() {
+            for j in 0..pairs.len() {
+                if j != i {
+                    let first = &pairs[i];
+                    let second = &pairs[j];
+                    let added = add(first.clone(), second.clone());
+                    max = std::cmp::max(max, magnitude(&added));
+                }
+            }
+        }
+
+        max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(4140, Day18::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(4235, Day18::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(3993, Day18::solve_part2("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(4659, Day18::solve_part2("input.txt"));
+    }
+}
@@ -26,9 +26,25 @@ impl Element {
 
 impl Display for Element {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        Ok(match self {
-            Element::Number(num) => write!(f, "{}", num)?,
-            Element::Pair(pair) => write!(f, "{}", pair)?,
-        })
+        match self {
+            Element::Number(num) => write!(f, "{}", num),
+            Element::Pair(pair) => write!(f, "{}", pair),
+        }
     }
 }
+
+/// A random snailfish number with pairs nested at most `depth` deep. Numbers are
+/// mostly regular ones, with the odd one that needs splitting.
+#[cfg(test)]
+pub fn random_pair(rng: &mut helpers::rng::Rng, depth: usize) -> Pair {
+    let element = |rng: &mut helpers::rng::Rng| {
+        if depth > 1 && rng.chance(0.6) {
+            Element::Pair(Box::new(random_pair(rng, depth - 1)))
+        } else if rng.chance(0.9) {
+            Element::Number(rng.below(10) as usize)
+        } else {
+            Element::Number(10 + rng.below(20) as usize)
+        }
+    };
+    Pair(element(rng), element(rng))
+}
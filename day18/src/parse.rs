@@ -1,46 +1,46 @@
 use crate::pair::*;
+use helpers::{parse::Span, ParseError};
 
+/// Parses a snailfish number such as `[[1,2],3]`.
+pub fn parse_pair_span(span: Span) -> Result<Pair, ParseError> {
+    let inner = span.strip_prefix("[")?.strip_suffix("]")?;
+    let (left, right) = split_at_comma(inner)?;
+    let a = parse_element(left)?;
+    let b = parse_element(right)?;
+    Ok(Pair(a, b))
+}
+
+/// Parses a snailfish number that is known to be valid, panicking with the report otherwise.
+#[cfg(test)]
 pub fn parse_pair(input: &str) -> Pair {
-    let input = trim_outer_braces(input);
-    let (left, right) = split_at_comma(input);
-    let a = parse_element(left);
-    let b = parse_element(right);
-    Pair(a, b)
+    let file = helpers::parse::InputFile::from_text("pair", input);
+    parse_pair_span(file.first_line().unwrap()).unwrap_or_else(|e| panic!("\n{}", e))
 }
 
-fn parse_element(input: &str) -> Element {
-    if input.contains("[") {
-        let pair = parse_pair(input);
-        Element::Pair(Box::new(pair))
+fn parse_element(span: Span) -> Result<Element, ParseError> {
+    if span.as_str().starts_with('[') {
+        let pair = parse_pair_span(span)?;
+        Ok(Element::Pair(Box::new(pair)))
     } else {
-        let num = input.parse().unwrap();
-        Element::Number(num)
+        let num = span.parse("a regular number or a pair")?;
+        Ok(Element::Number(num))
     }
 }
 
-fn trim_outer_braces(input: &str) -> &str {
-    assert_eq!("[", &input[0..1]);
-    assert_eq!("]", &input[input.len() - 1..]);
-    &input[1..input.len() - 1]
-}
-
-fn split_at_comma(input: &str) -> (&str, &str) {
+fn split_at_comma(span: Span) -> Result<(Span, Span), ParseError> {
     let mut depth = 0;
-    for (i, c) in input.char_indices() {
+    for (i, c) in span.as_str().char_indices() {
         match c {
             '[' => depth += 1,
             ']' => depth -= 1,
-            ',' => {
-                if depth == 0 {
-                    let (a, b) = input.split_at(i);
-                    return (a, &b[1..]);
-                }
+            ',' if depth == 0 => {
+                return Ok((span.slice(0, i), span.slice(i + 1, span.len() - i - 1)));
             }
             _ => {}
         }
     }
 
-    panic!("Never found comma!");
+    Err(span.missing("`,` between the two elements"))
 }
 
 #[cfg(test)]
@@ -62,4 +62,20 @@ mod tests {
             assert_eq!(input, &format!("{}", parse_pair(input)));
         }
     }
+
+    #[test]
+    fn test_parse_error() {
+        let file = helpers::parse::InputFile::from_text("pairs.txt", "[[1,2],[3 4]]");
+        let error = parse_pair_span(file.first_line().unwrap()).err().unwrap();
+        assert_eq!(12, error.column);
+        assert_eq!("`,` between the two elements", error.expected);
+    }
+
+    #[test]
+    fn test_random_round_trip() {
+        helpers::rng::check("day18 random round trip", 200, |rng| {
+            let text = random_pair(rng, 6).to_string();
+            assert_eq!(text, parse_pair(&text).to_string());
+        });
+    }
 }
@@ -0,0 +1,244 @@
+mod rotate;
+use helpers::{
+    parallel,
+    parse::{InputFile, Span},
+    trace::{self, Event},
+    ParseError, Solution,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use rotate::Rotate;
+
+type Point = (i32, i32, i32);
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Input = Vec<Scanner>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(file_name: &str) -> Result<Vec<Scanner>, ParseError> {
+        parse_scanners(file_name)
+    }
+
+    fn part1(scanners: &Vec<Scanner>) -> usize {
+        both_parts(scanners).0
+    }
+
+    fn part2(scanners: &Vec<Scanner>) -> usize {
+        both_parts(scanners).1
+    }
+}
+
+fn both_parts(scanners: &[Scanner]) -> (usize, usize) {
+    let mut scanners = scanners.iter().cloned().collect::<VecDeque<_>>();
+    let scanner_zero = scanners.pop_front().unwrap();
+
+    let mut beacons = HashSet::<_>::from_iter(scanner_zero.points);
+    // scanner 0 is where the others are measured from
+    let mut scanner_distances = vec![(0, 0, 0)];
+    let mut rotations = HashSet::new();
+    let mut calculations = 0usize;
+
+    while let Some(scanner) = scanners.pop_front() {
+        //println!("Scanner {}", scanner.id);
+        let orientations = Rotate::new(&scanner.points).collect::<Vec<_>>();
+        let found = parallel::find_first(&orientations, |points| {
+            let mut distances: HashMap<Point, usize> = HashMap::new();
+            for &point in points.iter() {
+                for &orig in beacons.iter() {
+                    *distances.entry(diff(point, orig)).or_default() += 1;
+                }
+            }
+            distances
+                .into_iter()
+                .find(|&(_, c)| c >= 12)
+                .map(|(distance, _)| distance)
+        });
+        // the same as a serial search, which stops at the first rotation that fits
+        let tried = found.map_or(orientations.len(), |(i, _)| i + 1);
+        calculations += tried * scanner.points.len() * beacons.len();
+
+        match found {
+            Some((i, distance)) => {
+                scanner_distances.push(distance);
+                // println!(
+                //     "Distance from scanner 0: {},{},{}",
+                //     distance.0, distance.1, distance.2
+                // );
+                rotations.insert(i);
+                //println!("Found after {} rotations", i);
+                let points = &orientations[i];
+                beacons.extend(points.iter().map(|&p| diff(p, distance)));
+            }
+            None => scanners.push_back(scanner),
+        }
+    }
+
+    trace::emit(|| {
+        Event::new("aligned")
+            .counter("rotations", rotations.len() as i64)
+            .counter("calculations", calculations as i64)
+    });
+
+    let mut max = 0;
+    for &distance in scanner_distances.iter() {
+        for &other in scanner_distances.iter() {
+            let manhattan = (distance.0 - other.0).abs()
+                + (distance.1 - other.1).abs()
+                + (distance.2 - other.2).abs();
+            max = std::cmp::max(max, manhattan);
+        }
+    }
+
+    (beacons.len(), max as usize)
+}
+
+fn diff(left: Point, right: Point) -> Point {
+    (left.0 - right.0, left.1 - right.1, left.2 - right.2)
+}
+
+#[derive(Clone)]
+pub struct Scanner {
+    pub id: i32,
+    pub points: Vec<Point>,
+}
+
+fn parse_scanners(file_name: &str) -> Result<Vec<Scanner>, ParseError> {
+    let input = InputFile::open(file_name)?;
+    input
+        .chunks()
+        .into_iter()
+        .map(|chunk| {
+            let id = chunk[0]
+                .strip_prefix("--- scanner ")?
+                .strip_suffix(" ---")?
+                .parse("a scanner id")?;
+            let points = chunk[1..]
+                .iter()
+                .map(parse_point)
+                .collect::<Result<_, _>>()?;
+            Ok(Scanner { id, points })
+        })
+        .collect()
+}
+
+fn parse_point(line: &Span) -> Result<Point, ParseError> {
+    let mut coords = line.split(',');
+    let mut coord = |expected| line.next_from(&mut coords, expected)?.parse(expected);
+    let point = (
+        coord("an x coordinate")?,
+        coord("a y coordinate")?,
+        coord("a z coordinate")?,
+    );
+    line.expect_end(&mut coords)?;
+    Ok(point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::parallel::with_threads;
+    use helpers::rng::{check, Rng};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(79, Day19::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(472, Day19::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(3621, Day19::solve_part2("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(12092, Day19::solve_part2("input.txt"));
+    }
+
+    #[test]
+    fn test_threads() {
+        let scanners = Day19::parse("test_input.txt").unwrap();
+        let serial = with_threads(1, || both_parts(&scanners));
+        let parallel = with_threads(4, || both_parts(&scanners));
+        assert_eq!(serial, parallel);
+    }
+
+    fn near(rng: &mut Rng, (x, y, z): Point, spread: i64) -> Point {
+        let mut offset = || rng.range(-spread..=spread) as i32;
+        (x + offset(), y + offset(), z + offset())
+    }
+
+    /// A row of scanners, each turned some random way and sharing 12 beacons with the
+    /// one before it, along with the number of beacons and the furthest apart any two
+    /// of the scanners are.
+    fn random_scanners(rng: &mut Rng) -> (String, usize, usize) {
+        let mut positions = vec![(0, 0, 0)];
+        for _ in 0..1 + rng.index(3) {
+            let &last = positions.last().unwrap();
+            positions.push(near(rng, last, 600));
+        }
+
+        let mut seen = vec![Vec::new(); positions.len()];
+        for k in 1..positions.len() {
+            let (a, b) = (positions[k - 1], positions[k]);
+            let middle = ((a.0 + b.0) / 2, (a.1 + b.1) / 2, (a.2 + b.2) / 2);
+            for _ in 0..12 {
+                let beacon = near(rng, middle, 300);
+                seen[k - 1].push(beacon);
+                seen[k].push(beacon);
+            }
+        }
+        for (k, &position) in positions.iter().enumerate() {
+            for _ in 0..rng.index(10) {
+                seen[k].push(near(rng, position, 800));
+            }
+        }
+        let beacons = seen.iter().flatten().collect::<HashSet<_>>().len();
+
+        let mut text = Vec::new();
+        for (k, (&position, beacons)) in positions.iter().zip(seen.iter()).enumerate() {
+            let relative = beacons
+                .iter()
+                .map(|&b| diff(b, position))
+                .collect::<Vec<_>>();
+            let turned = Rotate::new(&relative).nth(rng.index(24)).unwrap();
+            text.push(format!("--- scanner {} ---", k));
+            text.extend(turned.iter().map(|(x, y, z)| format!("{},{},{}", x, y, z)));
+            text.push(String::new());
+        }
+
+        let furthest = positions
+            .iter()
+            .flat_map(|&a| positions.iter().map(move |&b| diff(a, b)))
+            .map(|(x, y, z)| (x.abs() + y.abs() + z.abs()) as usize)
+            .max()
+            .unwrap();
+        (text.join("\n"), beacons, furthest)
+    }
+
+    #[test]
+    fn test_random_scanners() {
+        check("day19 random scanners", 20, |rng| {
+            let (text, beacons, furthest) = random_scanners(rng);
+            let input = InputFile::from_text("random", &text);
+            let scanners = input
+                .chunks()
+                .into_iter()
+                .map(|chunk| Scanner {
+                    id: 0,
+                    points: chunk[1..].iter().map(|l| parse_point(l).unwrap()).collect(),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!((beacons, furthest), both_parts(&scanners), "\n{}", text);
+        });
+    }
+}
@@ -6,9 +6,9 @@ pub struct Rotate {
 }
 
 impl Rotate {
-    pub fn new(points: &Vec<Point>) -> Self {
+    pub fn new(points: &[Point]) -> Self {
         Self {
-            points: Vec::from_iter(points.into_iter().cloned()),
+            points: points.to_vec(),
             idx: 0,
         }
     }
@@ -94,12 +94,29 @@ mod tests {
     #[test]
     fn test_rotate_iterator() {
         let points = vec![(1, 2, 3), (4, 5, 6)];
-        let mut rotate = Rotate::new(&points);
+        let rotate = Rotate::new(&points);
         let mut all_points = HashSet::new();
-        while let Some(next) = rotate.next() {
+        for next in rotate {
             all_points.extend(next);
         }
 
         assert_eq!(48, all_points.len());
     }
+
+    #[test]
+    fn test_rotations_compose() {
+        // turning one way and then another is always the same as some single turn, and
+        // every turn can be undone by another
+        let axes = [(1, 0, 0), (0, 1, 0), (0, 0, 1)];
+        let turned = |n| axes.map(|axis| rotate(axis, n));
+        for a in 0..24 {
+            let mut undone = false;
+            for b in 0..24 {
+                let both = axes.map(|axis| rotate(rotate(axis, a), b));
+                assert!((0..24).any(|n| turned(n) == both), "{} then {}", a, b);
+                undone |= both == axes;
+            }
+            assert!(undone, "{} can't be undone", a);
+        }
+    }
 }
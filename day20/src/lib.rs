@@ -0,0 +1,228 @@
+use helpers::{
+    grid::Grid,
+    parallel,
+    parse::{self, InputFile, Span},
+    trace::{self, Event},
+    ParseError, Solution,
+};
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Input = (Vec<u8>, Grid<u8>);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(file_name: &str) -> Result<(Vec<u8>, Grid<u8>), ParseError> {
+        parse_input(file_name)
+    }
+
+    fn part1((algo, image): &(Vec<u8>, Grid<u8>)) -> usize {
+        both_parts(2, algo, image)
+    }
+
+    fn part2((algo, image): &(Vec<u8>, Grid<u8>)) -> usize {
+        both_parts(50, algo, image)
+    }
+}
+
+fn both_parts(steps: usize, algo: &[u8], image: &Grid<u8>) -> usize {
+    let mut image = image.clone();
+
+    for step in 0..steps {
+        let default = if algo[0] == 1 {
+            // have to toggle the "infinite" part of the image every step
+            if step % 2 == 0 {
+                0
+            } else {
+                1
+            }
+        } else {
+            0
+        };
+
+        // the image grows by one pixel on every side each step
+        let rows = (0..image.height() + 2).collect::<Vec<_>>();
+        let rows = parallel::map(&rows, |&y| {
+            (0..image.width() + 2)
+                .map(|x| enhance_pixel(x as isize - 1, y as isize - 1, &image, algo, default))
+                .collect()
+        });
+        image = Grid::from_rows(rows);
+
+        trace::emit(|| {
+            Event::new("enhance")
+                .step(step + 1)
+                .counter("lit", image.iter().filter(|&&b| b == 1).count() as i64)
+                .snapshot(image.map(|&b| if b > 0 { '#' } else { '.' }))
+                .frame(image.map(|&b| b * 255))
+        });
+    }
+
+    image.iter().filter(|&&b| b == 1).count()
+}
+
+fn enhance_pixel(x: isize, y: isize, image: &Grid<u8>, algo: &[u8], default: u8) -> u8 {
+    let window_value = pixel_window_value(x, y, image, default);
+    algo[window_value]
+}
+
+fn pixel_window_value(x: isize, y: isize, image: &Grid<u8>, default: u8) -> usize {
+    let mut value = 0usize;
+    let mut position = 9usize;
+    for j in -1..2 {
+        for i in -1..2 {
+            let bit = image.get_or(x + i, y + j, default);
+
+            position -= 1;
+            if bit > 0 {
+                value |= 1 << position;
+            }
+        }
+    }
+
+    value
+}
+
+fn parse_input(file_name: &str) -> Result<(Vec<u8>, Grid<u8>), ParseError> {
+    let input = InputFile::open(file_name)?;
+    let (algo, image) = match input.chunks().as_slice() {
+        [algo, image] if algo.len() == 1 => (algo[0], image.clone()),
+        [algo, _] => return Err(algo[1].error("a blank line after the algorithm")),
+        [_] => return Err(input.error("an image", "only the algorithm")),
+        [] => return Err(input.error("an enhancement algorithm", "an empty file")),
+        [_, _, extra, ..] => return Err(extra[0].error("end of file")),
+    };
+
+    if algo.len() != 512 {
+        return Err(algo.error("an algorithm of 512 pixels"));
+    }
+    let algo = zeroes_and_ones(algo)?;
+
+    let image = parse::char_grid(image, "`#` or `.`", |c| match c {
+        '#' => Some(1u8),
+        '.' => Some(0u8),
+        _ => None,
+    })?;
+
+    Ok((algo, image))
+}
+
+fn zeroes_and_ones(line: Span) -> Result<Vec<u8>, ParseError> {
+    line.chars()
+        .map(|(c, span)| match c {
+            '#' => Ok(1u8),
+            '.' => Ok(0u8),
+            _ => Err(span.error("`#` or `.`")),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::parallel::with_threads;
+    use helpers::rng::check;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(35, Day20::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(5680, Day20::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(3351, Day20::solve_part2("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(19766, Day20::solve_part2("input.txt"));
+    }
+
+    #[test]
+    fn test_pixel_window() {
+        let image = parse_input("test_input.txt").unwrap().1;
+
+        assert_eq!(1, pixel_window_value(-1, -1, &image, 0));
+        assert_eq!(34, pixel_window_value(2, 2, &image, 0));
+        assert_eq!(256, pixel_window_value(5, 5, &image, 0));
+    }
+
+    #[test]
+    fn test_threads() {
+        let (algo, image) = parse_input("input.txt").unwrap();
+        let serial = with_threads(1, || both_parts(10, &algo, &image));
+        let parallel = with_threads(4, || both_parts(10, &algo, &image));
+        assert_eq!(serial, parallel);
+    }
+
+    /// Enhances rows of pixels on a canvas with room for them to grow into, keeping
+    /// track of what the endless rest of the image is outside it.
+    fn naive(steps: usize, algo: &[u8], rows: &[Vec<u8>]) -> usize {
+        let pad = steps + 1;
+        let (width, height) = (rows[0].len() + 2 * pad, rows.len() + 2 * pad);
+        let mut canvas = vec![vec![0u8; width]; height];
+        for (y, row) in rows.iter().enumerate() {
+            canvas[y + pad][pad..pad + row.len()].copy_from_slice(row);
+        }
+        let mut outside = 0;
+        for _ in 0..steps {
+            let pixel = |x: isize, y: isize| {
+                let inside = (0..width as isize).contains(&x) && (0..height as isize).contains(&y);
+                if inside {
+                    canvas[y as usize][x as usize]
+                } else {
+                    outside
+                }
+            };
+            let mut next = vec![vec![0u8; width]; height];
+            for (y, row) in next.iter_mut().enumerate() {
+                for (x, out) in row.iter_mut().enumerate() {
+                    let (x, y) = (x as isize, y as isize);
+                    let index = (-1..=1)
+                        .flat_map(|dy| (-1..=1).map(move |dx| (x + dx, y + dy)))
+                        .fold(0, |index, (x, y)| index << 1 | pixel(x, y) as usize);
+                    *out = algo[index];
+                }
+            }
+            outside = algo[if outside == 0 { 0 } else { 511 }];
+            canvas = next;
+        }
+        assert_eq!(0, outside, "an endless number of pixels are lit");
+        canvas.iter().flatten().filter(|&&p| p == 1).count()
+    }
+
+    #[test]
+    fn test_random_images() {
+        check("day20 random images", 100, |rng| {
+            let mut algo = (0..512).map(|_| rng.index(2) as u8).collect::<Vec<_>>();
+            // lighting the dark outside for good would light up endlessly many pixels
+            if algo[0] == 1 {
+                algo[511] = 0;
+            }
+            let (width, height) = (1 + rng.index(10), 1 + rng.index(10));
+            let rows = (0..height)
+                .map(|_| (0..width).map(|_| rng.index(2) as u8).collect())
+                .collect::<Vec<Vec<u8>>>();
+            let image = Grid::from_rows(rows.clone());
+
+            for steps in [0, 2, 4] {
+                assert_eq!(
+                    naive(steps, &algo, &rows),
+                    both_parts(steps, &algo, &image),
+                    "{} steps of {:?} on\n{}",
+                    steps,
+                    algo,
+                    image
+                );
+            }
+        });
+    }
+}
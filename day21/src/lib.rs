@@ -0,0 +1,287 @@
+use helpers::{
+    parse::InputFile,
+    trace::{self, Event},
+    ParseError, Solution,
+};
+use std::{cmp::min, collections::HashMap};
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+
+    type Input = (usize, usize);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(file_name: &str) -> Result<(usize, usize), ParseError> {
+        parse_positions(&InputFile::open(file_name)?)
+    }
+
+    fn part1(&(pos1, pos2): &(usize, usize)) -> usize {
+        part1(pos1, pos2)
+    }
+
+    fn part2(&(pos1, pos2): &(usize, usize)) -> usize {
+        part2(pos1, pos2)
+    }
+}
+
+/// `Player 1 starting position: 4`, then the same for player 2.
+fn parse_positions(file: &InputFile) -> Result<(usize, usize), ParseError> {
+    let mut lines = file.lines().filter(|line| !line.trim().is_empty());
+    let mut position = |player| {
+        let prefix = format!("Player {} starting position: ", player);
+        let line = lines
+            .next()
+            .ok_or_else(|| file.error(&format!("`{}`", prefix), "end of file"))?;
+        let position = line.trim().strip_prefix(&prefix)?;
+        match position.parse("a position")? {
+            p @ 1..=10 => Ok(p),
+            _ => Err(position.error("a position from 1 to 10")),
+        }
+    };
+    Ok((position(1)?, position(2)?))
+}
+
+fn part1(mut pos1: usize, mut pos2: usize) -> usize {
+    let mut score1 = 0;
+    let mut score2 = 0;
+    let mut roll = (1usize..=100).cycle();
+    let mut roll_count = 0;
+    loop {
+        let mut move1 = 0;
+        for _ in 0..3 {
+            move1 += roll.next().unwrap();
+            roll_count += 1;
+        }
+        pos1 = move_pawn(pos1, move1);
+        score1 += pos1;
+
+        if score1 >= 1000 {
+            break;
+        }
+
+        let mut move2 = 0;
+        for _ in 0..3 {
+            move2 += roll.next().unwrap();
+            roll_count += 1;
+        }
+        pos2 = move_pawn(pos2, move2);
+        score2 += pos2;
+
+        if score2 >= 1000 {
+            break;
+        }
+    }
+
+    let loser = min(score1, score2);
+    loser * roll_count
+}
+
+fn move_pawn(start: usize, distance: usize) -> usize {
+    (start + distance - 1) % 10 + 1
+}
+
+// Brain-computed potential outcomes of three sequential rolls
+const STEP_OUTCOMES: [(usize, usize); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+fn part2(pos1: usize, pos2: usize) -> usize {
+    let mut states_before_player1: HashMap<GameState, usize> = HashMap::new();
+    let mut states_before_player2: HashMap<GameState, usize> = HashMap::new();
+    let initial = GameState::new(pos1 - 1, 0, pos2 - 1, 0);
+    states_before_player1.insert(initial, 1usize);
+
+    let mut player1_wins = 0;
+    let mut player2_wins = 0;
+
+    for step in 1..50 {
+        for (state, universes) in states_before_player1.drain() {
+            for (dist, num) in STEP_OUTCOMES {
+                let new_pos = (state.pos1 + dist) % 10;
+                let new_score = state.score1 + new_pos + 1;
+                let new_universes = universes * num;
+                if new_score >= 21 {
+                    player1_wins += new_universes
+                } else {
+                    let new_state = GameState::new(new_pos, new_score, state.pos2, state.score2);
+                    *states_before_player2.entry(new_state).or_default() += new_universes;
+                }
+            }
+        }
+
+        trace::emit(|| {
+            Event::new("player 1 turn")
+                .step(step)
+                .counter("wins", player1_wins as i64)
+                .counter("states", states_before_player2.len() as i64)
+        });
+
+        for (state, universes) in states_before_player2.drain() {
+            for (dist, num) in STEP_OUTCOMES {
+                let new_pos = (state.pos2 + dist) % 10;
+                let new_score = state.score2 + new_pos + 1;
+                let new_universes = universes * num;
+                if new_score >= 21 {
+                    player2_wins += new_universes
+                } else {
+                    let new_state = GameState::new(state.pos1, state.score1, new_pos, new_score);
+                    *states_before_player1.entry(new_state).or_default() += new_universes;
+                }
+            }
+        }
+
+        trace::emit(|| {
+            Event::new("player 2 turn")
+                .step(step)
+                .counter("wins", player2_wins as i64)
+                .counter("states", states_before_player1.len() as i64)
+        });
+
+        if states_before_player1.is_empty() {
+            break;
+        }
+    }
+
+    trace::emit(|| {
+        Event::new("game over")
+            .counter("player 1 wins", player1_wins as i64)
+            .counter("player 2 wins", player2_wins as i64)
+    });
+
+    std::cmp::max(player1_wins, player2_wins)
+}
+
+#[derive(Hash, PartialEq, Eq, Default, Clone, Copy, Debug)]
+struct GameState {
+    pos1: usize,
+    pos2: usize,
+    score1: usize,
+    score2: usize,
+}
+
+impl GameState {
+    fn new(pos1: usize, score1: usize, pos2: usize, score2: usize) -> Self {
+        Self {
+            pos1,
+            score1,
+            pos2,
+            score2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng::check;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(739785, part1(4, 8));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(864900, Day21::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(444356092776315, part2(4, 8));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(575111835924670, Day21::solve_part2("input.txt"));
+    }
+
+    #[test]
+    fn test_move() {
+        assert_eq!(10, move_pawn(4, 1 + 2 + 3));
+        assert_eq!(3, move_pawn(8, 4 + 5 + 6));
+        assert_eq!(4, move_pawn(10, 7 + 8 + 9));
+    }
+
+    #[test]
+    fn test_parse_positions() {
+        let parse = |text| parse_positions(&InputFile::from_text("example", text));
+        assert_eq!(
+            (4, 8),
+            parse("Player 1 starting position: 4\nPlayer 2 starting position: 8\n").unwrap()
+        );
+        let error =
+            parse("Player 1 starting position: 4\nPlayer 2 starting position: 11").unwrap_err();
+        assert_eq!((2, 29), (error.line, error.column));
+        assert_eq!(0, parse("Player 1 starting position: 4").unwrap_err().line);
+    }
+
+    /// How many universes each player wins in from here, the player about to move
+    /// first, working each of the 27 rolls out in turn.
+    fn dirac_wins(
+        positions: [usize; 2],
+        scores: [usize; 2],
+        seen: &mut HashMap<([usize; 2], [usize; 2]), [usize; 2]>,
+    ) -> [usize; 2] {
+        if let Some(&wins) = seen.get(&(positions, scores)) {
+            return wins;
+        }
+        let mut wins = [0, 0];
+        for rolls in 0..27 {
+            let moved = rolls / 9 + rolls / 3 % 3 + rolls % 3 + 3;
+            let position = move_pawn(positions[0], moved);
+            let score = scores[0] + position;
+            if score >= 21 {
+                wins[0] += 1;
+            } else {
+                let [theirs, ours] = dirac_wins([positions[1], position], [scores[1], score], seen);
+                wins[0] += ours;
+                wins[1] += theirs;
+            }
+        }
+        seen.insert((positions, scores), wins);
+        wins
+    }
+
+    #[test]
+    fn test_random_games() {
+        // the universes won from a game state don't depend on how the game started
+        let mut seen = HashMap::new();
+        check("day21 random games", 20, |rng| {
+            let start = [1 + rng.index(10), 1 + rng.index(10)];
+            let text = format!(
+                "Player 1 starting position: {}\nPlayer 2 starting position: {}\n",
+                start[0], start[1]
+            );
+            let positions = parse_positions(&InputFile::from_text("random", &text)).unwrap();
+            assert_eq!((start[0], start[1]), positions);
+
+            // the deterministic die, a roll at a time
+            let (mut pawns, mut scores, mut rolls) = (start, [0, 0], 0);
+            let mut player = 0;
+            while scores[0] < 1000 && scores[1] < 1000 {
+                let moved = (0..3).map(|_| {
+                    rolls += 1;
+                    (rolls - 1) % 100 + 1
+                });
+                pawns[player] = move_pawn(pawns[player], moved.sum());
+                scores[player] += pawns[player];
+                player = 1 - player;
+            }
+            assert_eq!(
+                scores[player] * rolls,
+                Day21::part1(&positions),
+                "{:?}",
+                start
+            );
+
+            let wins = dirac_wins(start, [0, 0], &mut seen);
+            assert_eq!(
+                wins[0].max(wins[1]),
+                Day21::part2(&positions),
+                "{:?}",
+                start
+            );
+        });
+    }
+}
@@ -0,0 +1,347 @@
+use helpers::{
+    parse::{InputFile, Span},
+    stream::LineReader,
+    ParseError, Solution,
+};
+use std::{
+    cmp::{max, min},
+    io::BufRead,
+};
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+
+    type Input = Vec<Command>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(file_name: &str) -> Result<Vec<Command>, ParseError> {
+        parse_inputs(file_name)
+    }
+
+    fn part1(commands: &Vec<Command>) -> usize {
+        let commands = commands
+            .iter()
+            .filter(|c| c.cube().is_small())
+            .cloned()
+            .collect::<Vec<_>>();
+        reboot_sequence(&commands)
+    }
+
+    fn part2(commands: &Vec<Command>) -> usize {
+        reboot_sequence(commands)
+    }
+
+    fn stream(file_name: &str) -> Option<Result<(usize, usize), ParseError>> {
+        Some(LineReader::open(file_name).and_then(solve_stream))
+    }
+}
+
+fn reboot_sequence(commands: &[Command]) -> usize {
+    let mut changed = 0i64;
+    for i in 0..commands.len() {
+        changed += cubes_changed_by(i, commands);
+    }
+    changed as usize
+}
+
+fn cubes_changed_by(i: usize, commands: &[Command]) -> i64 {
+    let mut changed = 0;
+    let command = &commands[i];
+    match command {
+        Command::On(cuboid) => {
+            changed += cuboid.num_points() as i64;
+            let overlaps = find_overlaps(cuboid, &commands[0..i]);
+            for j in 0..overlaps.len() {
+                changed -= cubes_changed_by(j, &overlaps);
+            }
+        }
+        Command::Off(cuboid) => {
+            let overlaps = find_overlaps(cuboid, &commands[0..i]);
+            for j in 0..overlaps.len() {
+                changed -= cubes_changed_by(j, &overlaps);
+            }
+        }
+    }
+
+    changed
+}
+
+fn find_overlaps(cuboid: &Cuboid, commands: &[Command]) -> Vec<Command> {
+    commands
+        .iter()
+        .cloned()
+        .filter_map(|p| {
+            cuboid.overlaps(p.cube()).map(|c| match p {
+                Command::On(_) => Command::On(c),
+                Command::Off(_) => Command::Off(c),
+            })
+        })
+        .collect()
+}
+
+fn parse_inputs(file_name: &str) -> Result<Vec<Command>, ParseError> {
+    parse_commands(&InputFile::open(file_name)?)
+}
+
+fn parse_commands(input: &InputFile) -> Result<Vec<Command>, ParseError> {
+    input.lines().map(parse_command).collect()
+}
+
+fn parse_command(line: Span) -> Result<Command, ParseError> {
+    let (state, ranges) = line.split_once(" ")?;
+    match state.as_str() {
+        "on" => Ok(Command::On(parse_ranges(ranges)?)),
+        "off" => Ok(Command::Off(parse_ranges(ranges)?)),
+        _ => Err(state.error("`on` or `off`")),
+    }
+}
+
+/// Both parts in one pass. The initialization area is small enough to switch its
+/// cubes one by one as commands come in, but part 2 needs every command to work out
+/// how they overlap.
+pub fn solve_stream<R: BufRead>(lines: LineReader<R>) -> Result<(usize, usize), ParseError> {
+    let mut init_area = InitArea::default();
+    let mut commands = Vec::new();
+    for command in lines.parse_lines(parse_command) {
+        let command = command?;
+        if command.cube().is_small() {
+            init_area.apply(&command);
+        }
+        commands.push(command);
+    }
+    Ok((init_area.count_on(), reboot_sequence(&commands)))
+}
+
+const INIT_SIZE: usize = 101;
+
+/// Every cube from -50 to 50 on each axis.
+struct InitArea {
+    on: Vec<bool>,
+}
+
+impl Default for InitArea {
+    fn default() -> Self {
+        Self {
+            on: vec![false; INIT_SIZE * INIT_SIZE * INIT_SIZE],
+        }
+    }
+}
+
+impl InitArea {
+    fn apply(&mut self, command: &Command) {
+        if command.cube().is_empty() {
+            return;
+        }
+        let state = matches!(command, Command::On(_));
+        let [x, y, z] = command.cube().0;
+        let index = |n: i32| (n + 50) as usize;
+        for i in index(x.0)..=index(x.1) {
+            for j in index(y.0)..=index(y.1) {
+                let row = (i * INIT_SIZE + j) * INIT_SIZE;
+                self.on[row + index(z.0)..=row + index(z.1)].fill(state);
+            }
+        }
+    }
+
+    fn count_on(&self) -> usize {
+        self.on.iter().filter(|&&on| on).count()
+    }
+}
+
+fn parse_ranges(input: Span) -> Result<Cuboid, ParseError> {
+    let mut split = input.split(',');
+    let mut range = |axis| parse_range(input.next_from(&mut split, "a range")?, axis);
+    let cuboid = Cuboid([range("x=")?, range("y=")?, range("z=")?]);
+    input.expect_end(&mut split)?;
+    Ok(cuboid)
+}
+
+fn parse_range(input: Span, axis: &str) -> Result<(i32, i32), ParseError> {
+    let (start, end) = input.strip_prefix(axis)?.split_once("..")?;
+    let start = start.parse("a range start")?;
+    let end = end.parse("a range end")?;
+    Ok((start, end))
+}
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    On(Cuboid),
+    Off(Cuboid),
+}
+
+impl Command {
+    fn cube(&self) -> &Cuboid {
+        match self {
+            Command::On(cube) => cube,
+            Command::Off(cube) => cube,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Cuboid([(i32, i32); 3]);
+
+impl Cuboid {
+    fn iter(&self) -> impl Iterator<Item = &(i32, i32)> {
+        self.0.iter()
+    }
+
+    fn is_small(&self) -> bool {
+        self.iter().all(|r| r.0 >= -50 && r.1 <= 50)
+    }
+
+    /// Whether a range runs backwards, so there are no cubes in it at all.
+    fn is_empty(&self) -> bool {
+        self.iter().any(|r| r.0 > r.1)
+    }
+
+    fn num_points(&self) -> usize {
+        let width = length(&self.0[0]);
+        let height = length(&self.0[1]);
+        let depth = length(&self.0[2]);
+        width * height * depth
+    }
+
+    fn overlaps(&self, other: &Cuboid) -> Option<Cuboid> {
+        let c = &self.0;
+        let o = &other.0;
+        let x = range_overlaps(&c[0], &o[0])?;
+        let y = range_overlaps(&c[1], &o[1])?;
+        let z = range_overlaps(&c[2], &o[2])?;
+        Some(Cuboid([x, y, z]))
+    }
+}
+
+fn length(range: &(i32, i32)) -> usize {
+    (range.1 - range.0 + 1).max(0) as usize
+}
+
+fn range_overlaps(a: &(i32, i32), b: &(i32, i32)) -> Option<(i32, i32)> {
+    let &(a_start, a_end) = a;
+    let &(b_start, b_end) = b;
+    if a_start > b_end || a_end < b_start {
+        None
+    } else {
+        let start = max(a_start, b_start);
+        let end = min(a_end, b_end);
+        Some((start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng::{check, Rng};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_part1_small() {
+        assert_eq!(39, Day22::solve_part1("test_input_small.txt"));
+    }
+
+    #[test]
+    fn test_part1_large() {
+        assert_eq!(590784, Day22::solve_part1("test_input_large.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(542711, Day22::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(2758514936282235, Day22::solve_part2("test_input_full.txt"));
+    }
+
+    #[test]
+    fn test_part2_small() {
+        let commands = parse_inputs("test_input_small.txt")
+            .unwrap()
+            .into_iter()
+            .filter(|c| c.cube().is_small())
+            .collect::<Vec<_>>();
+        assert_eq!(39, reboot_sequence(&commands));
+    }
+
+    #[test]
+    fn test_part2_large() {
+        let commands = parse_inputs("test_input_large.txt")
+            .unwrap()
+            .into_iter()
+            .filter(|c| c.cube().is_small())
+            .collect::<Vec<_>>();
+        assert_eq!(590784, reboot_sequence(&commands));
+    }
+
+    #[test]
+    fn final_part2() {
+        assert_eq!(1160303042684776, Day22::solve_part2("input.txt"));
+    }
+
+    fn random_range(rng: &mut Rng) -> (i32, i32) {
+        let start = rng.range(-6..=6) as i32;
+        (start, start + rng.range(0..=5) as i32)
+    }
+
+    /// Switches every cube one at a time.
+    fn brute_force(commands: &[Command]) -> usize {
+        let mut on = HashSet::new();
+        for command in commands {
+            let [x, y, z] = command.cube().0;
+            for cube in (x.0..=x.1)
+                .flat_map(|x| (y.0..=y.1).map(move |y| (x, y)))
+                .flat_map(|(x, y)| (z.0..=z.1).map(move |z| (x, y, z)))
+            {
+                match command {
+                    Command::On(_) => on.insert(cube),
+                    Command::Off(_) => on.remove(&cube),
+                };
+            }
+        }
+        on.len()
+    }
+
+    #[test]
+    fn test_random_reboots() {
+        check("day22 random reboots", 100, |rng| {
+            let lines = (0..1 + rng.index(10))
+                .map(|_| {
+                    let state = if rng.chance(0.6) { "on" } else { "off" };
+                    let [x, y, z] = [(); 3].map(|_| random_range(rng));
+                    format!(
+                        "{} x={}..{},y={}..{},z={}..{}",
+                        state, x.0, x.1, y.0, y.1, z.0, z.1
+                    )
+                })
+                .collect::<Vec<_>>();
+            let text = lines.join("\n");
+            let commands = parse_commands(&InputFile::from_text("random", &text))
+                .unwrap_or_else(|e| panic!("\n{}", e));
+
+            assert_eq!(
+                brute_force(&commands),
+                reboot_sequence(&commands),
+                "\n{}",
+                text
+            );
+        });
+    }
+
+    #[test]
+    fn test_stream() {
+        let lines = LineReader::open("input.txt").unwrap();
+        assert_eq!(Ok((542711, 1160303042684776)), solve_stream(lines));
+
+        let lines = LineReader::open("test_input_large.txt").unwrap();
+        assert_eq!(590784, solve_stream(lines).unwrap().0);
+
+        // a backwards range switches nothing
+        let text = "on x=0..1,y=0..1,z=5..2\non x=0..0,y=0..0,z=0..0\n";
+        let lines = LineReader::new("backwards", text.as_bytes());
+        assert_eq!(Ok((1, 1)), solve_stream(lines));
+    }
+}
@@ -4,7 +4,7 @@ use crate::Burrow;
 
 impl Display for Burrow {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut chars = vec!['.'; 19];
+        let mut chars = ['.'; 19];
         for (i, &c) in ['A', 'A', 'B', 'B', 'C', 'C', 'D', 'D'].iter().enumerate() {
             let pos = self.pods[i] as usize;
             chars[pos] = c;
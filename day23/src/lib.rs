@@ -0,0 +1,520 @@
+mod display;
+
+use helpers::{
+    parse::InputFile,
+    search::{self, SearchProblem},
+    trace::{self, Event},
+    ParseError, Solution, Unsolved,
+};
+use std::collections::{HashMap, VecDeque};
+
+/// The burrow as the puzzle draws it, with `?` where an amphipod starts.
+const DIAGRAM: [&str; 5] = [
+    "#############",
+    "#...........#",
+    "###?#?#?#?###",
+    "  #?#?#?#?#",
+    "  #########",
+];
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+
+    type Input = Burrow;
+    type Part1 = usize;
+    type Part2 = Unsolved;
+
+    fn parse(file_name: &str) -> Result<Burrow, ParseError> {
+        parse_diagram(&InputFile::open(file_name)?)
+    }
+
+    fn part1(burrow: &Burrow) -> usize {
+        part1(burrow)
+    }
+
+    fn part2(_burrow: &Burrow) -> Unsolved {
+        Unsolved
+    }
+}
+
+/// Reads the amphipods out of the diagram, checking the walls and the empty hallway
+/// around them.
+fn parse_diagram(file: &InputFile) -> Result<Burrow, ParseError> {
+    let mut lines = file.lines();
+    let mut pods = String::new();
+    for row in DIAGRAM {
+        let line = lines
+            .next()
+            .ok_or_else(|| file.error("the rest of the burrow", "end of file"))?;
+        let line = line.slice(0, line.as_str().trim_end().len());
+        if line.as_str().chars().count() != row.len() {
+            return Err(line.error(&format!("`{}`", row)));
+        }
+
+        for ((c, span), expected) in line.chars().zip(row.chars()) {
+            match expected {
+                '?' if ('A'..='D').contains(&c) => pods.push(c),
+                '?' => return Err(span.error("an amphipod from A to D")),
+                _ if c != expected => return Err(span.error(&format!("`{}`", expected))),
+                _ => {}
+            }
+        }
+    }
+    if let Some(extra) = lines.find(|line| !line.trim().is_empty()) {
+        return Err(extra.error("the end of the burrow"));
+    }
+
+    for kind in ['A', 'B', 'C', 'D'] {
+        let count = pods.matches(kind).count();
+        if count != 2 {
+            let found = format!("{} of kind {}", count, kind);
+            return Err(file.error("two amphipods of each kind", &found));
+        }
+    }
+    Ok(Burrow::parse(&pods))
+}
+
+fn part1(burrow: &Burrow) -> usize {
+    let result = search::dijkstra(&Amphipods, burrow.clone()).unwrap();
+    trace::emit(|| {
+        Event::new("search")
+            .counter("cost", result.cost as i64)
+            .counter("expanded", result.stats.expanded as i64)
+            .counter("pushed", result.stats.pushed as i64)
+    });
+    for (i, burrow) in result.path.iter().enumerate() {
+        trace::emit(|| Event::new("burrow").step(i).snapshot(burrow));
+    }
+    result.cost
+}
+
+/// Sorting the amphipods into their rooms, one move at a time.
+struct Amphipods;
+
+impl SearchProblem for Amphipods {
+    type State = Burrow;
+    type Cost = usize;
+
+    fn is_goal(&self, burrow: &Burrow) -> bool {
+        burrow.is_complete()
+    }
+
+    fn neighbors(&self, burrow: &Burrow) -> Vec<(Burrow, usize)> {
+        PodMoves::new(burrow).collect()
+    }
+}
+
+/*
+ 0  1  2  3  4  5  6  7  8  9 10
+      11    12    13    14
+      15    16    17    18
+*/
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Burrow {
+    pods: [u8; 8],
+}
+
+impl Burrow {
+    fn parse(input: &str) -> Self {
+        let mut pods = Vec::new();
+        for kind in ['A', 'B', 'C', 'D'] {
+            pods.extend(input.match_indices(kind).map(|(i, _)| i as u8 + 11))
+        }
+        let pods = pods.try_into().unwrap();
+        Self { pods }
+    }
+
+    fn occupied(&self, position: u8) -> bool {
+        self.pods.contains(&position)
+    }
+
+    fn is_in_room(&self, i: usize) -> bool {
+        self.pods[i] > 10
+    }
+
+    fn is_complete(&self) -> bool {
+        (0..8).all(|i| self.is_pod_home(i))
+    }
+
+    fn is_pod_home(&self, i: usize) -> bool {
+        let slot = i as u8 / 2;
+        let position = self.pods[i];
+        if position == slot + 15 {
+            // if we're in the deeper spot, we're home
+            return true;
+        }
+        if position == slot + 11 {
+            // we're in the outer spot, only home if the other is home too...
+            let other_idx = Self::pair_idx(i);
+            return self.is_pod_home(other_idx);
+        }
+
+        false
+    }
+
+    fn pair_idx(i: usize) -> usize {
+        if i.is_multiple_of(2) {
+            i + 1
+        } else {
+            i - 1
+        }
+    }
+
+    fn entrance(i: usize) -> u8 {
+        (i / 2 + 11) as u8
+    }
+
+    fn steps_from(mut a: u8, mut b: u8) -> u8 {
+        let mut dist = 0;
+        if a > b {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        let outside = match b {
+            11 | 15 => 2,
+            12 | 16 => 4,
+            13 | 17 => 6,
+            14 | 18 => 8,
+            _ => unreachable!(),
+        };
+        dist += (a as i16 - outside).unsigned_abs() as u8;
+        if b > 14 {
+            dist += 2;
+        } else {
+            dist += 1;
+        }
+
+        dist
+    }
+
+    fn cost(i: usize) -> usize {
+        match i / 2 {
+            0 => 1,
+            1 => 10,
+            2 => 100,
+            3 => 1000,
+            _ => unreachable!(),
+        }
+    }
+
+    fn move_pod(&self, i: usize, new_pos: u8) -> Self {
+        let mut new = self.clone();
+        new.pods[i] = new_pos;
+        new
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref OUT_MOVES: HashMap<u8, Vec<Vec<u8>>> = {
+        let mut m = HashMap::new();
+        m.insert(11, vec![vec![1, 0], vec![3, 5, 7, 9, 10]]);
+        m.insert(12, vec![vec![3, 1, 0], vec![5, 7, 9, 10]]);
+        m.insert(13, vec![vec![5, 3, 1, 0], vec![7, 9, 10]]);
+        m.insert(14, vec![vec![7, 5, 3, 1, 0], vec![9, 10]]);
+        m
+    };
+}
+
+struct PodMoves<'b> {
+    burrow: &'b Burrow,
+    current_idx: usize,
+    queue: VecDeque<u8>,
+}
+
+impl<'b> PodMoves<'b> {
+    fn new(burrow: &'b Burrow) -> Self {
+        let mut s = Self {
+            burrow,
+            current_idx: 0,
+            queue: VecDeque::new(),
+        };
+        s.add_possible_positions();
+        s
+    }
+
+    fn add_possible_positions(&mut self) {
+        if self.burrow.is_pod_home(self.current_idx) {
+            return;
+        }
+
+        let mut position = self.burrow.pods[self.current_idx];
+        if self.burrow.is_in_room(self.current_idx) {
+            // in a room
+            if position > 14 {
+                if self.burrow.occupied(position - 4) {
+                    // stuck in room behind another pod
+                    return;
+                }
+
+                position -= 4;
+            }
+
+            let directions = OUT_MOVES.get(&position).unwrap();
+            for direction in directions {
+                let new_positions = direction.iter().take_while(|&&p| !self.burrow.occupied(p));
+                self.queue.extend(new_positions);
+            }
+        } else {
+            // in the hallway
+            let entrance = Burrow::entrance(self.current_idx);
+
+            let direction = OUT_MOVES
+                .get(&entrance)
+                .unwrap()
+                .iter()
+                .find(|&d| d.contains(&position))
+                .unwrap();
+            if direction
+                .iter()
+                .rev()
+                .skip_while(|&&p| p != position)
+                .skip(1)
+                .any(|&p| self.burrow.occupied(p))
+            {
+                // path to entrance is blocked, can't move
+                return;
+            }
+
+            if !self.burrow.occupied(entrance) {
+                // entrance is vacant
+                let other_idx = Burrow::pair_idx(self.current_idx);
+                if self.burrow.is_pod_home(other_idx) {
+                    // pair is home, go to entrance
+                    self.queue.push_back(entrance);
+                } else if !self.burrow.occupied(entrance + 4) {
+                    // bottom spot is open, go there
+                    self.queue.push_back(entrance + 4);
+                }
+            }
+        }
+    }
+
+    fn cost_for_move(&self, new_pos: u8) -> usize {
+        let pos = self.burrow.pods[self.current_idx];
+        let dist = Burrow::steps_from(pos, new_pos) as usize;
+        let cost = Burrow::cost(self.current_idx);
+        dist * cost
+    }
+}
+
+impl<'b> Iterator for PodMoves<'b> {
+    type Item = (Burrow, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_idx > 7 {
+            return None;
+        }
+
+        if let Some(new_pos) = self.queue.pop_front() {
+            return Some((
+                self.burrow.move_pod(self.current_idx, new_pos),
+                self.cost_for_move(new_pos),
+            ));
+        }
+
+        while self.current_idx < 7 && self.queue.is_empty() {
+            self.current_idx += 1;
+            self.add_possible_positions();
+            if let Some(new_pos) = self.queue.pop_front() {
+                return Some((
+                    self.burrow.move_pod(self.current_idx, new_pos),
+                    self.cost_for_move(new_pos),
+                ));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helpers::rng::check;
+    use std::{cmp::Reverse, collections::BinaryHeap};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(12521, Day23::solve_part1("test_input.txt"));
+    }
+
+    #[test]
+    fn final_part1() {
+        assert_eq!(12240, Day23::solve_part1("input.txt"));
+    }
+
+    #[test]
+    fn test_parse_diagram() {
+        let burrow = Day23::parse("test_input.txt").unwrap();
+        assert!(Burrow::parse("BCBDADCA") == burrow);
+
+        let parse = |text: &str| parse_diagram(&InputFile::from_text("example", text));
+        let mut lines = DIAGRAM.map(String::from);
+        lines[2] = String::from("###A#B#C#D###");
+        lines[3] = String::from("  #A#B#C#D#  ");
+        assert!(parse(&lines.join("\n")).unwrap().is_complete());
+
+        lines[1] = String::from("#....A......#");
+        let error = parse(&lines.join("\n")).err().unwrap();
+        assert_eq!((2, 6), (error.line, error.column));
+
+        lines[1] = DIAGRAM[1].to_string();
+        lines[2] = String::from("###E#B#C#D###");
+        assert_eq!(
+            "an amphipod from A to D",
+            parse(&lines.join("\n")).err().unwrap().expected
+        );
+        assert!(parse(&DIAGRAM[..4].join("\n")).is_err());
+    }
+
+    #[test]
+    fn test_solution_path() {
+        let start = Day23::parse("test_input.txt").unwrap();
+        let result = search::dijkstra(&Amphipods, start.clone()).unwrap();
+        assert!(start == result.path[0]);
+        assert!(result.path.last().unwrap().is_complete());
+
+        let path_cost: usize = result
+            .path
+            .windows(2)
+            .map(|pair| {
+                let (_, cost) = PodMoves::new(&pair[0])
+                    .find(|(next, _)| *next == pair[1])
+                    .unwrap();
+                cost
+            })
+            .sum();
+        assert_eq!(result.cost, path_cost);
+    }
+
+    #[test]
+    fn test_is_complete() {
+        let burrow = Burrow::parse("ABCDABCD");
+        assert!(burrow.is_complete());
+
+        let burrow = Burrow::parse("BCBDADCA");
+        assert!(!burrow.is_complete());
+    }
+
+    #[test]
+    fn test_moves() {
+        let burrow = Burrow::parse("BCBDADCA");
+        let moves = PodMoves::new(&burrow);
+        let all_moves = moves.collect::<Vec<_>>();
+        assert_eq!(28, all_moves.len());
+    }
+
+    #[test]
+    fn test_steps_from() {
+        assert_eq!(3, Burrow::steps_from(0, 11));
+        assert_eq!(4, Burrow::steps_from(0, 15));
+
+        assert_eq!(3, Burrow::steps_from(10, 14));
+        assert_eq!(4, Burrow::steps_from(10, 18));
+
+        assert_eq!(9, Burrow::steps_from(0, 14));
+        assert_eq!(10, Burrow::steps_from(0, 18));
+    }
+
+    #[test]
+    fn test_last_move() {
+        let pods = [9u8, 15, 12, 16, 13, 17, 14, 18];
+        let burrow = Burrow { pods };
+        let mut moves = PodMoves::new(&burrow);
+        assert!(moves.any(|(b, cost)| b.is_complete() && cost == 8));
+    }
+
+    /// The least energy it takes to sort the amphipods, trying every move the puzzle
+    /// allows on the cells numbered as in `Burrow`, each either `.` or a kind.
+    fn naive(start: [u8; 19]) -> usize {
+        let column = |room: usize| 2 + 2 * room;
+        let clear = |cells: &[u8; 19], from: usize, to: usize| {
+            let (low, high) = (from.min(to), from.max(to));
+            (low..=high).all(|h| h == from || cells[h] == b'.')
+        };
+
+        let mut best = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((0, start)));
+        while let Some(Reverse((energy, cells))) = queue.pop() {
+            if cells[11..] == *b"ABCDABCD" {
+                return energy;
+            }
+            if best.get(&cells).is_some_and(|&b| b < energy) {
+                continue;
+            }
+            let mut moves = Vec::new();
+            for room in 0..4 {
+                // the top amphipod in a room out into the hallway
+                let Some(depth) = [1, 2].into_iter().find(|d| cells[7 + 4 * d + room] != b'.')
+                else {
+                    continue;
+                };
+                let from = 7 + 4 * depth + room;
+                for hallway in [0, 1, 3, 5, 7, 9, 10] {
+                    if cells[hallway] == b'.' && clear(&cells, column(room), hallway) {
+                        moves.push((from, hallway, depth + column(room).abs_diff(hallway)));
+                    }
+                }
+            }
+            for hallway in 0..11 {
+                // an amphipod in the hallway into its own room, when there are no
+                // others in it
+                let kind = cells[hallway];
+                if kind == b'.' {
+                    continue;
+                }
+                let room = (kind - b'A') as usize;
+                let (top, bottom) = (11 + room, 15 + room);
+                let strangers = [top, bottom]
+                    .iter()
+                    .any(|&c| cells[c] != b'.' && cells[c] != kind);
+                if strangers || cells[top] != b'.' || !clear(&cells, hallway, column(room)) {
+                    continue;
+                }
+                let (to, depth) = if cells[bottom] == b'.' {
+                    (bottom, 2)
+                } else {
+                    (top, 1)
+                };
+                moves.push((hallway, to, depth + column(room).abs_diff(hallway)));
+            }
+
+            for (from, to, steps) in moves {
+                let mut next = cells;
+                next.swap(from, to);
+                let energy = energy + steps * 10usize.pow(u32::from(cells[from] - b'A'));
+                if best.get(&next).is_none_or(|&b| energy < b) {
+                    best.insert(next, energy);
+                    queue.push(Reverse((energy, next)));
+                }
+            }
+        }
+        unreachable!("the amphipods can always be sorted")
+    }
+
+    #[test]
+    fn test_random_burrows() {
+        // each search takes a while without optimisations, so there are only a few
+        check("day23 random burrows", 3, |rng| {
+            let mut pods = *b"AABBCCDD";
+            rng.shuffle(&mut pods);
+            let pods = String::from_utf8(pods.to_vec()).unwrap();
+            let mut lines = DIAGRAM.map(String::from);
+            for (row, pods) in [(2, &pods[..4]), (3, &pods[4..])] {
+                for pod in pods.chars() {
+                    lines[row] = lines[row].replacen('?', &pod.to_string(), 1);
+                }
+            }
+            let text = lines.join("\n");
+            let burrow = parse_diagram(&InputFile::from_text("random", &text)).unwrap();
+            assert!(Burrow::parse(&pods) == burrow, "\n{}", text);
+
+            let mut cells = [b'.'; 19];
+            cells[11..].copy_from_slice(pods.as_bytes());
+            assert_eq!(naive(cells), part1(&burrow), "\n{}", text);
+        });
+    }
+}
@@ -0,0 +1,378 @@
+use crate::{
+    grid::Grid,
+    trace::{Context, Event, Sink},
+};
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// A trace sink that keeps the frame of every event that has one.
+#[derive(Clone, Default)]
+pub struct Recorder {
+    frames: Arc<Mutex<Vec<Grid<u8>>>>,
+}
+
+impl Recorder {
+    /// The frames recorded so far, leaving the recorder empty.
+    pub fn take(&self) -> Vec<Grid<u8>> {
+        std::mem::take(&mut *self.frames.lock().unwrap())
+    }
+}
+
+impl Sink for Recorder {
+    fn record(&mut self, _context: Option<Context>, event: &Event) {
+        if let Some(frame) = &event.frame {
+            self.frames.lock().unwrap().push(frame.clone());
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Played back in the terminal with background colours.
+    Ansi,
+    /// One black and white image per frame.
+    Pbm,
+    /// One greyscale image per frame.
+    Pgm,
+    Gif,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "ansi" => Ok(Format::Ansi),
+            "pbm" => Ok(Format::Pbm),
+            "pgm" => Ok(Format::Pgm),
+            "gif" => Ok(Format::Gif),
+            _ => Err(format!(
+                "`{}` isn't a frame format, use ansi, pbm, pgm or gif",
+                s
+            )),
+        }
+    }
+}
+
+/// Grows every frame to the size of the largest, keeping each one centred, so a
+/// simulation that grows or shrinks doesn't jump around.
+pub fn pad(frames: &[Grid<u8>]) -> Vec<Grid<u8>> {
+    let width = frames.iter().map(Grid::width).max().unwrap_or(0);
+    let height = frames.iter().map(Grid::height).max().unwrap_or(0);
+    frames
+        .iter()
+        .map(|frame| {
+            let left = ((width - frame.width()) / 2) as isize;
+            let top = ((height - frame.height()) / 2) as isize;
+            Grid::from_fn(width, height, |(x, y)| {
+                frame.get_or(x as isize - left, y as isize - top, 0)
+            })
+        })
+        .collect()
+}
+
+/// Blows each cell up into a `scale` by `scale` square.
+pub fn scale(frame: &Grid<u8>, scale: usize) -> Grid<u8> {
+    Grid::from_fn(frame.width() * scale, frame.height() * scale, |(x, y)| {
+        frame[(x / scale, y / scale)]
+    })
+}
+
+/// Plays the frames in the terminal, each cell two characters wide so it's roughly
+/// square, waiting `delay` between frames.
+pub fn write_ansi<W: Write>(
+    frames: &[Grid<u8>],
+    out: &mut W,
+    delay: Duration,
+) -> std::io::Result<()> {
+    for (i, frame) in pad(frames).iter().enumerate() {
+        if i > 0 {
+            std::thread::sleep(delay);
+        }
+        // home the cursor and clear the screen
+        let mut text = String::from("\x1b[H\x1b[2J");
+        for row in frame.rows() {
+            for &level in row {
+                // the 24 greys at the end of the 256 colour palette
+                text.push_str(&format!("\x1b[48;5;{}m  ", 232 + level as u32 * 23 / 255));
+            }
+            text.push_str("\x1b[0m\n");
+        }
+        out.write_all(text.as_bytes())?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// Writes `frame_0001.pbm` (or `.pgm`) and so on into `dir`, creating it if need be,
+/// and returns the paths written. In PBM, cells of 128 and up are drawn in black ink.
+pub fn write_pnm(
+    frames: &[Grid<u8>],
+    dir: &Path,
+    format: Format,
+    scale_by: usize,
+) -> std::io::Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+    let mut paths = Vec::new();
+    for (i, frame) in pad(frames).iter().enumerate() {
+        let frame = scale(frame, scale_by);
+        let (extension, bytes) = match format {
+            Format::Pbm => ("pbm", pbm(&frame)),
+            _ => ("pgm", pgm(&frame)),
+        };
+        let path = dir.join(format!("frame_{:04}.{}", i + 1, extension));
+        std::fs::write(&path, bytes)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+fn pbm(frame: &Grid<u8>) -> Vec<u8> {
+    let mut bytes = format!("P4\n{} {}\n", frame.width(), frame.height()).into_bytes();
+    for row in frame.rows() {
+        // eight pixels to a byte, most significant bit first, each row padded out
+        for pixels in row.chunks(8) {
+            let byte = pixels
+                .iter()
+                .enumerate()
+                .filter(|(_, &level)| level >= 128)
+                .fold(0u8, |byte, (i, _)| byte | 0x80 >> i);
+            bytes.push(byte);
+        }
+    }
+    bytes
+}
+
+fn pgm(frame: &Grid<u8>) -> Vec<u8> {
+    let mut bytes = format!("P5\n{} {}\n255\n", frame.width(), frame.height()).into_bytes();
+    bytes.extend(frame.iter());
+    bytes
+}
+
+/// An animated GIF that loops forever, in 256 shades of grey.
+pub fn encode_gif(frames: &[Grid<u8>], scale_by: usize, delay: Duration) -> Vec<u8> {
+    let frames = pad(frames)
+        .iter()
+        .map(|frame| scale(frame, scale_by))
+        .collect::<Vec<_>>();
+    let width = frames.first().map_or(0, Grid::width) as u16;
+    let height = frames.first().map_or(0, Grid::height) as u16;
+    let delay = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend(width.to_le_bytes());
+    gif.extend(height.to_le_bytes());
+    // a global colour table of 256 entries, no background colour or aspect ratio
+    gif.extend([0xf7, 0, 0]);
+    for grey in 0..=255u8 {
+        gif.extend([grey, grey, grey]);
+    }
+    // the Netscape extension, which makes the animation loop
+    gif.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+    for frame in frames {
+        // graphic control: leave the frame in place, then wait `delay` hundredths
+        gif.extend([0x21, 0xf9, 0x04, 0x04]);
+        gif.extend(delay.to_le_bytes());
+        gif.extend([0, 0]);
+
+        gif.push(0x2c);
+        gif.extend([0, 0, 0, 0]);
+        gif.extend(width.to_le_bytes());
+        gif.extend(height.to_le_bytes());
+        gif.push(0);
+
+        gif.push(MIN_CODE_SIZE);
+        for block in lzw(frame.iter().copied()).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0);
+    }
+
+    gif.push(0x3b);
+    gif
+}
+
+/// Every pixel value is a code of its own.
+const MIN_CODE_SIZE: u8 = 8;
+const CLEAR: u16 = 1 << MIN_CODE_SIZE;
+const END: u16 = CLEAR + 1;
+const MAX_CODE: u16 = 4095;
+
+/// GIF's variable width LZW compression.
+fn lzw<I: Iterator<Item = u8>>(pixels: I) -> Vec<u8> {
+    let mut out = BitWriter::default();
+    let mut codes: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next_code = END + 1;
+    let mut width = MIN_CODE_SIZE + 1;
+
+    out.write(CLEAR, width);
+    let mut prefix: Option<u16> = None;
+    for pixel in pixels {
+        let current = match prefix {
+            None => {
+                prefix = Some(pixel as u16);
+                continue;
+            }
+            Some(current) => current,
+        };
+        if let Some(&code) = codes.get(&(current, pixel)) {
+            prefix = Some(code);
+            continue;
+        }
+
+        out.write(current, width);
+        if next_code < MAX_CODE {
+            codes.insert((current, pixel), next_code);
+            // the decoder is a code behind, so widen as soon as this code needs it
+            if next_code == 1 << width {
+                width += 1;
+            }
+            next_code += 1;
+        } else {
+            out.write(CLEAR, width);
+            codes.clear();
+            next_code = END + 1;
+            width = MIN_CODE_SIZE + 1;
+        }
+        prefix = Some(pixel as u16);
+    }
+
+    if let Some(current) = prefix {
+        out.write(current, width);
+    }
+    out.write(END, width);
+    out.finish()
+}
+
+/// Packs codes least significant bit first, as GIF wants.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += width;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Undoes `lzw`, to check it against.
+    fn unlzw(bytes: &[u8]) -> Vec<u8> {
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            *table = (0..=END).map(|c| vec![c as u8]).collect();
+        };
+        reset(&mut table);
+
+        let (mut position, mut width) = (0, MIN_CODE_SIZE + 1);
+        let mut read = |width: u8| {
+            let mut code = 0u16;
+            for bit in 0..width as usize {
+                let i = position + bit;
+                code |= (((bytes[i / 8] >> (i % 8)) & 1) as u16) << bit;
+            }
+            position += width as usize;
+            code
+        };
+
+        let mut pixels = Vec::new();
+        let mut previous: Option<Vec<u8>> = None;
+        loop {
+            let code = read(width);
+            if code == CLEAR {
+                reset(&mut table);
+                width = MIN_CODE_SIZE + 1;
+                previous = None;
+                continue;
+            }
+            if code == END {
+                return pixels;
+            }
+
+            let entry = match (table.get(code as usize), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [previous.clone(), vec![previous[0]]].concat(),
+                (None, None) => panic!("code {} before any pixels", code),
+            };
+            if let Some(previous) = previous {
+                table.push([previous, vec![entry[0]]].concat());
+                if table.len() == 1 << width && width < 12 {
+                    width += 1;
+                }
+            }
+            pixels.extend(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        let repetitive = (0..20_000).map(|i| (i / 7 % 3) as u8).collect::<Vec<_>>();
+        assert_eq!(repetitive, unlzw(&lzw(repetitive.iter().copied())));
+
+        // enough variety to fill the code table and force a clear
+        let mut state = 1u32;
+        let noisy = (0..50_000)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                (state >> 16) as u8
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(noisy, unlzw(&lzw(noisy.iter().copied())));
+    }
+
+    #[test]
+    fn test_gif_layout() {
+        let frames = vec![Grid::filled(2, 1, 255), Grid::filled(3, 3, 0)];
+        let gif = encode_gif(&frames, 2, Duration::from_millis(250));
+        assert_eq!(b"GIF89a", &gif[..6]);
+        // padded to 3x3, then scaled up to 6x6
+        assert_eq!([6, 0, 6, 0], gif[6..10]);
+        assert_eq!(2, gif.windows(2).filter(|w| w == &[0x21, 0xf9]).count());
+        assert_eq!(Some(&0x3b), gif.last());
+    }
+
+    #[test]
+    fn test_pad_and_pnm() {
+        let frames = vec![Grid::filled(1, 1, 200), Grid::filled(3, 1, 0)];
+        let padded = pad(&frames);
+        assert_eq!(
+            vec![0, 200, 0],
+            padded[0].iter().copied().collect::<Vec<_>>()
+        );
+
+        let frame = Grid::from_rows(vec![vec![255, 0, 0, 0, 0, 0, 0, 0, 255]]);
+        assert_eq!(b"P4\n9 1\n\x80\x80", pbm(&frame).as_slice());
+        assert_eq!(
+            b"P5\n1 1\n255\n\x07",
+            pgm(&Grid::filled(1, 1, 7)).as_slice()
+        );
+    }
+}
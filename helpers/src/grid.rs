@@ -0,0 +1,278 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+/// An `(x, y)` position in a grid, with `(0, 0)` in the top left corner.
+pub type Point = (usize, usize);
+
+/// Offsets to the 4 orthogonal neighbours, in reading order.
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets to all 8 surrounding neighbours, in reading order.
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid of cells stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "grid cells don't fill the grid"
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Builds a grid from rows that must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "grid rows have different lengths"
+        );
+        Self::new(width, height, rows.into_iter().flatten().collect())
+    }
+
+    pub fn from_fn<F: FnMut(Point) -> T>(width: usize, height: usize, mut f: F) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Self::new(width, height, cells)
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Total number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn in_bounds(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn get(&self, (x, y): Point) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): Point) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Bounds-checked access with coordinates that may be negative.
+    pub fn get_signed(&self, x: isize, y: isize) -> Option<&T> {
+        if self.in_bounds(x, y) {
+            self.get((x as usize, y as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Treats the grid as sitting in an infinite plane filled with `background`.
+    pub fn get_or(&self, x: isize, y: isize, background: T) -> T
+    where
+        T: Copy,
+    {
+        self.get_signed(x, y).copied().unwrap_or(background)
+    }
+
+    /// Treats the grid as tiling an infinite plane, returning the cell under `(x, y)`
+    /// along with which copy of the grid it falls in (`(0, 0)` being the original).
+    pub fn tiled(&self, x: isize, y: isize) -> (&T, (isize, isize)) {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let cell = &self[(x.rem_euclid(width) as usize, y.rem_euclid(height) as usize)];
+        (cell, (x.div_euclid(width), y.div_euclid(height)))
+    }
+
+    /// Every point in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every cell paired with its point, in reading order.
+    pub fn cells(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        self.points().zip(self.cells.iter_mut())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() panics on 0, and an empty grid has no rows anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The in-bounds points above, left, right and below `point`.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.offsets(point, &ORTHOGONAL)
+    }
+
+    /// The in-bounds points surrounding `point`, diagonals included.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.offsets(point, &SURROUNDING)
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    fn offsets(
+        &self,
+        (x, y): Point,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let (x, y) = (x as isize, y as isize);
+        offsets
+            .iter()
+            .map(move |(dx, dy)| (x + dx, y + dy))
+            .filter(move |&(x, y)| x >= 0 && y >= 0 && x < width && y < height)
+            .map(|(x, y)| (x as usize, y as usize))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", point))
+    }
+}
+
+/// Renders each row on its own line, with the cells written back to back.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]])
+    }
+
+    #[test]
+    fn test_access() {
+        let mut grid = grid();
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(6, grid[(2, 1)]);
+        assert_eq!(None, grid.get((3, 0)));
+        assert_eq!(None, grid.get_signed(-1, 0));
+        assert_eq!(0, grid.get_or(5, 5, 0));
+
+        grid[(0, 1)] = 9;
+        assert_eq!(&[9, 5, 6], grid.row(1));
+        assert_eq!(vec![&2, &5], grid.column(1).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = grid();
+        assert_eq!(
+            vec![(0, 0), (2, 0), (1, 1)],
+            grid.neighbors4((1, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(1, 0), (0, 1), (1, 1)],
+            grid.neighbors8((0, 0)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_tiled() {
+        let grid = grid();
+        assert_eq!((&1, (0, 0)), grid.tiled(0, 0));
+        assert_eq!((&6, (1, 1)), grid.tiled(5, 3));
+        assert_eq!((&3, (-1, -1)), grid.tiled(-1, -2));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("123\n456\n", grid().to_string());
+        let from_fn = Grid::from_fn(2, 2, |(x, y)| x + y * 2);
+        assert_eq!("01\n23\n", from_fn.to_string());
+    }
+}
@@ -0,0 +1,368 @@
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+    net::TcpStream,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// Where puzzle inputs are downloaded from unless `AOC_BASE_URL` says otherwise.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2021";
+
+/// Finds the input for a day in a cache directory, downloading it the first time
+/// it's needed. The cache keeps the same layout as the workspace, `dayNN/input.txt`,
+/// so running from the workspace root uses the inputs committed alongside each day.
+#[derive(Debug, Clone)]
+pub struct InputSource {
+    cache_dir: PathBuf,
+    base_url: String,
+    session: Option<String>,
+}
+
+impl InputSource {
+    pub fn new<P: Into<PathBuf>>(cache_dir: P) -> Self {
+        Self {
+            cache_dir: cache_dir.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: None,
+        }
+    }
+
+    /// Configured from the environment:
+    /// - `AOC_INPUT_DIR` is the cache directory, the current directory by default.
+    /// - `AOC_BASE_URL` replaces `DEFAULT_BASE_URL`.
+    /// - `AOC_SESSION` is the session token sent when downloading.
+    pub fn from_env() -> Self {
+        let cache_dir = std::env::var_os("AOC_INPUT_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("."));
+        let mut source = Self::new(cache_dir);
+        if let Ok(base_url) = std::env::var("AOC_BASE_URL") {
+            source = source.with_base_url(&base_url);
+        }
+        if let Ok(session) = std::env::var("AOC_SESSION") {
+            source = source.with_session(&session);
+        }
+        source
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_session(mut self, session: &str) -> Self {
+        self.session = Some(session.trim().to_string());
+        self
+    }
+
+    /// Where a day's input lives in the cache, whether or not it's there yet.
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(format!("day{:02}", day))
+            .join("input.txt")
+    }
+
+    pub fn url(&self, day: u8) -> String {
+        format!("{}/day/{}/input", self.base_url, day)
+    }
+
+    /// The path to a day's input, downloading it into the cache if it isn't there.
+    /// Once cached an input is never downloaded again.
+    pub fn resolve(&self, day: u8) -> Result<PathBuf, InputError> {
+        let path = self.path(day);
+        if path.exists() {
+            return Ok(path);
+        }
+
+        let session = self
+            .session
+            .as_ref()
+            .ok_or_else(|| InputError::NoSession { path: path.clone() })?;
+        // it goes into a header and a curl config line, where a newline would start
+        // another one
+        if session.chars().any(char::is_control) {
+            return Err(InputError::BadSession);
+        }
+        let input = fetch(&self.url(day), session)?;
+        save(&path, &input)?;
+        Ok(path)
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// The input isn't cached, and there's no session token to download it with.
+    NoSession {
+        path: PathBuf,
+    },
+    /// The session token has control characters in it, so it can't be sent.
+    BadSession,
+    Fetch {
+        url: String,
+        message: String,
+    },
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+impl std::error::Error for InputError {}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NoSession { path } => write!(
+                f,
+                "{} doesn't exist, and AOC_SESSION isn't set to download it",
+                path.display()
+            ),
+            InputError::BadSession => {
+                write!(f, "AOC_SESSION can't contain control characters")
+            }
+            InputError::Fetch { url, message } => {
+                write!(f, "couldn't download {}: {}", url, message)
+            }
+            InputError::Io { path, error } => {
+                write!(f, "couldn't save {}: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+fn fetch(url: &str, session: &str) -> Result<String, InputError> {
+    let error = |message: String| InputError::Fetch {
+        url: url.to_string(),
+        message,
+    };
+
+    if let Some(address) = url.strip_prefix("http://") {
+        http_get(address, session).map_err(error)
+    } else if url.starts_with("https://") {
+        // there's no TLS in std, so leave https to curl
+        curl(url, session).map_err(error)
+    } else {
+        Err(error(String::from(
+            "only http:// and https:// URLs are supported",
+        )))
+    }
+}
+
+/// A bare-bones HTTP/1.0 GET, which is all a local stand-in server needs. Asking for
+/// 1.0 means the response can't be chunked.
+fn http_get(address: &str, session: &str) -> Result<String, String> {
+    let (host, path) = match address.find('/') {
+        Some(i) => address.split_at(i),
+        None => (address, "/"),
+    };
+    let authority = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+
+    let mut stream = TcpStream::connect(&authority).map_err(|e| e.to_string())?;
+    write!(
+        stream,
+        "GET {} HTTP/1.0\r\nHost: {}\r\nCookie: session={}\r\nConnection: close\r\n\r\n",
+        path, host, session
+    )
+    .map_err(|e| e.to_string())?;
+
+    let mut response = Vec::new();
+    stream
+        .read_to_end(&mut response)
+        .map_err(|e| e.to_string())?;
+    let response = String::from_utf8(response).map_err(|_| "the input isn't UTF-8")?;
+
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or("the response has no body")?;
+    let status = head.lines().next().unwrap_or_default();
+    match status.split_whitespace().nth(1) {
+        Some("200") => Ok(body.to_string()),
+        _ => Err(format!("the server replied `{}`", status)),
+    }
+}
+
+/// The session goes to curl as a config file on stdin rather than an argument, where
+/// anyone on the machine could read it from the process list.
+fn curl(url: &str, session: &str) -> Result<String, String> {
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--config", "-"])
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("couldn't run curl: {}", e))?;
+
+    let mut stdin = child.stdin.take().expect("curl's stdin is piped");
+    let written = writeln!(stdin, "cookie = \"session={}\"", curl_quoted(session));
+    drop(stdin);
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("couldn't run curl: {}", e))?;
+    written.map_err(|e| format!("couldn't pass the session to curl: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    String::from_utf8(output.stdout).map_err(|_| String::from("the input isn't UTF-8"))
+}
+
+/// Escapes a value for a double-quoted string in a curl config file.
+fn curl_quoted(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Writes to a temporary file first, so an interrupted download doesn't leave a
+/// partial input in the cache.
+fn save(path: &Path, input: &str) -> Result<(), InputError> {
+    let io_error = |error| InputError::Io {
+        path: path.to_path_buf(),
+        error,
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    let partial = path.with_extension("txt.part");
+    std::fs::write(&partial, input).map_err(io_error)?;
+    std::fs::rename(&partial, path).map_err(io_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    /// A local stand-in for the puzzle server that answers every request with the
+    /// same response and remembers the request lines and cookies it was sent.
+    struct StandIn {
+        base_url: String,
+        requests: Arc<Mutex<Vec<(String, String)>>>,
+    }
+
+    impl StandIn {
+        fn start(status: &'static str, body: &'static str) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let base_url = format!("http://{}/2021", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+
+            let seen = Arc::clone(&requests);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let mut stream = stream.unwrap();
+                    let mut lines = BufReader::new(stream.try_clone().unwrap()).lines();
+                    let request = lines.next().unwrap().unwrap();
+                    let mut cookie = String::new();
+                    for line in lines.map(Result::unwrap) {
+                        if line.is_empty() {
+                            break;
+                        }
+                        if let Some(value) = line.strip_prefix("Cookie: ") {
+                            cookie = value.to_string();
+                        }
+                    }
+                    seen.lock().unwrap().push((request, cookie));
+                    write!(stream, "HTTP/1.0 {}\r\n\r\n{}", status, body).unwrap();
+                }
+            });
+
+            Self { base_url, requests }
+        }
+
+        fn requests(&self) -> Vec<(String, String)> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-input-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_downloads_once() {
+        let server = StandIn::start("200 OK", "1,2,3\n");
+        let dir = cache_dir("downloads-once");
+        let source = InputSource::new(&dir)
+            .with_base_url(&server.base_url)
+            .with_session("s3cret");
+
+        let path = source.resolve(7).unwrap();
+        assert_eq!(dir.join("day07").join("input.txt"), path);
+        assert_eq!("1,2,3\n", std::fs::read_to_string(&path).unwrap());
+        assert_eq!(path, source.resolve(7).unwrap());
+        assert_eq!(
+            vec![(
+                String::from("GET /2021/day/7/input HTTP/1.0"),
+                String::from("session=s3cret")
+            )],
+            server.requests()
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_failed_download_isnt_cached() {
+        let server = StandIn::start(
+            "404 Not Found",
+            "Please don't repeatedly request this endpoint",
+        );
+        let dir = cache_dir("failed-download");
+        let source = InputSource::new(&dir)
+            .with_base_url(&server.base_url)
+            .with_session("s3cret");
+
+        let error = source.resolve(30).unwrap_err().to_string();
+        assert!(error.contains("404 Not Found"), "{}", error);
+        assert!(!source.path(30).exists());
+        source.resolve(30).unwrap_err();
+        assert_eq!(2, server.requests().len());
+    }
+
+    #[test]
+    fn test_session_with_control_characters() {
+        let server = StandIn::start("200 OK", "199\n");
+        let dir = cache_dir("bad-session");
+        let source = InputSource::new(&dir)
+            .with_base_url(&server.base_url)
+            .with_session("s3cret\r\nX-Injected: yes");
+
+        assert!(matches!(source.resolve(1), Err(InputError::BadSession)));
+        assert!(server.requests().is_empty());
+        assert!(!source.path(1).exists());
+    }
+
+    #[test]
+    fn test_curl_quoted() {
+        assert_eq!("abc123", curl_quoted("abc123"));
+        assert_eq!(r#"a\"b\\c"#, curl_quoted(r#"a"b\c"#));
+    }
+
+    #[test]
+    fn test_needs_session() {
+        let dir = cache_dir("needs-session");
+        let source = InputSource::new(&dir);
+        assert!(matches!(
+            source.resolve(1),
+            Err(InputError::NoSession { .. })
+        ));
+
+        // an input that's already cached doesn't need one
+        std::fs::create_dir_all(dir.join("day01")).unwrap();
+        std::fs::write(source.path(1), "199\n").unwrap();
+        assert_eq!(source.path(1), source.resolve(1).unwrap());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
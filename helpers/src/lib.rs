@@ -2,6 +2,21 @@ use std::fs::File;
 use std::io::{self, BufRead, BufReader};
 use std::path::Path;
 
+pub mod frames;
+pub mod grid;
+pub mod input;
+pub mod parallel;
+pub mod parse;
+pub mod rng;
+pub mod search;
+mod solution;
+pub mod stream;
+pub mod table;
+pub mod trace;
+
+pub use parse::ParseError;
+pub use solution::*;
+
 pub fn read_lines<P: AsRef<Path>>(path: P) -> io::Result<impl Iterator<Item = io::Result<String>>> {
     let file = File::open(path.as_ref())?;
     let reader = BufReader::new(file);
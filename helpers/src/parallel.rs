@@ -0,0 +1,243 @@
+use crate::trace;
+use std::{
+    cell::Cell,
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+/// The thread count set with `set_threads`, where 0 means every available core.
+/// Everything runs serially until it's set.
+static THREADS: AtomicUsize = AtomicUsize::new(1);
+
+thread_local! {
+    /// Set on worker threads, so work they split up again runs serially rather than
+    /// starting threads of its own.
+    static WORKER: Cell<bool> = const { Cell::new(false) };
+    /// A count that `with_threads` uses in place of `THREADS` on this thread.
+    static OVERRIDE: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Splits work across `threads` threads from now on. 1 runs everything serially, and
+/// 0 goes back to using every available core.
+pub fn set_threads(threads: usize) {
+    THREADS.store(threads, Ordering::Relaxed);
+}
+
+/// Picks the thread count from `AOC_THREADS`, if it's set.
+pub fn init_from_env() -> Result<(), String> {
+    match std::env::var("AOC_THREADS") {
+        Ok(threads) => {
+            let threads = threads
+                .trim()
+                .parse()
+                .map_err(|_| format!("AOC_THREADS must be a whole number, not `{}`", threads))?;
+            set_threads(threads);
+            Ok(())
+        }
+        Err(_) => Ok(()),
+    }
+}
+
+/// How many threads work is split across.
+pub fn threads() -> usize {
+    let threads = OVERRIDE
+        .with(Cell::get)
+        .unwrap_or_else(|| THREADS.load(Ordering::Relaxed));
+    if threads > 0 {
+        threads
+    } else {
+        thread::available_parallelism().map_or(1, |n| n.get())
+    }
+}
+
+/// Runs `f` with work on this thread split across `threads` threads, whatever the
+/// global setting is. Handy for comparing the serial and parallel paths.
+pub fn with_threads<R, F: FnOnce() -> R>(threads: usize, f: F) -> R {
+    let _restore = RestoreOverride(OVERRIDE.with(|o| o.replace(Some(threads))));
+    f()
+}
+
+/// Puts back the override `with_threads` replaced, even if `f` panics.
+struct RestoreOverride(Option<usize>);
+
+impl Drop for RestoreOverride {
+    fn drop(&mut self) {
+        OVERRIDE.with(|o| o.set(self.0));
+    }
+}
+
+/// `items.iter().map(f).collect()`, with the items shared out between threads as
+/// they become free. The results are in the same order as the items.
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let workers = workers_for(items.len());
+    if workers <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results = on_workers(workers, || {
+        let mut done = Vec::new();
+        loop {
+            let i = next.fetch_add(1, Ordering::Relaxed);
+            if i >= items.len() {
+                return done;
+            }
+            done.push((i, f(&items[i])));
+        }
+    });
+    results.sort_unstable_by_key(|&(i, _)| i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// The first item for which `f` returns something, with its index, like
+/// `items.iter().enumerate().find_map(..)`. Items are tried in order, several at a
+/// time, and none after a match are started.
+pub fn find_first<T, R, F>(items: &[T], f: F) -> Option<(usize, R)>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Option<R> + Sync,
+{
+    let workers = workers_for(items.len());
+    if workers <= 1 {
+        return items
+            .iter()
+            .enumerate()
+            .find_map(|(i, item)| f(item).map(|result| (i, result)));
+    }
+
+    let next = AtomicUsize::new(0);
+    let first = AtomicUsize::new(usize::MAX);
+    let found = on_workers(workers, || {
+        let mut found = Vec::new();
+        loop {
+            let i = next.fetch_add(1, Ordering::Relaxed);
+            if i >= items.len() || i > first.load(Ordering::Relaxed) {
+                return found;
+            }
+            if let Some(result) = f(&items[i]) {
+                first.fetch_min(i, Ordering::Relaxed);
+                found.push((i, result));
+            }
+        }
+    });
+    found.into_iter().min_by_key(|&(i, _)| i)
+}
+
+fn workers_for(items: usize) -> usize {
+    if WORKER.with(Cell::get) {
+        1
+    } else {
+        threads().min(items)
+    }
+}
+
+/// Runs `work` on `workers` scoped threads and gathers what they return. A panic on
+/// any of them is passed on once they've all finished.
+fn on_workers<R, F>(workers: usize, work: F) -> Vec<R>
+where
+    R: Send,
+    F: Fn() -> Vec<R> + Sync,
+{
+    let context = trace::context();
+    thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    WORKER.with(|w| w.set(true));
+                    trace::set_context(context);
+                    work()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut results = Vec::new();
+        let mut panicked = None;
+        for handle in handles {
+            match handle.join() {
+                Ok(done) => results.extend(done),
+                Err(payload) => panicked = Some(payload),
+            }
+        }
+        if let Some(payload) = panicked {
+            panic::resume_unwind(payload);
+        }
+        results
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::HashSet, sync::Mutex};
+
+    #[test]
+    fn test_map() {
+        let items = (0..1000).collect::<Vec<u64>>();
+        let serial = with_threads(1, || map(&items, |n| n * n));
+        let parallel = with_threads(4, || map(&items, |n| n * n));
+        assert_eq!(items.iter().map(|n| n * n).collect::<Vec<_>>(), serial);
+        assert_eq!(serial, parallel);
+        assert!(with_threads(4, || map(&[] as &[u64], |n| *n)).is_empty());
+    }
+
+    #[test]
+    fn test_uses_threads() {
+        let seen = Mutex::new(HashSet::new());
+        let items = (0..8).collect::<Vec<_>>();
+        let nested = with_threads(4, || {
+            map(&items, |_| {
+                std::thread::sleep(std::time::Duration::from_millis(20));
+                seen.lock().unwrap().insert(std::thread::current().id());
+                map(&items, |_| std::thread::current().id())
+            })
+        });
+        assert!(seen.lock().unwrap().len() > 1);
+        // nested work stays on the worker it was given to
+        for ids in nested {
+            assert!(ids.iter().all(|id| id == &ids[0]));
+        }
+    }
+
+    #[test]
+    fn test_find_first() {
+        let items = (0..500).collect::<Vec<u32>>();
+        let even_square = |&n: &u32| Some(n * n).filter(|&sq| sq > 1000 && sq % 2 == 0);
+        assert_eq!(
+            Some((32, 1024)),
+            with_threads(1, || find_first(&items, even_square))
+        );
+        assert_eq!(
+            Some((32, 1024)),
+            with_threads(8, || find_first(&items, even_square))
+        );
+        assert_eq!(None, with_threads(8, || find_first(&items, |_| None::<()>)));
+    }
+
+    #[test]
+    fn test_override_survives_panics() {
+        let before = threads();
+        let result = panic::catch_unwind(|| with_threads(before + 3, || panic!("oops")));
+        assert!(result.is_err());
+        assert_eq!(before, threads());
+    }
+
+    #[test]
+    #[should_panic(expected = "item 7")]
+    fn test_passes_on_panics() {
+        let items = (0..10).collect::<Vec<_>>();
+        with_threads(3, || {
+            map(&items, |&n| {
+                if n == 7 {
+                    panic!("item {}", n);
+                }
+            })
+        });
+    }
+}
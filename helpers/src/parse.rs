@@ -0,0 +1,421 @@
+use crate::grid::Grid;
+use std::{fmt::Display, str::FromStr, sync::OnceLock};
+
+/// The file name that means "read standard input instead".
+pub const STDIN: &str = "-";
+
+/// A puzzle input that couldn't be parsed, pointing at where it went wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file_name: String,
+    /// 1-based line number, or 0 when the problem is with the file as a whole.
+    pub line: usize,
+    /// 1-based column, counted in chars.
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+    source_line: String,
+    width: usize,
+}
+
+impl ParseError {
+    pub(crate) fn for_file(file_name: &str, expected: &str, found: String) -> Self {
+        Self {
+            file_name: file_name.to_string(),
+            line: 0,
+            column: 0,
+            expected: expected.to_string(),
+            found,
+            source_line: String::new(),
+            width: 0,
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "error: expected {}, found {}", self.expected, self.found)?;
+        if self.line == 0 {
+            return write!(f, " --> {}", self.file_name);
+        }
+
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(
+            f,
+            "{} --> {}:{}:{}",
+            gutter, self.file_name, self.line, self.column
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(std::cmp::max(1, self.width))
+        )
+    }
+}
+
+/// The lines of a puzzle input, kept around so errors can quote them.
+pub struct InputFile {
+    pub(crate) name: String,
+    pub(crate) lines: Vec<String>,
+    /// How many lines came before these, for inputs that are read a piece at a time.
+    pub(crate) offset: usize,
+}
+
+impl InputFile {
+    /// Reads a file, or standard input when `file_name` is `-`.
+    pub fn open(file_name: &str) -> Result<Self, ParseError> {
+        if file_name == STDIN {
+            return read_stdin()
+                .as_ref()
+                .map(|text| Self::from_text("<stdin>", text))
+                .map_err(|e| ParseError::for_file("<stdin>", "readable input", e.clone()));
+        }
+
+        let text = std::fs::read_to_string(file_name)
+            .map_err(|e| ParseError::for_file(file_name, "a readable file", e.to_string()))?;
+        Ok(Self::from_text(file_name, &text))
+    }
+
+    pub fn from_text(name: &str, text: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            lines: text.lines().map(String::from).collect(),
+            offset: 0,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = Span<'_>> {
+        (0..self.lines.len()).map(move |i| self.line(i))
+    }
+
+    /// The first line, for inputs that are a single line long.
+    pub fn first_line(&self) -> Result<Span<'_>, ParseError> {
+        if self.lines.is_empty() {
+            return Err(self.error("at least one line", "an empty file"));
+        }
+        Ok(self.line(0))
+    }
+
+    /// Groups of lines separated by blank lines, skipping runs of blank lines.
+    pub fn chunks(&self) -> Vec<Vec<Span<'_>>> {
+        let mut chunks = vec![Vec::new()];
+        for line in self.lines() {
+            if line.as_str().trim().is_empty() {
+                if !chunks.last().unwrap().is_empty() {
+                    chunks.push(Vec::new());
+                }
+            } else {
+                chunks.last_mut().unwrap().push(line);
+            }
+        }
+        if chunks.last().unwrap().is_empty() {
+            chunks.pop();
+        }
+        chunks
+    }
+
+    /// An error about the file as a whole, e.g. one that is missing a section.
+    pub fn error(&self, expected: &str, found: &str) -> ParseError {
+        ParseError::for_file(&self.name, expected, found.to_string())
+    }
+
+    pub(crate) fn line(&self, i: usize) -> Span<'_> {
+        Span {
+            file: self,
+            line: i,
+            start: 0,
+            text: &self.lines[i],
+        }
+    }
+}
+
+/// Standard input can only be read once, so keep it around for every part (and every
+/// benchmark iteration) that parses it.
+pub(crate) fn read_stdin() -> &'static Result<String, String> {
+    static TEXT: OnceLock<Result<String, String>> = OnceLock::new();
+    TEXT.get_or_init(|| std::io::read_to_string(std::io::stdin()).map_err(|e| e.to_string()))
+}
+
+/// A piece of a single input line that knows where it came from.
+#[derive(Clone, Copy)]
+pub struct Span<'a> {
+    file: &'a InputFile,
+    line: usize,
+    start: usize,
+    text: &'a str,
+}
+
+impl<'a> Span<'a> {
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    /// 1-based line number within the file.
+    pub fn line_number(&self) -> usize {
+        self.file.offset + self.line + 1
+    }
+
+    /// 1-based column of the start of the span.
+    pub fn column(&self) -> usize {
+        self.file.lines[self.line][..self.start].chars().count() + 1
+    }
+
+    pub fn error(&self, expected: &str) -> ParseError {
+        let found = if self.text.is_empty() {
+            String::from("end of line")
+        } else {
+            format!("`{}`", self.text)
+        };
+        ParseError {
+            file_name: self.file.name.clone(),
+            line: self.line_number(),
+            column: self.column(),
+            expected: expected.to_string(),
+            found,
+            source_line: self.file.lines[self.line].clone(),
+            width: self.text.chars().count(),
+        }
+    }
+
+    /// An error for something that should have come after this span.
+    pub fn missing(&self, expected: &str) -> ParseError {
+        self.sub(&self.text[self.text.len()..]).error(expected)
+    }
+
+    pub fn parse<T: FromStr>(&self, expected: &str) -> Result<T, ParseError> {
+        self.text.parse().map_err(|_| self.error(expected))
+    }
+
+    pub fn parse_with<T, F>(&self, expected: &str, f: F) -> Result<T, ParseError>
+    where
+        F: FnOnce(&'a str) -> Option<T>,
+    {
+        f(self.text).ok_or_else(|| self.error(expected))
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        self.sub(self.text.trim())
+    }
+
+    pub fn split(&self, pat: char) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+        self.text.split(pat).map(move |s| span.sub(s))
+    }
+
+    pub fn split_str(&self, pat: &'a str) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+        self.text.split(pat).map(move |s| span.sub(s))
+    }
+
+    pub fn split_whitespace(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+        self.text.split_whitespace().map(move |s| span.sub(s))
+    }
+
+    pub fn split_once(&self, pat: &str) -> Result<(Span<'a>, Span<'a>), ParseError> {
+        match self.text.split_once(pat) {
+            Some((a, b)) => Ok((self.sub(a), self.sub(b))),
+            None => Err(self.error(&format!("`{}`", pat))),
+        }
+    }
+
+    pub fn strip_prefix(&self, prefix: &str) -> Result<Span<'a>, ParseError> {
+        match self.text.strip_prefix(prefix) {
+            Some(rest) => Ok(self.sub(rest)),
+            None => Err(self.error(&format!("`{}`", prefix))),
+        }
+    }
+
+    pub fn strip_suffix(&self, suffix: &str) -> Result<Span<'a>, ParseError> {
+        match self.text.strip_suffix(suffix) {
+            Some(rest) => Ok(self.sub(rest)),
+            None => Err(self.error(&format!("`{}`", suffix))),
+        }
+    }
+
+    /// The span covering `len` bytes starting `offset` bytes in.
+    pub fn slice(&self, offset: usize, len: usize) -> Span<'a> {
+        self.sub(&self.text[offset..offset + len])
+    }
+
+    /// Each char paired with a single-char span pointing at it.
+    pub fn chars(&self) -> impl Iterator<Item = (char, Span<'a>)> + 'a {
+        let span = *self;
+        self.text
+            .char_indices()
+            .map(move |(i, c)| (c, span.slice(i, c.len_utf8())))
+    }
+
+    /// Take the next piece from a split, or point at the end of this span if there isn't one.
+    pub fn next_from<I>(&self, parts: &mut I, expected: &str) -> Result<Span<'a>, ParseError>
+    where
+        I: Iterator<Item = Span<'a>>,
+    {
+        parts.next().ok_or_else(|| self.missing(expected))
+    }
+
+    /// Errors if a split has anything left over.
+    pub fn expect_end<I>(&self, parts: &mut I) -> Result<(), ParseError>
+    where
+        I: Iterator<Item = Span<'a>>,
+    {
+        match parts.next() {
+            Some(extra) => Err(extra.error("end of line")),
+            None => Ok(()),
+        }
+    }
+
+    fn sub(&self, s: &'a str) -> Span<'a> {
+        let offset = s.as_ptr() as usize - self.text.as_ptr() as usize;
+        debug_assert!(offset + s.len() <= self.text.len());
+        Span {
+            file: self.file,
+            line: self.line,
+            start: self.start + offset,
+            text: s,
+        }
+    }
+}
+
+/// `1,2,3` style lists of numbers.
+pub fn comma_separated<T: FromStr>(span: Span, expected: &str) -> Result<Vec<T>, ParseError> {
+    span.split(',').map(|n| n.trim().parse(expected)).collect()
+}
+
+/// Rows of single digits, all of the same width.
+pub fn digit_grid<'a, I>(lines: I) -> Result<Grid<u8>, ParseError>
+where
+    I: IntoIterator<Item = Span<'a>>,
+{
+    char_grid(lines, "a digit", |c| c.to_digit(10).map(|d| d as u8))
+}
+
+/// Rows of cells that are one char each, all of the same width.
+pub fn char_grid<'a, I, T, F>(lines: I, expected: &str, f: F) -> Result<Grid<T>, ParseError>
+where
+    I: IntoIterator<Item = Span<'a>>,
+    F: Fn(char) -> Option<T>,
+{
+    let mut rows: Vec<Vec<T>> = Vec::new();
+    for line in lines {
+        let row = line
+            .chars()
+            .map(|(c, span)| f(c).ok_or_else(|| span.error(expected)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(first) = rows.first() {
+            if row.len() != first.len() {
+                return Err(line.error(&format!("a row of {} cells", first.len())));
+            }
+        }
+        rows.push(row);
+    }
+    Ok(Grid::from_rows(rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(text: &str) -> InputFile {
+        InputFile::from_text("test.txt", text)
+    }
+
+    #[test]
+    fn test_error_report() {
+        let input = file("forward 5\ndown x");
+        let line = input.lines().nth(1).unwrap();
+        let (_, distance) = line.split_once(" ").unwrap();
+        let error = distance.parse::<i32>("a distance").unwrap_err();
+
+        assert_eq!(2, error.line);
+        assert_eq!(6, error.column);
+        let expected = "\
+error: expected a distance, found `x`
+  --> test.txt:2:6
+  |
+2 | down x
+  |      ^";
+        assert_eq!(expected, error.to_string());
+    }
+
+    #[test]
+    fn test_missing() {
+        let input = file("forward");
+        let line = input.first_line().unwrap();
+        let mut parts = line.split_whitespace();
+        line.next_from(&mut parts, "a direction").unwrap();
+        let error = line.next_from(&mut parts, "a distance").err().unwrap();
+        assert_eq!(8, error.column);
+        assert_eq!("end of line", error.found);
+    }
+
+    #[test]
+    fn test_open_missing_file() {
+        let error = InputFile::open("does_not_exist.txt").err().unwrap();
+        assert_eq!(0, error.line);
+        assert!(error.to_string().contains("does_not_exist.txt"));
+    }
+
+    #[test]
+    fn test_chunks() {
+        let input = file("a\nb\n\n\nc\n\n");
+        let chunks = input.chunks();
+        assert_eq!(2, chunks.len());
+        assert_eq!(
+            vec!["a", "b"],
+            chunks[0].iter().map(|s| s.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(5, chunks[1][0].line_number());
+    }
+
+    #[test]
+    fn test_comma_separated() {
+        let input = file("3,4, 5,x");
+        let line = input.first_line().unwrap();
+        let error = comma_separated::<i32>(line, "a number").unwrap_err();
+        assert_eq!(8, error.column);
+
+        let input = file("3,4, 5");
+        let line = input.first_line().unwrap();
+        assert_eq!(
+            vec![3, 4, 5],
+            comma_separated::<i32>(line, "a number").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_digit_grid() {
+        let input = file("123\n456");
+        assert_eq!(
+            Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]),
+            digit_grid(input.lines()).unwrap()
+        );
+
+        let input = file("123\n4a6");
+        let error = digit_grid(input.lines()).unwrap_err();
+        assert_eq!((2, 2), (error.line, error.column));
+
+        let input = file("123\n45");
+        let error = digit_grid(input.lines()).unwrap_err();
+        assert_eq!("a row of 3 cells", error.expected);
+    }
+}
@@ -0,0 +1,154 @@
+use std::ops::RangeInclusive;
+
+/// A small seeded random number generator (SplitMix64), good enough for generating
+/// test inputs and simulations. The same seed always gives the same numbers.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "can't pick a number below zero");
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// An index into something `len` long.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "can't pick from an empty range");
+        let span = end.wrapping_sub(start) as u64;
+        match span.checked_add(1) {
+            Some(n) => start.wrapping_add(self.below(n) as i64),
+            None => self.next_u64() as i64,
+        }
+    }
+
+    /// A number in `0.0..1.0`.
+    pub fn f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.f64() < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+/// Runs a property `cases` times, each with its own generator. The seeds are fixed by
+/// the property's name, so a failure is reproducible, and the failing seed is printed
+/// so that one case can be rerun on its own with `AOC_CHECK_SEED`.
+pub fn check<F: FnMut(&mut Rng)>(name: &str, cases: usize, mut property: F) {
+    let seeds = match std::env::var("AOC_CHECK_SEED") {
+        Ok(seed) => vec![seed
+            .parse()
+            .unwrap_or_else(|_| panic!("AOC_CHECK_SEED `{}` isn't a number", seed))],
+        Err(_) => {
+            let mut seeds = Rng::new(fnv1a(name));
+            (0..cases).map(|_| seeds.next_u64()).collect()
+        }
+    };
+
+    for seed in seeds {
+        let _report = Report { name, seed };
+        property(&mut Rng::new(seed));
+    }
+}
+
+/// Names the failing case if a property panics.
+struct Report<'a> {
+    name: &'a str,
+    seed: u64,
+}
+
+impl Drop for Report<'_> {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            eprintln!(
+                "property `{}` failed, rerun it with AOC_CHECK_SEED={}",
+                self.name, self.seed
+            );
+        }
+    }
+}
+
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeatable() {
+        let first = (0..5).map(|_| Rng::new(7).next_u64()).collect::<Vec<_>>();
+        assert!(first.iter().all(|&n| n == first[0]));
+
+        let mut rng = Rng::new(7);
+        let numbers = (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>();
+        assert_eq!(first[0], numbers[0]);
+        assert_ne!(numbers[0], numbers[1]);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut rng = Rng::new(11);
+        let mut seen = [false; 7];
+        for _ in 0..1000 {
+            let n = rng.range(-3..=3);
+            assert!((-3..=3).contains(&n));
+            seen[(n + 3) as usize] = true;
+            assert!(rng.below(10) < 10);
+            assert!((0.0..1.0).contains(&rng.f64()));
+        }
+        assert!(seen.iter().all(|&s| s));
+        assert_eq!(5, rng.range(5..=5));
+        rng.range(i64::MIN..=i64::MAX);
+
+        let mut items = (0..20).collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        assert_ne!((0..20).collect::<Vec<_>>(), items);
+        items.sort_unstable();
+        assert_eq!((0..20).collect::<Vec<_>>(), items);
+    }
+
+    #[test]
+    fn test_check() {
+        let mut seeds = Vec::new();
+        check("collects seeds", 10, |rng| seeds.push(rng.next_u64()));
+        let mut again = Vec::new();
+        check("collects seeds", 10, |rng| again.push(rng.next_u64()));
+        assert_eq!(10, seeds.len());
+        assert_eq!(seeds, again);
+    }
+}
@@ -0,0 +1,243 @@
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap},
+    hash::Hash,
+    ops::Add,
+};
+
+/// A graph to find the cheapest path through, from some start state to any goal state.
+pub trait SearchProblem {
+    type State: Clone + Eq + Hash;
+    type Cost: Copy + Ord + Default + Add<Output = Self::Cost>;
+
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    /// The states reachable in one move from `state`, with the cost of each move.
+    fn neighbors(&self, state: &Self::State) -> Vec<(Self::State, Self::Cost)>;
+
+    /// A lower bound on the cost from `state` to the nearest goal, used by `astar`.
+    /// It must never overestimate, or A* can return a path that isn't the cheapest.
+    fn heuristic(&self, _state: &Self::State) -> Self::Cost {
+        Self::Cost::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<S, C> {
+    pub cost: C,
+    /// Every state from the start to the goal, inclusive.
+    pub path: Vec<S>,
+    pub stats: SearchStats,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// States popped off the frontier and had their neighbours explored.
+    pub expanded: usize,
+    /// States pushed onto the frontier, counting re-pushes after finding a cheaper route.
+    pub pushed: usize,
+}
+
+/// Cheapest path from `start` to a goal, ignoring the problem's heuristic.
+pub fn dijkstra<P: SearchProblem>(
+    problem: &P,
+    start: P::State,
+) -> Option<SearchResult<P::State, P::Cost>> {
+    search(problem, start, |_| P::Cost::default())
+}
+
+/// Cheapest path from `start` to a goal, guided by the problem's heuristic.
+pub fn astar<P: SearchProblem>(
+    problem: &P,
+    start: P::State,
+) -> Option<SearchResult<P::State, P::Cost>> {
+    search(problem, start, |state| problem.heuristic(state))
+}
+
+fn search<P, H>(
+    problem: &P,
+    start: P::State,
+    heuristic: H,
+) -> Option<SearchResult<P::State, P::Cost>>
+where
+    P: SearchProblem,
+    H: Fn(&P::State) -> P::Cost,
+{
+    let mut stats = SearchStats::default();
+    let mut best: BestRoutes<P::State, P::Cost> = HashMap::new();
+    let mut frontier = BinaryHeap::new();
+
+    best.insert(start.clone(), (P::Cost::default(), None));
+    frontier.push(Frontier {
+        priority: heuristic(&start),
+        cost: P::Cost::default(),
+        order: 0,
+        state: start,
+    });
+    stats.pushed += 1;
+
+    while let Some(Frontier { cost, state, .. }) = frontier.pop() {
+        if cost > best[&state].0 {
+            // a cheaper route to this state was found after this one was pushed
+            continue;
+        }
+
+        if problem.is_goal(&state) {
+            let path = reconstruct_path(&best, state);
+            return Some(SearchResult { cost, path, stats });
+        }
+
+        stats.expanded += 1;
+        for (next, step_cost) in problem.neighbors(&state) {
+            let next_cost = cost + step_cost;
+            match best.entry(next.clone()) {
+                Entry::Occupied(entry) if entry.get().0 <= next_cost => continue,
+                Entry::Occupied(mut entry) => {
+                    entry.insert((next_cost, Some(state.clone())));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((next_cost, Some(state.clone())));
+                }
+            }
+
+            frontier.push(Frontier {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                order: stats.pushed,
+                state: next,
+            });
+            stats.pushed += 1;
+        }
+    }
+
+    None
+}
+
+/// The best known cost to each state, and the state it was reached from.
+type BestRoutes<S, C> = HashMap<S, (C, Option<S>)>;
+
+fn reconstruct_path<S, C>(best: &BestRoutes<S, C>, goal: S) -> Vec<S>
+where
+    S: Clone + Eq + Hash,
+{
+    let mut path = vec![goal];
+    while let Some((_, Some(previous))) = best.get(path.last().unwrap()) {
+        path.push(previous.clone());
+    }
+    path.reverse();
+    path
+}
+
+struct Frontier<S, C> {
+    priority: C,
+    cost: C,
+    /// Push order, so ties pop first-in-first-out without needing `S: Ord`.
+    order: usize,
+    state: S,
+}
+
+// BinaryHeap is a max-heap, so flip the comparison to pop the lowest priority first.
+impl<S, C: Ord> Ord for Frontier<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Frontier<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Frontier<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S, C: Ord> Eq for Frontier<S, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Walking along a number line, where a step costs 1 and a jump of 5 costs 3.
+    struct NumberLine {
+        goal: i32,
+    }
+
+    impl SearchProblem for NumberLine {
+        type State = i32;
+        type Cost = u32;
+
+        fn is_goal(&self, state: &i32) -> bool {
+            *state == self.goal
+        }
+
+        fn neighbors(&self, &state: &i32) -> Vec<(i32, u32)> {
+            vec![(state - 1, 1), (state + 1, 1), (state + 5, 3)]
+        }
+
+        fn heuristic(&self, &state: &i32) -> u32 {
+            // a jump covers at most 5 for 3, so this never overestimates
+            ((self.goal - state).unsigned_abs() * 3) / 5
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let result = dijkstra(&NumberLine { goal: 12 }, 0).unwrap();
+        assert_eq!(8, result.cost);
+        assert_eq!(0, result.path[0]);
+        assert_eq!(12, *result.path.last().unwrap());
+        assert_eq!(5, result.path.len());
+    }
+
+    #[test]
+    fn test_astar_matches_dijkstra() {
+        for goal in -7..30 {
+            let problem = NumberLine { goal };
+            let plain = dijkstra(&problem, 0).unwrap();
+            let guided = astar(&problem, 0).unwrap();
+            assert_eq!(plain.cost, guided.cost);
+            assert!(guided.stats.expanded <= plain.stats.expanded);
+        }
+    }
+
+    #[test]
+    fn test_start_is_goal() {
+        let result = dijkstra(&NumberLine { goal: 0 }, 0).unwrap();
+        assert_eq!(0, result.cost);
+        assert_eq!(vec![0], result.path);
+        assert_eq!(
+            SearchStats {
+                expanded: 0,
+                pushed: 1
+            },
+            result.stats
+        );
+    }
+
+    #[test]
+    fn test_unreachable() {
+        struct Stuck;
+
+        impl SearchProblem for Stuck {
+            type State = u8;
+            type Cost = u8;
+
+            fn is_goal(&self, _: &u8) -> bool {
+                false
+            }
+
+            fn neighbors(&self, &state: &u8) -> Vec<(u8, u8)> {
+                (state < 3).then(|| (state + 1, 1)).into_iter().collect()
+            }
+        }
+
+        assert_eq!(None, dijkstra(&Stuck, 0));
+    }
+}
@@ -0,0 +1,279 @@
+use crate::{input::InputSource, parallel, trace, ParseError};
+use std::{
+    fmt::Display,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// A single day's puzzle, split into parsing the input file and solving each part.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(file_name: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+
+    /// Solves both parts in one pass, reading the input a line at a time with a
+    /// `LineReader`, for the days that don't need all of it in memory at once. The
+    /// rest keep this default, which says they can't.
+    fn stream(_file_name: &str) -> Option<Streamed<Self>> {
+        None
+    }
+
+    /// Parses and solves part one, panicking with the parse report on bad input.
+    fn solve_part1(file_name: &str) -> Self::Part1 {
+        Self::part1(&parse_or_panic::<Self>(file_name))
+    }
+
+    /// Parses and solves part two, panicking with the parse report on bad input.
+    fn solve_part2(file_name: &str) -> Self::Part2 {
+        Self::part2(&parse_or_panic::<Self>(file_name))
+    }
+}
+
+/// Both parts' answers from `Solution::stream`, or why the input couldn't be read.
+pub type Streamed<S> = Result<(<S as Solution>::Part1, <S as Solution>::Part2), ParseError>;
+
+fn parse_or_panic<S: Solution + ?Sized>(file_name: &str) -> S::Input {
+    S::parse(file_name).unwrap_or_else(|e| panic!("\n{}", e))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Answer {
+    pub value: String,
+    /// Time spent reading and parsing the input file.
+    pub parse_time: Duration,
+    /// Time spent in `part1` or `part2` once the input was parsed.
+    pub solve_time: Duration,
+}
+
+impl Answer {
+    pub fn elapsed(&self) -> Duration {
+        self.parse_time + self.solve_time
+    }
+}
+
+/// Object-safe view of a `Solution`, so the runner can hold every day in one list.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+    fn run(&self, part: Part, file_name: &str) -> Result<Answer, ParseError>;
+    /// Both parts' answers from `Solution::stream`, if the day has it. Reading and
+    /// solving happen together, so all of the time counts as parsing.
+    fn run_stream(&self, file_name: &str) -> Option<Result<[Answer; 2], ParseError>>;
+}
+
+impl<S: Solution + Sync> Puzzle for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn run(&self, part: Part, file_name: &str) -> Result<Answer, ParseError> {
+        let start = Instant::now();
+        let input = S::parse(file_name)?;
+        let parsed = Instant::now();
+        let value = trace::with_context(S::DAY, part, || match part {
+            Part::One => S::part1(&input).to_string(),
+            Part::Two => S::part2(&input).to_string(),
+        });
+        Ok(Answer {
+            value,
+            parse_time: parsed - start,
+            solve_time: Instant::now() - parsed,
+        })
+    }
+
+    fn run_stream(&self, file_name: &str) -> Option<Result<[Answer; 2], ParseError>> {
+        let start = Instant::now();
+        let answers = S::stream(file_name)?;
+        let elapsed = start.elapsed();
+        let answer = |value: String| Answer {
+            value,
+            parse_time: elapsed,
+            solve_time: Duration::ZERO,
+        };
+        Some(answers.map(|(one, two)| [answer(one.to_string()), answer(two.to_string())]))
+    }
+}
+
+/// Answer for a part that hasn't been solved yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsolved;
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unsolved")
+    }
+}
+
+/// Entry point for the per-day binaries: solves both parts of the file given as the
+/// first argument (`-` for stdin), or the day's input from `InputSource::from_env`,
+/// and prints the answers. Days that can are solved a line at a time, so their
+/// inputs can be bigger than memory. Tracing is set up from the environment, see
+/// `trace::init_from_env`.
+pub fn run_main<P: Puzzle>(puzzle: &P) {
+    if let Err(e) = trace::init_from_env().and_then(|_| parallel::init_from_env()) {
+        eprintln!("{}", e);
+        std::process::exit(2);
+    }
+
+    let file_name = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| resolve_or_exit(puzzle.day()));
+
+    let answers = match puzzle.run_stream(&file_name) {
+        Some(answers) => answers.map(Vec::from),
+        None => [Part::One, Part::Two]
+            .iter()
+            .map(|&part| puzzle.run(part, &file_name))
+            .collect(),
+    };
+    match answers {
+        Ok(answers) => {
+            for (answer, label) in answers.iter().zip(["one", "two"]) {
+                println!(
+                    "Answer {}: {} ({:?})",
+                    label,
+                    answer.value,
+                    answer.elapsed()
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// The path to a day's input from `InputSource::from_env`, or exits with the reason
+/// it couldn't be found.
+pub fn resolve_or_exit(day: u8) -> String {
+    match InputSource::from_env().resolve(day) {
+        Ok(path) => path.to_string_lossy().into_owned(),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Whether a subcommand's argument names its input file: `-` for stdin, or anything
+/// that isn't a flag.
+pub fn is_file_arg(arg: &str) -> bool {
+    arg == "-" || !arg.starts_with('-')
+}
+
+/// Parses the value of a subcommand's `flag`, with an error message for the usage.
+pub fn parse_number<T: FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} must be a number, not `{}`", flag, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::LineReader;
+
+    struct Doubler;
+
+    impl Solution for Doubler {
+        const DAY: u8 = 42;
+
+        type Input = Vec<i32>;
+        type Part1 = i32;
+        type Part2 = Unsolved;
+
+        fn parse(file_name: &str) -> Result<Vec<i32>, ParseError> {
+            let input = crate::parse::InputFile::from_text("test", file_name);
+            crate::parse::comma_separated(input.first_line()?, "a number")
+        }
+
+        fn part1(input: &Vec<i32>) -> i32 {
+            input.iter().map(|n| n * 2).sum()
+        }
+
+        fn part2(_: &Vec<i32>) -> Unsolved {
+            Unsolved
+        }
+    }
+
+    /// Sums a line at a time, and counts the lines. Its "file names" are the text.
+    struct Streamer;
+
+    impl Solution for Streamer {
+        const DAY: u8 = 43;
+
+        type Input = ();
+        type Part1 = i32;
+        type Part2 = usize;
+
+        fn parse(_: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part1(_: &()) -> i32 {
+            0
+        }
+
+        fn part2(_: &()) -> usize {
+            0
+        }
+
+        fn stream(file_name: &str) -> Option<Result<(i32, usize), ParseError>> {
+            let text = file_name.replace(',', "\n");
+            let lines = LineReader::new("test", text.as_bytes());
+            let numbers = lines
+                .parse_lines(|line| line.parse("a number"))
+                .collect::<Result<Vec<i32>, _>>();
+            Some(numbers.map(|numbers| (numbers.iter().sum(), numbers.len())))
+        }
+    }
+
+    #[test]
+    fn test_puzzle_run() {
+        let puzzle: &dyn Puzzle = &Doubler;
+        assert_eq!(42, puzzle.day());
+        assert_eq!("12", puzzle.run(Part::One, "1,2,3").unwrap().value);
+        assert_eq!("unsolved", puzzle.run(Part::Two, "1,2,3").unwrap().value);
+        assert!(puzzle.run(Part::One, "1,x,3").is_err());
+    }
+
+    #[test]
+    fn test_run_stream() {
+        assert!(Doubler.run_stream("1,2,3").is_none());
+        let [one, two] = Streamer.run_stream("1,2,3").unwrap().unwrap();
+        assert_eq!(("6", "3"), (one.value.as_str(), two.value.as_str()));
+        assert_eq!(Duration::ZERO, one.solve_time);
+        assert!(Streamer.run_stream("1,x,3").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_subcommand_args() {
+        assert!(is_file_arg("input.txt") && is_file_arg("-") && !is_file_arg("--seed"));
+        assert_eq!(Ok(7u64), parse_number("--seed", "7"));
+        assert_eq!(
+            Err(String::from("--seed must be a number, not `x`")),
+            parse_number::<u64>("--seed", "x")
+        );
+    }
+}
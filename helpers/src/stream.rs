@@ -0,0 +1,180 @@
+use crate::parse::{read_stdin, InputFile, Span, STDIN};
+use crate::ParseError;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+};
+
+/// Reads an input a line at a time from any `BufRead`: a file, stdin, or text that's
+/// already in memory. Only the current line is kept, so inputs of any size can be
+/// parsed in constant memory, and errors still point at the right line.
+pub struct LineReader<R> {
+    reader: R,
+    /// Holds the current line, so spans into it work just like spans into a whole file.
+    current: InputFile,
+    lines_read: usize,
+    done: bool,
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(name: &str, reader: R) -> Self {
+        Self {
+            reader,
+            current: InputFile {
+                name: name.to_string(),
+                lines: vec![String::new()],
+                offset: 0,
+            },
+            lines_read: 0,
+            done: false,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.current.name
+    }
+
+    /// Reads the next line, returning `None` at the end of the input.
+    pub fn next_line(&mut self) -> Option<Result<Span<'_>, ParseError>> {
+        if self.done {
+            return None;
+        }
+
+        let line = &mut self.current.lines[0];
+        line.clear();
+        match self.reader.read_line(line) {
+            Ok(0) => {
+                self.done = true;
+                None
+            }
+            Ok(_) => {
+                if line.ends_with('\n') {
+                    line.pop();
+                    if line.ends_with('\r') {
+                        line.pop();
+                    }
+                }
+                self.current.offset = self.lines_read;
+                self.lines_read += 1;
+                Some(Ok(self.current.line(0)))
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(ParseError::for_file(
+                    &self.current.name,
+                    "readable input",
+                    e.to_string(),
+                )))
+            }
+        }
+    }
+
+    /// An error about the input as a whole, e.g. one that turned out to be empty.
+    pub fn error(&self, expected: &str, found: &str) -> ParseError {
+        self.current.error(expected, found)
+    }
+
+    /// Parses each line with `f`, as an iterator of the results.
+    pub fn parse_lines<T, F>(self, f: F) -> ParseLines<R, F>
+    where
+        F: FnMut(Span<'_>) -> Result<T, ParseError>,
+    {
+        ParseLines { lines: self, f }
+    }
+}
+
+impl LineReader<Box<dyn BufRead>> {
+    /// Reads a file, or standard input when `file_name` is `-`. Standard input is read
+    /// whole, like `InputFile::open` does, so every part can parse it.
+    pub fn open(file_name: &str) -> Result<Self, ParseError> {
+        if file_name == STDIN {
+            let text = read_stdin()
+                .as_ref()
+                .map_err(|e| ParseError::for_file("<stdin>", "readable input", e.clone()))?;
+            return Ok(Self::new("<stdin>", Box::new(text.as_bytes())));
+        }
+
+        let file = File::open(file_name)
+            .map_err(|e| ParseError::for_file(file_name, "a readable file", e.to_string()))?;
+        Ok(Self::new(file_name, Box::new(BufReader::new(file))))
+    }
+}
+
+/// The lines of a `LineReader`, each parsed into a `T`.
+pub struct ParseLines<R, F> {
+    lines: LineReader<R>,
+    f: F,
+}
+
+impl<R, F, T> Iterator for ParseLines<R, F>
+where
+    R: BufRead,
+    F: FnMut(Span<'_>) -> Result<T, ParseError>,
+{
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let f = &mut self.f;
+        self.lines.next_line().map(|line| line.and_then(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn reader(text: &str) -> LineReader<&[u8]> {
+        LineReader::new("test.txt", text.as_bytes())
+    }
+
+    #[test]
+    fn test_lines() {
+        let mut lines = reader("one\r\n\nthree");
+        let mut seen = Vec::new();
+        while let Some(line) = lines.next_line() {
+            let line = line.unwrap();
+            seen.push((line.line_number(), line.as_str().to_string()));
+        }
+        let expected = [(1, "one"), (2, ""), (3, "three")];
+        assert_eq!(expected.map(|(n, s)| (n, s.to_string())).to_vec(), seen);
+        assert!(lines.next_line().is_none());
+    }
+
+    #[test]
+    fn test_errors_point_at_the_line() {
+        let numbers = reader("1\n2\nx\n4").parse_lines(|line| line.parse::<i32>("a number"));
+        let results = numbers.collect::<Vec<_>>();
+        assert_eq!(4, results.len());
+        let error = results[2].clone().unwrap_err();
+        assert_eq!((3, 1), (error.line, error.column));
+        assert!(error.to_string().contains("3 | x"), "{}", error);
+        assert_eq!(Ok(4), results[3]);
+    }
+
+    /// Gives up part way through, like a dropped connection.
+    struct Broken;
+
+    impl Read for Broken {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("connection reset"))
+        }
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let lines = LineReader::new("broken", BufReader::new(b"1\n".chain(Broken)));
+        let results = lines
+            .parse_lines(|line| line.parse::<i32>("a number"))
+            .collect::<Vec<_>>();
+        assert_eq!(Ok(1), results[0]);
+        let error = results[1].clone().unwrap_err();
+        assert_eq!("connection reset", error.found);
+        assert_eq!(2, results.len());
+
+        assert_eq!(
+            0,
+            LineReader::open("does_not_exist.txt").err().unwrap().line
+        );
+    }
+}
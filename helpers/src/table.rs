@@ -0,0 +1,72 @@
+use std::fmt::Display;
+
+/// Plain-text table with columns padded to their widest cell.
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new<S: ToString>(headers: &[S]) -> Self {
+        Self {
+            headers: headers.iter().map(S::to_string).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        assert_eq!(self.headers.len(), row.len());
+        self.rows.push(row);
+    }
+
+    fn widths(&self) -> Vec<usize> {
+        let mut widths = self.headers.iter().map(|h| h.len()).collect::<Vec<_>>();
+        for row in self.rows.iter() {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = std::cmp::max(*width, cell.chars().count());
+            }
+        }
+        widths
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let widths = self.widths();
+        let write_row = |f: &mut std::fmt::Formatter<'_>, row: &[String]| {
+            let cells = row
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>();
+            writeln!(f, "{}", cells.join(" | ").trim_end())
+        };
+
+        write_row(f, &self.headers)?;
+        let divider = widths.iter().map(|&w| "-".repeat(w)).collect::<Vec<_>>();
+        writeln!(f, "{}", divider.join("-+-"))?;
+        for row in self.rows.iter() {
+            write_row(f, row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        let mut table = Table::new(&["Day", "Answer"]);
+        table.push(vec![String::from("1"), String::from("1521")]);
+        table.push(vec![String::from("23"), String::from("12")]);
+        let expected = "\
+Day | Answer
+----+-------
+1   | 1521
+23  | 12
+";
+        assert_eq!(expected, table.to_string());
+    }
+}
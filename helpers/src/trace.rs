@@ -0,0 +1,326 @@
+use crate::{grid::Grid, Part};
+use std::{
+    cell::Cell,
+    fmt::Write as _,
+    io::Write,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+/// Something a solver reports while it works, e.g. one step of a simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub name: &'static str,
+    pub step: Option<usize>,
+    pub counters: Vec<(&'static str, i64)>,
+    /// A rendering of the solver's state, usually over several lines.
+    pub snapshot: Option<String>,
+    /// The solver's state as an image, from black at 0 to white at 255.
+    pub frame: Option<Grid<u8>>,
+}
+
+impl Event {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            step: None,
+            counters: Vec::new(),
+            snapshot: None,
+            frame: None,
+        }
+    }
+
+    pub fn step(mut self, step: usize) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    pub fn counter(mut self, name: &'static str, value: i64) -> Self {
+        self.counters.push((name, value));
+        self
+    }
+
+    pub fn snapshot<T: std::fmt::Display>(mut self, state: T) -> Self {
+        self.snapshot = Some(state.to_string());
+        self
+    }
+
+    pub fn frame(mut self, frame: Grid<u8>) -> Self {
+        self.frame = Some(frame);
+        self
+    }
+}
+
+/// The day and part being solved when an event was emitted, if it came from inside
+/// `Puzzle::run`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Context {
+    pub day: u8,
+    pub part: Part,
+}
+
+/// Where events go once they've been emitted.
+pub trait Sink: Send {
+    fn record(&mut self, context: Option<Context>, event: &Event);
+}
+
+/// Human-readable lines, with any snapshot printed below its event. Frames are left
+/// out, see `frames::Recorder` for those.
+pub struct Text<W>(pub W);
+
+impl<W: Write + Send> Sink for Text<W> {
+    fn record(&mut self, context: Option<Context>, event: &Event) {
+        let mut line = String::new();
+        if let Some(Context { day, part }) = context {
+            write!(line, "day {:02} part {} ", day, part).unwrap();
+        }
+        line.push_str(event.name);
+        if let Some(step) = event.step {
+            write!(line, " {}", step).unwrap();
+        }
+        for (name, value) in event.counters.iter() {
+            write!(line, " {}={}", name, value).unwrap();
+        }
+        if let Some(snapshot) = &event.snapshot {
+            write!(line, "\n{}", snapshot.trim_end()).unwrap();
+        }
+        // tracing is best effort, it shouldn't stop a solver
+        let _ = writeln!(self.0, "{}", line);
+    }
+}
+
+/// One JSON object per event, per line.
+pub struct JsonLines<W>(pub W);
+
+impl<W: Write + Send> Sink for JsonLines<W> {
+    fn record(&mut self, context: Option<Context>, event: &Event) {
+        let mut json = String::from("{");
+        if let Some(Context { day, part }) = context {
+            write!(json, "\"day\": {}, \"part\": {}, ", day, part).unwrap();
+        }
+        write!(json, "\"event\": {}", json_string(event.name)).unwrap();
+        if let Some(step) = event.step {
+            write!(json, ", \"step\": {}", step).unwrap();
+        }
+        json.push_str(", \"counters\": {");
+        for (i, (name, value)) in event.counters.iter().enumerate() {
+            let separator = if i == 0 { "" } else { ", " };
+            write!(json, "{}{}: {}", separator, json_string(name), value).unwrap();
+        }
+        json.push('}');
+        if let Some(snapshot) = &event.snapshot {
+            write!(json, ", \"snapshot\": {}", json_string(snapshot)).unwrap();
+        }
+        json.push('}');
+        let _ = writeln!(self.0, "{}", json);
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// The sinks that can be picked by name, e.g. from `AOC_TRACE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Silent,
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "" | "off" | "silent" => Ok(Format::Silent),
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "`{}` isn't a trace format, use off, text or json",
+                s
+            )),
+        }
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SINK: Mutex<Option<Box<dyn Sink>>> = Mutex::new(None);
+
+thread_local! {
+    static CONTEXT: Cell<Option<Context>> = const { Cell::new(None) };
+}
+
+/// Sends events to `sink` from now on, or drops them if it's `None`.
+pub fn set_sink(sink: Option<Box<dyn Sink>>) {
+    let mut current = SINK.lock().unwrap();
+    ENABLED.store(sink.is_some(), Ordering::Relaxed);
+    *current = sink;
+}
+
+/// Picks a sink from the environment: `AOC_TRACE` is off, text or json, and events
+/// go to stderr unless `AOC_TRACE_FILE` names a file to write them to.
+pub fn init_from_env() -> Result<(), String> {
+    let format = std::env::var("AOC_TRACE")
+        .unwrap_or_default()
+        .parse::<Format>()?;
+    let writer: Box<dyn Write + Send> = match std::env::var_os("AOC_TRACE_FILE") {
+        Some(path) if format != Format::Silent => {
+            let file = std::fs::File::create(&path)
+                .map_err(|e| format!("couldn't create {}: {}", path.to_string_lossy(), e))?;
+            Box::new(std::io::LineWriter::new(file))
+        }
+        _ => Box::new(std::io::stderr()),
+    };
+
+    set_sink(match format {
+        Format::Silent => None,
+        Format::Text => Some(Box::new(Text(writer))),
+        Format::Json => Some(Box::new(JsonLines(writer))),
+    });
+    Ok(())
+}
+
+/// Whether anything is listening, for solvers that need to do extra work to trace.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Reports an event. It's only built if there's a sink to send it to, so snapshots
+/// cost nothing when tracing is off.
+pub fn emit<F: FnOnce() -> Event>(event: F) {
+    if !enabled() {
+        return;
+    }
+    let event = event();
+    let context = CONTEXT.with(Cell::get);
+    if let Some(sink) = SINK.lock().unwrap().as_mut() {
+        sink.record(context, &event);
+    }
+}
+
+/// The day and part events are currently tagged with, so other threads can carry on
+/// with the same tags.
+pub(crate) fn context() -> Option<Context> {
+    CONTEXT.with(Cell::get)
+}
+
+pub(crate) fn set_context(context: Option<Context>) {
+    CONTEXT.with(|c| c.set(context));
+}
+
+/// Runs `f` with events tagged as coming from `day` and `part`.
+pub fn with_context<R, F: FnOnce() -> R>(day: u8, part: Part, f: F) -> R {
+    let _restore = RestoreContext(CONTEXT.with(|c| c.replace(Some(Context { day, part }))));
+    f()
+}
+
+/// Puts back the context `with_context` replaced, even if `f` panics.
+struct RestoreContext(Option<Context>);
+
+impl Drop for RestoreContext {
+    fn drop(&mut self) {
+        set_context(self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Buffer {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    fn event() -> Event {
+        Event::new("step")
+            .step(3)
+            .counter("flashes", 9)
+            .counter("total", -1)
+            .snapshot("12\n\"4\"\n")
+    }
+
+    const CONTEXT: Option<Context> = Some(Context {
+        day: 11,
+        part: Part::Two,
+    });
+
+    #[test]
+    fn test_text() {
+        let buffer = Buffer::default();
+        Text(buffer.clone()).record(CONTEXT, &event());
+        Text(buffer.clone()).record(None, &Event::new("done"));
+        assert_eq!(
+            "day 11 part 2 step 3 flashes=9 total=-1\n12\n\"4\"\ndone\n",
+            buffer.text()
+        );
+    }
+
+    #[test]
+    fn test_json_lines() {
+        let buffer = Buffer::default();
+        JsonLines(buffer.clone()).record(CONTEXT, &event());
+        JsonLines(buffer.clone()).record(None, &Event::new("done"));
+        let expected = "\
+{\"day\": 11, \"part\": 2, \"event\": \"step\", \"step\": 3, \
+\"counters\": {\"flashes\": 9, \"total\": -1}, \"snapshot\": \"12\\n\\\"4\\\"\\n\"}
+{\"event\": \"done\", \"counters\": {}}
+";
+        assert_eq!(expected, buffer.text());
+    }
+
+    #[test]
+    fn test_context_survives_panics() {
+        let result = std::panic::catch_unwind(|| with_context(4, Part::One, || panic!("oops")));
+        assert!(result.is_err());
+        assert_eq!(None, context());
+    }
+
+    #[test]
+    fn test_emit() {
+        // the only test that touches the global sink, so nothing else races it
+        emit(|| panic!("built an event with no sink"));
+
+        let buffer = Buffer::default();
+        set_sink(Some(Box::new(Text(buffer.clone()))));
+        with_context(11, Part::Two, || emit(|| Event::new("inside")));
+        emit(|| Event::new("outside"));
+        set_sink(None);
+        emit(|| Event::new("dropped"));
+
+        assert_eq!("day 11 part 2 inside\noutside\n", buffer.text());
+        assert_eq!(Ok(Format::Json), "json".parse());
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}